@@ -4,10 +4,10 @@ use evian::prelude::*;
 use vexide::prelude::*;
 
 use evian::{
-    control::loops::{AngularPid, Pid},
+    control::loops::{AngularPid, Pid, ZeroFeedback},
     drivetrain::model::{Arcade, Differential},
     motion::{Basic, Seeking},
-    tracking::wheeled::{TrackingWheel, WheeledTracking},
+    tracking::wheeled::{GearRatio, TrackingWheel, WheeledTracking},
 };
 
 struct Robot {
@@ -32,8 +32,8 @@ impl Compete for Robot {
     async fn autonomous(&mut self) {
         let dt = &mut self.drivetrain;
         let mut seeking = Seeking {
-            linear_controller: Pid::new(0.0, 0.0, 0.0, None),
-            lateral_controller: Pid::new(0.0, 0.0, 0.0, None),
+            linear_controller: ZeroFeedback,
+            lateral_controller: ZeroFeedback,
             tolerances: Self::LINEAR_TOLERANCES,
             timeout: Some(Duration::from_secs(10)),
         };
@@ -75,7 +75,11 @@ impl Compete for Robot {
                 .drivetrain
                 .model
                 .drive_arcade(state.left_stick.y(), state.left_stick.x());
-            println!("{}", self.drivetrain.tracking.position());
+            println!(
+                "{} {:.1}",
+                self.drivetrain.tracking.position(),
+                self.drivetrain.tracking.heading().display_degrees()
+            );
 
             sleep(Motor::WRITE_INTERVAL).await;
         }
@@ -99,20 +103,25 @@ async fn main(peripherals: Peripherals) {
         Motor::new(peripherals.port_20, Gearset::Blue, Direction::Forward),
     ];
 
-    let mut imu = InertialSensor::new(peripherals.port_15);
-    imu.calibrate().await.unwrap();
-
-    Robot {
-        drivetrain: Drivetrain::new(
-            Differential::new(left_motors, right_motors),
+    // Calibrates the IMU before creating the tracking system that uses it.
+    let drivetrain = Drivetrain::new_calibrated(
+        Differential::new(left_motors, right_motors, 3.25, 12.0),
+        InertialSensor::new(peripherals.port_15),
+        |imu| {
             WheeledTracking::new(
                 (0.0, 0.0),
                 90.0.deg(),
-                [TrackingWheel::new(forwards_enc, 2.0, 0.0, None)],
-                [TrackingWheel::new(sideways_enc, 2.0, 0.0, None)],
+                [TrackingWheel::new(forwards_enc, 2.0, 0.0, GearRatio::DIRECT)],
+                [TrackingWheel::new(sideways_enc, 2.0, 0.0, GearRatio::DIRECT)],
                 Some(imu),
-            ),
-        ),
+            )
+        },
+    )
+    .await
+    .unwrap();
+
+    Robot {
+        drivetrain,
         controller: peripherals.primary_controller,
     }
     .compete()
@@ -5,6 +5,9 @@
 //! mechanism that requires precise motion control.
 
 pub mod loops;
+pub mod profile;
 
 mod tolerances;
-pub use tolerances::Tolerances;
+pub use tolerances::{
+    ErrorToleranceMode, PoseToleranceCheck, PoseTolerances, ToleranceCheck, Tolerances,
+};
@@ -3,15 +3,75 @@ use std::time::Duration;
 use super::{Feedback};
 
 /// Bang-bang controller.
+///
+/// A bang-bang controller outputs its full magnitude whenever the measurement is below the
+/// setpoint, and nothing otherwise. This works well for systems such as flywheels that only
+/// need to be driven in one direction and coast down on their own.
+///
+/// `BangBang` implements [`Feedback`] with both its [state](Feedback::State) and
+/// [signal](Feedback::Signal) as `f64`, so it can be used anywhere a feedback controller is
+/// expected.
+///
+/// # Readiness
+///
+/// The controller keeps track of the error and output from its most recent update. A tolerance
+/// can be set with [`with_tolerance`](BangBang::with_tolerance) so that
+/// [`at_target`](BangBang::at_target) reports when the system is close enough to its setpoint,
+/// such as for a flywheel "ready to fire" indicator. The tolerance only affects readiness, and
+/// doesn't change the controller's output.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use evian_control::loops::{BangBang, Feedback};
+///
+/// let mut controller = BangBang::new(1.0).with_tolerance(20.0);
+/// assert!(!controller.at_target());
+///
+/// // Well below the setpoint, the controller outputs full power.
+/// assert_eq!(controller.update(100.0, 400.0, Duration::from_millis(10)), 1.0);
+/// assert_eq!(controller.current_output(), 1.0);
+/// assert!(!controller.at_target());
+///
+/// // Just below the setpoint, the controller is still powering the system, but it's close
+/// // enough to be considered ready.
+/// controller.update(390.0, 400.0, Duration::from_millis(10));
+/// assert_eq!(controller.current_output(), 1.0);
+/// assert!(controller.at_target());
+///
+/// // Overshooting the setpoint cuts power.
+/// controller.update(410.0, 400.0, Duration::from_millis(10));
+/// assert_eq!(controller.current_output(), 0.0);
+/// assert!(controller.at_target());
+/// ```
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct BangBang {
     magnitude: f64,
+    tolerance: f64,
+    prev_error: Option<f64>,
+    output: f64,
 }
 
 impl BangBang {
     /// Creates a new bang-bang controller with a given output magnitude.
     pub fn new(magnitude: f64) -> Self {
-        Self { magnitude }
+        Self {
+            magnitude,
+            tolerance: 0.0,
+            prev_error: None,
+            output: 0.0,
+        }
+    }
+
+    /// Sets the tolerance used by [`at_target`](BangBang::at_target).
+    ///
+    /// Defaults to `0.0`, in which case the controller is only considered to be at its target
+    /// when its most recent update had no error at all.
+    #[must_use]
+    pub const fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
     }
 
     /// Returns the controller's output magnitude.
@@ -23,16 +83,73 @@ impl BangBang {
     pub fn set_magnitude(&mut self, magnitude: f64) {
         self.magnitude = magnitude;
     }
+
+    /// Returns the tolerance used by [`at_target`](BangBang::at_target).
+    pub fn tolerance(&self) -> f64 {
+        self.tolerance
+    }
+
+    /// Sets the tolerance used by [`at_target`](BangBang::at_target).
+    pub fn set_tolerance(&mut self, tolerance: f64) {
+        self.tolerance = tolerance;
+    }
+
+    /// Returns `true` if the error from the controller's most recent update is within its
+    /// [tolerance](BangBang::with_tolerance).
+    ///
+    /// A controller that has yet to be updated is never at its target.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use evian_control::loops::{BangBang, Feedback};
+    ///
+    /// // Without a tolerance, only an exact match counts as being at the target.
+    /// let mut controller = BangBang::new(1.0);
+    /// controller.update(399.0, 400.0, Duration::from_millis(10));
+    /// assert!(!controller.at_target());
+    ///
+    /// controller.update(400.0, 400.0, Duration::from_millis(10));
+    /// assert!(controller.at_target());
+    /// ```
+    pub fn at_target(&self) -> bool {
+        self.at_setpoint(self.tolerance)
+    }
+
+    /// Returns the output from the controller's most recent update.
+    ///
+    /// A controller that has yet to be updated has an output of zero.
+    pub fn current_output(&self) -> f64 {
+        self.output
+    }
 }
 
 impl Feedback for BangBang {
     type State = f64;
     type Signal = f64;
+
     fn update(&mut self, measurement: f64, setpoint: f64, _dt: Duration) -> f64 {
-        if measurement < setpoint {
+        self.prev_error = Some(setpoint - measurement);
+        self.output = if measurement < setpoint {
             self.magnitude
         } else {
             0.0
-        }
+        };
+
+        self.output
+    }
+
+    /// Returns `true` if the error from the controller's most recent update is within
+    /// `tolerance` (inclusive).
+    ///
+    /// A controller that has yet to be updated is never at its setpoint.
+    fn at_setpoint(&self, tolerance: f64) -> bool {
+        self.prev_error.is_some_and(|error| error.abs() <= tolerance)
+    }
+
+    fn reset(&mut self) {
+        self.prev_error = None;
+        self.output = 0.0;
     }
 }
@@ -0,0 +1,143 @@
+use core::{fmt, marker::PhantomData};
+use std::time::Duration;
+
+use super::{Feedback, Feedforward};
+
+/// Feedback controller backed by a closure.
+///
+/// This allows any `FnMut(measurement, setpoint, dt) -> signal` to be used where a [`Feedback`]
+/// controller is expected, which is useful for quickly experimenting with control laws without
+/// defining a new type for them.
+///
+/// Motion algorithms clone their controllers before each motion, so the closure passed to a
+/// motion must implement [`Clone`] (which closures do automatically if everything they capture
+/// does).
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use evian_control::loops::{Feedback, FnFeedback};
+///
+/// // A proportional controller with a minimum output to overcome friction.
+/// let mut controller = FnFeedback::new(|measurement: f64, setpoint: f64, _dt: Duration| {
+///     let output = (setpoint - measurement) * 0.1;
+///     output.signum() * output.abs().max(0.05)
+/// });
+///
+/// assert_eq!(controller.update(0.0, 10.0, Duration::from_millis(10)), 1.0);
+/// assert_eq!(controller.update(9.9, 10.0, Duration::from_millis(10)), 0.05);
+/// ```
+pub struct FnFeedback<F, S, O> {
+    f: F,
+    _marker: PhantomData<fn(S, S, Duration) -> O>,
+}
+
+impl<F, S, O> FnFeedback<F, S, O>
+where
+    F: FnMut(S, S, Duration) -> O,
+{
+    /// Creates a new feedback controller from a closure taking the system's measured state,
+    /// setpoint, and the time since the last update.
+    pub const fn new(f: F) -> Self {
+        Self {
+            f,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F, S, O> Feedback for FnFeedback<F, S, O>
+where
+    F: FnMut(S, S, Duration) -> O,
+{
+    type State = S;
+    type Signal = O;
+
+    fn update(&mut self, measurement: S, setpoint: S, dt: Duration) -> O {
+        (self.f)(measurement, setpoint, dt)
+    }
+}
+
+impl<F: Clone, S, O> Clone for FnFeedback<F, S, O> {
+    fn clone(&self) -> Self {
+        Self {
+            f: self.f.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F: Copy, S, O> Copy for FnFeedback<F, S, O> {}
+
+impl<F, S, O> fmt::Debug for FnFeedback<F, S, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FnFeedback").finish_non_exhaustive()
+    }
+}
+
+/// Feedforward controller backed by a closure.
+///
+/// This allows any `FnMut(setpoint, dt) -> signal` to be used where a [`Feedforward`] controller
+/// is expected.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use evian_control::loops::{Feedforward, FnFeedforward};
+///
+/// // Static friction compensation plus a velocity gain.
+/// let mut controller = FnFeedforward::new(|velocity: f64, _dt: Duration| {
+///     0.5 * velocity.signum() + 0.02 * velocity
+/// });
+///
+/// assert_eq!(controller.update(100.0, Duration::from_millis(10)), 2.5);
+/// ```
+pub struct FnFeedforward<F, S, O> {
+    f: F,
+    _marker: PhantomData<fn(S, Duration) -> O>,
+}
+
+impl<F, S, O> FnFeedforward<F, S, O>
+where
+    F: FnMut(S, Duration) -> O,
+{
+    /// Creates a new feedforward controller from a closure taking the system's setpoint and the
+    /// time since the last update.
+    pub const fn new(f: F) -> Self {
+        Self {
+            f,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F, S, O> Feedforward for FnFeedforward<F, S, O>
+where
+    F: FnMut(S, Duration) -> O,
+{
+    type State = S;
+    type Signal = O;
+
+    fn update(&mut self, setpoint: S, dt: Duration) -> O {
+        (self.f)(setpoint, dt)
+    }
+}
+
+impl<F: Clone, S, O> Clone for FnFeedforward<F, S, O> {
+    fn clone(&self) -> Self {
+        Self {
+            f: self.f.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F: Copy, S, O> Copy for FnFeedforward<F, S, O> {}
+
+impl<F, S, O> fmt::Debug for FnFeedforward<F, S, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FnFeedforward").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,54 @@
+use std::time::Duration;
+
+use super::Feedback;
+
+/// Feedback controller that always outputs zero.
+///
+/// This is useful as a placeholder for a controller slot that isn't needed, such as disabling
+/// one axis of a motion that uses two controllers, or for testing motions without any output.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use evian_control::loops::{Feedback, ZeroFeedback};
+///
+/// let mut controller = ZeroFeedback;
+///
+/// assert_eq!(controller.update(0.0, 24.0, Duration::from_millis(10)), 0.0);
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZeroFeedback;
+
+impl Feedback for ZeroFeedback {
+    type State = f64;
+    type Signal = f64;
+
+    fn update(&mut self, _measurement: f64, _setpoint: f64, _dt: Duration) -> f64 {
+        0.0
+    }
+}
+
+/// Feedback controller that always outputs a fixed value, regardless of its setpoint.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use evian_control::loops::{ConstantFeedback, Feedback};
+///
+/// let mut controller = ConstantFeedback(0.5);
+///
+/// assert_eq!(controller.update(0.0, 24.0, Duration::from_millis(10)), 0.5);
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct ConstantFeedback(pub f64);
+
+impl Feedback for ConstantFeedback {
+    type State = f64;
+    type Signal = f64;
+
+    fn update(&mut self, _measurement: f64, _setpoint: f64, _dt: Duration) -> f64 {
+        self.0
+    }
+}
@@ -3,6 +3,7 @@
 use evian_math::Angle;
 
 use super::{Feedforward};
+use crate::profile::ProfileState;
 
 /// Desired setpoint of a DC motor.
 ///
@@ -16,6 +17,19 @@ pub struct MotorFeedforwardSetpoint {
     pub acceleration: f64,
 }
 
+impl From<ProfileState> for MotorFeedforwardSetpoint {
+    /// Takes the velocity and acceleration of a [`MotorFeedforward`] setpoint from a sample of a
+    /// [motion profile](crate::profile).
+    ///
+    /// See [`MotorFeedforward`] for the sign of the acceleration while decelerating.
+    fn from(state: ProfileState) -> Self {
+        Self {
+            velocity: state.velocity,
+            acceleration: state.acceleration,
+        }
+    }
+}
+
 /// Ideal DC motor feedforward controller.
 ///
 /// This is a open-loop velocity controller that computes the voltage to
@@ -24,6 +38,57 @@ pub struct MotorFeedforwardSetpoint {
 /// The controller is implemented according to the following model:
 ///
 /// `V = Kₛ sign(ω) + Kᵥ ω + Kₐ α`
+///
+/// # Following Motion Profiles
+///
+/// The `Kₐ` term only contributes when the setpoint has an acceleration, so a feedforward that
+/// is only given a velocity will lag behind its target whenever it speeds up or slows down. A
+/// [motion profile](crate::profile) provides both, and a [`ProfileState`] sampled from one can
+/// be converted directly into a [`MotorFeedforwardSetpoint`].
+///
+/// Acceleration is the rate of change of velocity, so its sign is relative to the direction of
+/// motion. While a profile moving in the positive direction decelerates, its acceleration is
+/// negative, and the `Kₐ` term subtracts from the output to help slow the system down. While a
+/// profile moving in the negative direction decelerates, its acceleration is positive.
+///
+/// # Examples
+///
+/// Following a profile on a simulated motor whose dynamics exactly match the feedforward model:
+///
+/// ```
+/// use std::time::Duration;
+/// use evian_control::{
+///     loops::{Feedforward, MotorFeedforward},
+///     profile::{MotionProfile, TrapezoidalProfile},
+/// };
+///
+/// let (ks, kv, ka) = (0.5, 0.4, 0.02);
+/// let profile = TrapezoidalProfile::new(48.0, 24.0, 48.0).with_max_deceleration(96.0);
+///
+/// // Returns the largest difference between the motor's velocity and the profile's.
+/// let follow = |mut feedforward: MotorFeedforward| {
+///     let dt = Duration::from_millis(1);
+///     let (mut t, mut velocity, mut max_error) = (Duration::ZERO, 0.0_f64, 0.0_f64);
+///
+///     while t < profile.total_duration() {
+///         let voltage = feedforward.update(profile.sample(t).into(), dt);
+///
+///         // The true dynamics of the motor, solved for acceleration.
+///         velocity += (voltage - ks * velocity.signum() - kv * velocity) / ka * dt.as_secs_f64();
+///         t += dt;
+///
+///         max_error = max_error.max((velocity - profile.sample(t).velocity).abs());
+///     }
+///
+///     max_error
+/// };
+///
+/// // With the acceleration term, the motor tracks the profile through every phase...
+/// assert!(follow(MotorFeedforward::new(ks, kv, ka)) < 0.2);
+///
+/// // ...while without it, the motor falls behind whenever the profile accelerates or brakes.
+/// assert!(follow(MotorFeedforward::new(ks, kv, 0.0)) > 2.0);
+/// ```
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct MotorFeedforward {
     ks: f64,
@@ -102,6 +167,118 @@ impl Feedforward for MotorFeedforward {
     }
 }
 
+/// Desired setpoint of a rotating system.
+///
+/// Describes the setpoint required to control a system's rotation, such as a drivetrain turning
+/// in place.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AngularFeedforwardSetpoint {
+    /// Desired angular velocity in radians per second.
+    pub velocity: f64,
+
+    /// Desired instantaneous angular acceleration in radians per second squared.
+    pub acceleration: f64,
+}
+
+impl From<ProfileState> for AngularFeedforwardSetpoint {
+    /// Takes the velocity and acceleration of an [`AngularFeedforward`] setpoint from a sample
+    /// of a [motion profile](crate::profile) whose positions are in radians.
+    fn from(state: ProfileState) -> Self {
+        Self {
+            velocity: state.velocity,
+            acceleration: state.acceleration,
+        }
+    }
+}
+
+/// Feedforward controller for rotational motion.
+///
+/// This is the rotational counterpart to [`MotorFeedforward`], computing the control signal
+/// needed to maintain a system at a certain angular velocity and acceleration. It is most useful
+/// for characterizing a drivetrain's turning dynamics separately from its linear dynamics, with
+/// the output being added to an angular feedback controller during turns. Keeping this as a
+/// distinct type prevents linear and angular constants from being mixed up.
+///
+/// The controller is implemented according to the following model:
+///
+/// `V = Kₛ sign(ω) + Kᵥ ω + Kₐ α`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AngularFeedforward {
+    ks: f64,
+    kv: f64,
+    ka: f64,
+}
+
+impl AngularFeedforward {
+    /// Creates a new [`AngularFeedforward`] with the given constants.
+    ///
+    /// # Parameters
+    ///
+    /// - `ks` - Feedforward constant for static friction compensation.
+    /// - `kv` - Feedforward constant for angular velocity compensation.
+    /// - `ka` - Feedforward constant for angular acceleration compensation.
+    pub const fn new(ks: f64, kv: f64, ka: f64) -> Self {
+        Self { ks, kv, ka }
+    }
+
+    /// Get the current feedforward constants as a tuple (`ks`, `kv`, `ka`).
+    #[must_use]
+    pub const fn constants(&self) -> (f64, f64, f64) {
+        (self.ks, self.kv, self.ka)
+    }
+
+    /// Returns the controller's static friction constant (`ks`).
+    #[must_use]
+    pub const fn ks(&self) -> f64 {
+        self.ks
+    }
+
+    /// Returns the controller's angular velocity constant (`kv`).
+    #[must_use]
+    pub const fn kv(&self) -> f64 {
+        self.kv
+    }
+
+    /// Returns the controller's angular acceleration constant (`ka`).
+    #[must_use]
+    pub const fn ka(&self) -> f64 {
+        self.ka
+    }
+
+    /// Sets the feedforward constants to provided values.
+    pub const fn set_constants(&mut self, ks: f64, kv: f64, ka: f64) {
+        self.ks = ks;
+        self.kv = kv;
+        self.ka = ka;
+    }
+
+    /// Sets the controller's static friction constant (`ks`).
+    pub const fn set_ks(&mut self, ks: f64) {
+        self.ks = ks;
+    }
+
+    /// Sets the controller's angular velocity constant (`kv`).
+    pub const fn set_kv(&mut self, kv: f64) {
+        self.kv = kv;
+    }
+
+    /// Sets the controller's angular acceleration constant (`ka`).
+    pub const fn set_ka(&mut self, ka: f64) {
+        self.ka = ka;
+    }
+}
+
+impl Feedforward for AngularFeedforward {
+    type State = AngularFeedforwardSetpoint;
+    type Signal = f64;
+
+    fn update(&mut self, setpoint: AngularFeedforwardSetpoint, _dt: core::time::Duration) -> f64 {
+        self.ks * setpoint.velocity.signum()
+            + self.kv * setpoint.velocity
+            + self.ka * setpoint.acceleration
+    }
+}
+
 /// Desired setpoint of a rotating arm.
 ///
 /// Describes the setpoint required to control a rotating arm.
@@ -212,6 +389,17 @@ pub struct ElevatorFeedforwardSetpoint {
     pub acceleration: f64,
 }
 
+impl From<ProfileState> for ElevatorFeedforwardSetpoint {
+    /// Takes the velocity and acceleration of an [`ElevatorFeedforward`] setpoint from a sample
+    /// of a [motion profile](crate::profile).
+    fn from(state: ProfileState) -> Self {
+        Self {
+            velocity: state.velocity,
+            acceleration: state.acceleration,
+        }
+    }
+}
+
 /// Feedforward controller for an ideal DC motor attached to an elevator mechanism.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct ElevatorFeedforward {
@@ -1,6 +1,8 @@
 //! Control loops.
 
 mod bang_bang;
+mod closure;
+mod constant;
 mod feedforward;
 mod pid;
 mod tbh;
@@ -8,11 +10,13 @@ mod tbh;
 use std::time::Duration;
 
 pub use bang_bang::BangBang;
+pub use closure::{FnFeedback, FnFeedforward};
+pub use constant::{ConstantFeedback, ZeroFeedback};
 pub use feedforward::{
-    ArmFeedforward, ArmFeedforwardSetpoint, ElevatorFeedforward, ElevatorFeedforwardSetpoint,
-    MotorFeedforward, MotorFeedforwardSetpoint,
+    AngularFeedforward, AngularFeedforwardSetpoint, ArmFeedforward, ArmFeedforwardSetpoint,
+    ElevatorFeedforward, ElevatorFeedforwardSetpoint, MotorFeedforward, MotorFeedforwardSetpoint,
 };
-pub use pid::{AngularPid, Pid};
+pub use pid::{AngularPid, IntegralConditions, Pid, PidBuilder};
 pub use tbh::TakeBackHalf;
 
 /// Feedback ("closed-loop") controller.
@@ -30,6 +34,30 @@ pub trait Feedback {
         setpoint: Self::State,
         dt: Duration,
     ) -> Self::Signal;
+
+    /// Returns `true` if the controller considers itself to be at its setpoint.
+    ///
+    /// This is determined by the controller's own internal history (typically the error from its
+    /// most recent update) being within `tolerance`, independently of any [`Tolerances`] used by
+    /// motion algorithms. This can be useful as a controller-level settling signal, such as a
+    /// flywheel "ready to fire" indicator.
+    ///
+    /// The default implementation always returns `false`, since not all controllers keep a record
+    /// of their error.
+    ///
+    /// [`Tolerances`]: crate::Tolerances
+    fn at_setpoint(&self, _tolerance: f64) -> bool {
+        false
+    }
+
+    /// Resets the controller's internal state (such as accumulated integral or previous error)
+    /// without changing its gains or other configuration.
+    ///
+    /// Motion algorithms call this before using a stored controller, so that each motion starts
+    /// from the same state regardless of what the controller was previously used for.
+    ///
+    /// The default implementation does nothing, which is correct for stateless controllers.
+    fn reset(&mut self) {}
 }
 
 /// Feedforward ("open-loop") controller.
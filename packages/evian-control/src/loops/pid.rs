@@ -4,6 +4,159 @@ use evian_math::Angle;
 
 use super::{Feedback};
 
+// MARK: Integral Conditions
+
+/// Conditions under which a PID controller accumulates its integral.
+///
+/// Integral windup commonly causes overshoot on large motions. While the controller is slewing
+/// towards a distant setpoint, its error stays large and keeps the same sign, so the integral
+/// accumulates far more than is needed to hold the setpoint once it arrives. The controller's
+/// sign-based reset only clears the integral *after* overshooting, which is already too late.
+///
+/// `IntegralConditions` prevents this by only integrating while each configured condition is
+/// met. Unlike leaving an `integration_range`, failing one of these conditions only pauses
+/// integration; the integral that has already accumulated is kept.
+///
+/// - **Error band:** The integral only accumulates while the magnitude of the error is below
+///   [`error_band`](IntegralConditions::error_band), in the same units as the controller's error
+///   (radians for [`AngularPid`]). This limits integration to the final approach.
+/// - **Saturation:** The integral doesn't accumulate while the magnitude of the controller's
+///   previous output was at or above [`saturation`](IntegralConditions::saturation), since more
+///   integral can't move a saturated system any faster. This should typically match the
+///   controller's output limit (or `1.0` for motor power). A single magnitude can't describe
+///   asymmetric [output bounds](Pid::set_output_bounds), so enabling
+///   [`saturate_at_bounds`](IntegralConditions::saturate_at_bounds) instead considers the
+///   controller saturated whenever its previous output was held at either bound, and only pauses
+///   integration that would push the output further into that bound.
+/// - **Converging error:** If [`require_converging`](IntegralConditions::require_converging) is
+///   enabled, the integral only accumulates while the magnitude of the error isn't increasing,
+///   such as while the system is being pushed away from its setpoint.
+///
+/// By default, no conditions are set and the integral always accumulates.
+///
+/// # Examples
+///
+/// The following simulates a 90° turn (measured in radians), where limiting integration to a 10°
+/// band and pausing it while saturated greatly reduces overshoot:
+///
+/// ```
+/// use std::time::Duration;
+/// use evian_control::loops::{Feedback, IntegralConditions, Pid};
+///
+/// let overshoot = |conditions: IntegralConditions| {
+///     let mut pid = Pid::new(2.0, 3.0, 0.1, None);
+///     pid.set_output_limit(Some(1.0));
+///     pid.set_integral_conditions(conditions);
+///
+///     let dt = Duration::from_millis(10);
+///     let (mut heading, mut velocity, mut max_heading) = (0.0_f64, 0.0, 0.0_f64);
+///
+///     for _ in 0..400 {
+///         let output = pid.update(heading, 90.0_f64.to_radians(), dt);
+///         let dt = dt.as_secs_f64();
+///
+///         velocity += (output * 20.0 - velocity * 4.0) * dt;
+///         heading += velocity * dt;
+///         max_heading = max_heading.max(heading);
+///     }
+///
+///     max_heading.to_degrees() - 90.0
+/// };
+///
+/// let conditions = IntegralConditions::new()
+///     .error_band(10.0_f64.to_radians())
+///     .saturation(1.0);
+///
+/// assert!(overshoot(IntegralConditions::new()) > 18.0);
+/// assert!(overshoot(conditions) < 13.0);
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct IntegralConditions {
+    /// Error magnitude below which the integral accumulates.
+    pub error_band: Option<f64>,
+
+    /// Output magnitude at or above which the controller is considered saturated.
+    pub saturation: Option<f64>,
+
+    /// Whether the integral only accumulates while the magnitude of the error isn't increasing.
+    pub require_converging: bool,
+
+    /// Whether the integral pauses while the controller's previous output was held at one of its
+    /// output bounds, in the direction of that bound.
+    pub saturate_at_bounds: bool,
+}
+
+impl IntegralConditions {
+    /// Creates a new set of integral conditions with no conditions set.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            error_band: None,
+            saturation: None,
+            require_converging: false,
+            saturate_at_bounds: false,
+        }
+    }
+
+    /// Only accumulates the integral while the magnitude of the error is below `band`.
+    #[must_use]
+    pub const fn error_band(mut self, band: f64) -> Self {
+        self.error_band = Some(band);
+        self
+    }
+
+    /// Pauses accumulation of the integral while the magnitude of the controller's output is at
+    /// or above `limit`.
+    #[must_use]
+    pub const fn saturation(mut self, limit: f64) -> Self {
+        self.saturation = Some(limit);
+        self
+    }
+
+    /// Sets whether the integral only accumulates while the magnitude of the error isn't
+    /// increasing.
+    #[must_use]
+    pub const fn require_converging(mut self, enabled: bool) -> Self {
+        self.require_converging = enabled;
+        self
+    }
+
+    /// Sets whether the integral pauses while the controller's output is held at one of its
+    /// output bounds.
+    ///
+    /// Only integration that would push the output further into the bound is paused, so the
+    /// integral is still free to unwind away from it.
+    #[must_use]
+    pub const fn saturate_at_bounds(mut self, enabled: bool) -> Self {
+        self.saturate_at_bounds = enabled;
+        self
+    }
+
+    /// Determines whether a controller should accumulate its integral during an update.
+    ///
+    /// `prev_error` and `prev_output` are from the controller's previous update, and `bounds` are
+    /// the controller's `(min, max)` output bounds, if any. Following a soft reset, the previous
+    /// error is meaningless and the converging condition is skipped.
+    fn allows(
+        &self,
+        error: f64,
+        prev_error: f64,
+        prev_output: f64,
+        bounds: Option<(f64, f64)>,
+        soft_reset: bool,
+    ) -> bool {
+        let at_bound = self.saturate_at_bounds
+            && bounds.is_some_and(|(min, max)| {
+                (prev_output >= max && error > 0.0) || (prev_output <= min && error < 0.0)
+            });
+
+        self.error_band.is_none_or(|band| error.abs() < band)
+            && self.saturation.is_none_or(|limit| prev_output.abs() < limit)
+            && (!self.require_converging || soft_reset || error.abs() <= prev_error.abs())
+            && !at_bound
+    }
+}
+
 // MARK: Linear Controller
 
 /// PID controller.
@@ -81,6 +234,17 @@ use super::{Feedback};
 /// 2. **Integration bounds:** An optional `integration_range` value can be passed to the
 ///    controller, which defines a range of error where integration will occur. When
 ///    `|error| > integration_range`, no integration will occur if used.
+///
+/// For finer control, [`IntegralConditions`] can be used to pause integration (without resetting
+/// the integral) while the error is large, while the output is saturated, or while the error is
+/// growing. See [`Pid::set_integral_conditions`] for more information.
+///
+/// # Following Moving Setpoints
+///
+/// A PID controller is purely reactive, so it will lag behind a setpoint that moves over time
+/// (such as one produced by a motion profile or setpoint ramp). An optional *setpoint velocity
+/// feedforward* gain ([`Pid::set_kv_setpoint`]) can be used to reduce this lag, which adds the
+/// rate of change of the setpoint (multiplied by the gain) to the controller's output.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Pid {
     kp: f64,
@@ -89,12 +253,22 @@ pub struct Pid {
 
     integral: f64,
     integration_range: Option<f64>,
-    output_limit: Option<f64>,
+    output_bounds: Option<(f64, f64)>,
+    min_output: Option<f64>,
+    derivative_filter: Option<f64>,
+    kv_setpoint: f64,
+    integral_conditions: IntegralConditions,
+    filtered_derivative: f64,
     prev_error: f64,
+    prev_output: f64,
+    prev_setpoint: Option<f64>,
+    soft_reset_pending: bool,
 }
 
 impl Pid {
     /// Construct a new PID controller from gain constants and an optional integration range.
+    ///
+    /// For configuring additional options, see [`Pid::builder`].
     #[must_use]
     pub const fn new(kp: f64, ki: f64, kd: f64, integration_range: Option<f64>) -> Self {
         Self {
@@ -102,12 +276,41 @@ impl Pid {
             ki,
             kd,
             integration_range,
-            output_limit: None,
+            output_bounds: None,
+            min_output: None,
+            derivative_filter: None,
+            kv_setpoint: 0.0,
+            integral_conditions: IntegralConditions::new(),
             integral: 0.0,
+            filtered_derivative: 0.0,
             prev_error: 0.0,
+            prev_output: 0.0,
+            prev_setpoint: None,
+            soft_reset_pending: false,
         }
     }
 
+    /// Creates a [`PidBuilder`] for constructing a PID controller using named options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evian_control::loops::Pid;
+    ///
+    /// const LINEAR_PID: Pid = Pid::builder()
+    ///     .kp(1.0)
+    ///     .kd(0.125)
+    ///     .output_limit(6.0)
+    ///     .build();
+    ///
+    /// assert_eq!(LINEAR_PID.gains(), (1.0, 0.0, 0.125));
+    /// assert_eq!(LINEAR_PID.output_limit(), Some(6.0));
+    /// ```
+    #[must_use]
+    pub const fn builder() -> PidBuilder {
+        PidBuilder::new()
+    }
+
     /// Get the current PID gains as a tuple (`kp`, `ki`, `kd`).
     #[must_use]
     pub const fn gains(&self) -> (f64, f64, f64) {
@@ -145,9 +348,57 @@ impl Pid {
 
     /// Returns the controller's output limit, or `None` if there is no
     /// limit applied.
+    ///
+    /// If the controller's [output bounds](Pid::output_bounds) are asymmetric, this is the larger
+    /// of their two magnitudes.
     #[must_use]
     pub const fn output_limit(&self) -> Option<f64> {
-        self.output_limit
+        match self.output_bounds {
+            Some((min, max)) => Some(max.max(-min)),
+            None => None,
+        }
+    }
+
+    /// Returns the controller's `(min, max)` output bounds, or `None` if there are no bounds
+    /// applied.
+    #[must_use]
+    pub const fn output_bounds(&self) -> Option<(f64, f64)> {
+        self.output_bounds
+    }
+
+    /// Returns the controller's minimum output magnitude, or `None` if there is no
+    /// minimum applied.
+    #[must_use]
+    pub const fn min_output(&self) -> Option<f64> {
+        self.min_output
+    }
+
+    /// Returns the time constant (in seconds) of the controller's derivative filter, or `None` if
+    /// the derivative is unfiltered.
+    #[must_use]
+    pub const fn derivative_filter(&self) -> Option<f64> {
+        self.derivative_filter
+    }
+
+    /// Returns the controller's setpoint velocity feedforward gain.
+    #[must_use]
+    pub const fn kv_setpoint(&self) -> f64 {
+        self.kv_setpoint
+    }
+
+    /// Returns the conditions under which the controller accumulates its integral.
+    #[must_use]
+    pub const fn integral_conditions(&self) -> IntegralConditions {
+        self.integral_conditions
+    }
+
+    /// Returns the error (`setpoint - measurement`) the controller acted on during its most
+    /// recent update.
+    ///
+    /// A controller that has yet to be updated has an error of zero.
+    #[must_use]
+    pub const fn error(&self) -> f64 {
+        self.prev_error
     }
 
     /// Sets the PID gains to provided values.
@@ -187,8 +438,352 @@ impl Pid {
     /// This sets a maximum range for the controller's output signal. It will effectively limit how
     /// fast the controller is able to drive the system, which may be desirable in some cases (e.g.
     /// limiting the maximum speed of a robot's motion).
+    ///
+    /// This is equivalent to calling [`Pid::set_output_bounds`] with bounds of `-range` and
+    /// `range`. Passing `None` removes the controller's output bounds.
     pub const fn set_output_limit(&mut self, range: Option<f64>) {
-        self.output_limit = range;
+        match range {
+            Some(range) => self.set_output_bounds(-range, range),
+            None => self.output_bounds = None,
+        }
+    }
+
+    /// Sets the minimum and maximum values of the controller's output signal.
+    ///
+    /// Unlike [`Pid::set_output_limit`], the bounds don't need to be symmetric. This is useful for
+    /// mechanisms that behave differently in each direction, such as a lift that gravity already
+    /// pulls down, which only needs a small amount of power to lower.
+    ///
+    /// If the controller's [integral conditions](IntegralConditions) enable
+    /// [`saturate_at_bounds`](IntegralConditions::saturate_at_bounds), the integral stops
+    /// accumulating against whichever bound the output is being held at.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is greater than `max`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use evian_control::loops::{Feedback, IntegralConditions, Pid};
+    ///
+    /// let lift = |conditions: IntegralConditions| {
+    ///     let mut pid = Pid::new(1.0, 1.0, 0.0, None);
+    ///     pid.set_output_bounds(-0.2, 1.0);
+    ///     pid.set_integral_conditions(conditions);
+    ///     pid
+    /// };
+    /// let dt = Duration::from_millis(10);
+    ///
+    /// // Far from the setpoint, the output is held at either bound.
+    /// assert_eq!(lift(IntegralConditions::new()).update(0.0, 10.0, dt), 1.0);
+    /// assert_eq!(lift(IntegralConditions::new()).update(10.0, 0.0, dt), -0.2);
+    ///
+    /// // Hold the lift 1.0 above its setpoint for a second, then let it settle 0.1 above it.
+    /// let settle = |mut pid: Pid| {
+    ///     for _ in 0..100 {
+    ///         pid.update(1.0, 0.0, dt);
+    ///     }
+    ///     pid.update(0.1, 0.0, dt)
+    /// };
+    ///
+    /// // Without anti-windup, the integral winds up against the lower bound...
+    /// assert_eq!(settle(lift(IntegralConditions::new())), -0.2);
+    ///
+    /// // ...but pausing it at the bounds keeps the integral from winding, leaving only the
+    /// // proportional term once the lift settles.
+    /// let conditions = IntegralConditions::new().saturate_at_bounds(true);
+    /// assert_eq!(settle(lift(conditions)), -0.1);
+    /// ```
+    pub const fn set_output_bounds(&mut self, min: f64, max: f64) {
+        assert!(min <= max, "minimum output bound is greater than maximum");
+        self.output_bounds = Some((min, max));
+    }
+
+    /// Sets the controller's minimum output magnitude.
+    ///
+    /// Any nonzero output signal smaller in magnitude than this value will be raised to it. This is
+    /// useful for overcoming static friction in a system, which may otherwise prevent small control
+    /// signals from moving it any further towards the setpoint.
+    pub const fn set_min_output(&mut self, min: Option<f64>) {
+        self.min_output = min;
+    }
+
+    /// Sets the time constant (in seconds) of a low-pass filter applied to the derivative term.
+    ///
+    /// Differentiating a noisy measurement amplifies its noise, which can make the derivative term
+    /// jittery at higher values of `kd`. When set, the derivative is passed through a first-order
+    /// low-pass filter with time constant `tau`, where larger values result in a smoother (but
+    /// slower to respond) derivative. The filter is disabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use evian_control::loops::{Feedback, Pid};
+    ///
+    /// let mut unfiltered = Pid::new(0.0, 0.0, 1.0, None);
+    /// let mut filtered = unfiltered;
+    /// filtered.set_derivative_filter(Some(0.05));
+    ///
+    /// let variance = |pid: &mut Pid| {
+    ///     let outputs: Vec<f64> = (0..100)
+    ///         .map(|i| {
+    ///             // Noisy measurement hovering around the setpoint.
+    ///             let noise = if i % 2 == 0 { 0.1 } else { -0.1 };
+    ///             pid.update(noise, 0.0, Duration::from_millis(10))
+    ///         })
+    ///         .collect();
+    ///     let mean = outputs.iter().sum::<f64>() / outputs.len() as f64;
+    ///     outputs.iter().map(|o| (o - mean).powi(2)).sum::<f64>() / outputs.len() as f64
+    /// };
+    ///
+    /// assert!(variance(&mut filtered) < variance(&mut unfiltered));
+    /// ```
+    pub const fn set_derivative_filter(&mut self, tau: Option<f64>) {
+        self.derivative_filter = tau;
+    }
+
+    /// Sets the controller's setpoint velocity feedforward gain (`kv_setpoint`).
+    ///
+    /// When nonzero, the rate of change of the setpoint between updates is multiplied by this gain
+    /// and added to the controller's output. This allows the controller to anticipate a moving
+    /// setpoint rather than only reacting once error has built up, greatly reducing how far it
+    /// lags behind. No feedforward is applied on the first update after creating or resetting the
+    /// controller, since there is no previous setpoint to compare against. Defaults to zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use evian_control::loops::{Feedback, Pid};
+    ///
+    /// // Track a setpoint ramping at 10 units per second with a system that moves at a rate
+    /// // equal to the controller's output.
+    /// let tracking_error = |kv_setpoint: f64| {
+    ///     let mut pid = Pid::new(2.0, 0.0, 0.0, None);
+    ///     pid.set_kv_setpoint(kv_setpoint);
+    ///
+    ///     let dt = Duration::from_millis(10);
+    ///     let mut position = 0.0;
+    ///     let mut setpoint = 0.0;
+    ///
+    ///     for _ in 0..500 {
+    ///         setpoint += 10.0 * dt.as_secs_f64();
+    ///         position += pid.update(position, setpoint, dt) * dt.as_secs_f64();
+    ///     }
+    ///
+    ///     setpoint - position
+    /// };
+    ///
+    /// // Pure proportional control lags behind by about `velocity / kp`...
+    /// assert!(tracking_error(0.0) > 4.0);
+    ///
+    /// // ...while the feedforward keeps up with the setpoint.
+    /// assert!(tracking_error(1.0).abs() < 0.1);
+    /// ```
+    pub const fn set_kv_setpoint(&mut self, kv_setpoint: f64) {
+        self.kv_setpoint = kv_setpoint;
+    }
+
+    /// Sets the conditions under which the controller accumulates its integral.
+    ///
+    /// These are checked in addition to the controller's integration range and sign-based reset,
+    /// and can be used to prevent the integral from winding up during large motions. See
+    /// [`IntegralConditions`] for more information.
+    pub const fn set_integral_conditions(&mut self, conditions: IntegralConditions) {
+        self.integral_conditions = conditions;
+    }
+
+    /// Fully resets the controller's internal state.
+    ///
+    /// This clears the accumulated integral, previous error, and derivative filter state,
+    /// returning the controller to the same state it was in when first created. Gains and other
+    /// configuration options are left unchanged.
+    pub const fn reset(&mut self) {
+        self.integral = 0.0;
+        self.filtered_derivative = 0.0;
+        self.prev_error = 0.0;
+        self.prev_output = 0.0;
+        self.prev_setpoint = None;
+        self.soft_reset_pending = false;
+    }
+
+    /// Resets the controller's derivative state while preserving its accumulated integral.
+    ///
+    /// The next update will not compute a derivative (since there is no meaningful previous error
+    /// to compare against), and will not reset the integral for crossing the setpoint. This is
+    /// useful when handing a controller off between motions with different setpoints, where the
+    /// integral represents a steady-state bias that is still valid (such as holding against a
+    /// constant load).
+    ///
+    /// For a full reset, see [`Pid::reset`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use evian_control::loops::{Feedback, Pid};
+    ///
+    /// let mut pid = Pid::new(0.0, 1.0, 0.0, None);
+    /// let dt = Duration::from_secs(1);
+    ///
+    /// assert_eq!(pid.update(0.0, 1.0, dt), 1.0);
+    ///
+    /// // Integral is kept after a soft reset...
+    /// pid.soft_reset();
+    /// assert_eq!(pid.update(0.0, 1.0, dt), 2.0);
+    ///
+    /// // ...but not after a full reset.
+    /// pid.reset();
+    /// assert_eq!(pid.update(0.0, 1.0, dt), 1.0);
+    /// ```
+    pub const fn soft_reset(&mut self) {
+        self.filtered_derivative = 0.0;
+        self.prev_setpoint = None;
+        self.soft_reset_pending = true;
+    }
+}
+
+// MARK: Builder
+
+/// Builder for configuring a [`Pid`] controller using named options.
+///
+/// This is created with [`Pid::builder`]. Any option that isn't set will use the same default as
+/// [`Pid::new`] (zero for gains, and no range or limits otherwise).
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct PidBuilder {
+    kp: f64,
+    ki: f64,
+    kd: f64,
+    integration_range: Option<f64>,
+    output_bounds: Option<(f64, f64)>,
+    min_output: Option<f64>,
+    derivative_filter: Option<f64>,
+    kv_setpoint: f64,
+    integral_conditions: IntegralConditions,
+}
+
+impl PidBuilder {
+    /// Creates a new builder with all gains set to zero and no additional options.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            kp: 0.0,
+            ki: 0.0,
+            kd: 0.0,
+            integration_range: None,
+            output_bounds: None,
+            min_output: None,
+            derivative_filter: None,
+            kv_setpoint: 0.0,
+            integral_conditions: IntegralConditions::new(),
+        }
+    }
+
+    /// Sets the controller's proportional gain (`kp`).
+    #[must_use]
+    pub const fn kp(mut self, kp: f64) -> Self {
+        self.kp = kp;
+        self
+    }
+
+    /// Sets the controller's integral gain (`ki`).
+    #[must_use]
+    pub const fn ki(mut self, ki: f64) -> Self {
+        self.ki = ki;
+        self
+    }
+
+    /// Sets the controller's derivative gain (`kd`).
+    #[must_use]
+    pub const fn kd(mut self, kd: f64) -> Self {
+        self.kd = kd;
+        self
+    }
+
+    /// Sets the controller's integration range.
+    ///
+    /// See [`Pid::set_integration_range`] for more information.
+    #[must_use]
+    pub const fn integration_range(mut self, range: f64) -> Self {
+        self.integration_range = Some(range);
+        self
+    }
+
+    /// Sets the controller's output limit.
+    ///
+    /// See [`Pid::set_output_limit`] for more information.
+    #[must_use]
+    pub const fn output_limit(mut self, limit: f64) -> Self {
+        self.output_bounds = Some((-limit, limit));
+        self
+    }
+
+    /// Sets the minimum and maximum values of the controller's output signal.
+    ///
+    /// See [`Pid::set_output_bounds`] for more information.
+    #[must_use]
+    pub const fn output_bounds(mut self, min: f64, max: f64) -> Self {
+        self.output_bounds = Some((min, max));
+        self
+    }
+
+    /// Sets the controller's minimum output magnitude.
+    ///
+    /// See [`Pid::set_min_output`] for more information.
+    #[must_use]
+    pub const fn min_output(mut self, min: f64) -> Self {
+        self.min_output = Some(min);
+        self
+    }
+
+    /// Sets the time constant (in seconds) of the controller's derivative filter.
+    ///
+    /// See [`Pid::set_derivative_filter`] for more information.
+    #[must_use]
+    pub const fn derivative_filter(mut self, tau: f64) -> Self {
+        self.derivative_filter = Some(tau);
+        self
+    }
+
+    /// Sets the controller's setpoint velocity feedforward gain (`kv_setpoint`).
+    ///
+    /// See [`Pid::set_kv_setpoint`] for more information.
+    #[must_use]
+    pub const fn kv_setpoint(mut self, kv_setpoint: f64) -> Self {
+        self.kv_setpoint = kv_setpoint;
+        self
+    }
+
+    /// Sets the conditions under which the controller accumulates its integral.
+    ///
+    /// See [`IntegralConditions`] for more information.
+    #[must_use]
+    pub const fn integral_conditions(mut self, conditions: IntegralConditions) -> Self {
+        self.integral_conditions = conditions;
+        self
+    }
+
+    /// Creates a [`Pid`] controller from the configured options.
+    #[must_use]
+    pub const fn build(self) -> Pid {
+        let mut pid = Pid::new(self.kp, self.ki, self.kd, self.integration_range);
+        if let Some((min, max)) = self.output_bounds {
+            pid.set_output_bounds(min, max);
+        }
+        pid.min_output = self.min_output;
+        pid.derivative_filter = self.derivative_filter;
+        pid.kv_setpoint = self.kv_setpoint;
+        pid.integral_conditions = self.integral_conditions;
+        pid
+    }
+}
+
+impl From<PidBuilder> for Pid {
+    fn from(builder: PidBuilder) -> Self {
+        builder.build()
     }
 }
 
@@ -201,31 +796,87 @@ impl Feedback for Pid {
     fn update(&mut self, measurement: f64, setpoint: f64, dt: Duration) -> f64 {
         let error = setpoint - measurement;
 
+        // Following a soft reset, our previous error is meaningless, so it can't be used to
+        // detect crossing the setpoint or to compute a derivative.
+        let soft_reset = core::mem::take(&mut self.soft_reset_pending);
+
         // If an integration range is used and we are within it, add to the integral.
         // If we are outside of the range, or if we have crossed the setpoint, reset integration.
         if self
             .integration_range
             .is_none_or(|range| error.abs() < range)
-            && error.signum() == self.prev_error.signum()
+            && (soft_reset || error.signum() == self.prev_error.signum())
         {
-            self.integral += error * dt.as_secs_f64();
+            if self
+                .integral_conditions
+                .allows(
+                    error,
+                    self.prev_error,
+                    self.prev_output,
+                    self.output_bounds,
+                    soft_reset,
+                )
+            {
+                self.integral += error * dt.as_secs_f64();
+            }
         } else {
             self.integral = 0.0;
         }
 
         // Calculate derivative (change in error / change in time)
-        let derivative = (error - self.prev_error) / dt.as_secs_f64();
+        let mut derivative = if soft_reset {
+            0.0
+        } else {
+            (error - self.prev_error) / dt.as_secs_f64()
+        };
         self.prev_error = error;
 
+        // Low-pass filter the derivative if a filter time constant is configured.
+        if let Some(tau) = self.derivative_filter {
+            let alpha = dt.as_secs_f64() / (tau + dt.as_secs_f64());
+            self.filtered_derivative += alpha * (derivative - self.filtered_derivative);
+            derivative = self.filtered_derivative;
+        }
+
         // Control signal = error * kp + integral + ki + derivative * kd.
         let mut output = (error * self.kp) + (self.integral * self.ki) + (derivative * self.kd);
 
-        if let Some(range) = self.output_limit {
-            output = output.clamp(-range, range);
+        // Feed through the setpoint's rate of change, so that we can keep up with a moving
+        // setpoint rather than waiting for error to accumulate.
+        if let Some(prev_setpoint) = self.prev_setpoint
+            && self.kv_setpoint != 0.0
+        {
+            output += self.kv_setpoint * (setpoint - prev_setpoint) / dt.as_secs_f64();
+        }
+        self.prev_setpoint = Some(setpoint);
+
+        if let Some(min) = self.min_output
+            && output != 0.0
+            && output.abs() < min
+        {
+            output = min.copysign(output);
         }
 
+        if let Some((min, max)) = self.output_bounds {
+            output = output.clamp(min, max);
+        }
+
+        self.prev_output = output;
+
         output
     }
+
+    /// Returns `true` if the error from the controller's most recent update is within
+    /// `tolerance`.
+    ///
+    /// A controller that has yet to be updated has an error of zero.
+    fn at_setpoint(&self, tolerance: f64) -> bool {
+        self.prev_error.abs() < tolerance
+    }
+
+    fn reset(&mut self) {
+        Pid::reset(self);
+    }
 }
 
 // MARK: Angular Controller
@@ -247,7 +898,10 @@ pub struct AngularPid {
     integral: f64,
     output_limit: Option<f64>,
     integration_range: Option<Angle>,
+    integral_conditions: IntegralConditions,
     prev_error: Angle,
+    prev_output: f64,
+    soft_reset_pending: bool,
 }
 
 impl AngularPid {
@@ -261,7 +915,10 @@ impl AngularPid {
             integration_range,
             integral: 0.0,
             output_limit: None,
+            integral_conditions: IntegralConditions::new(),
             prev_error: Angle::from_radians(0.0),
+            prev_output: 0.0,
+            soft_reset_pending: false,
         }
     }
 
@@ -300,6 +957,40 @@ impl AngularPid {
         self.integration_range
     }
 
+    /// Returns the conditions under which the controller accumulates its integral.
+    #[must_use]
+    pub const fn integral_conditions(&self) -> IntegralConditions {
+        self.integral_conditions
+    }
+
+    /// Returns the wrapped error the controller acted on during its most recent update.
+    ///
+    /// The error is wrapped to the range `[-π, π]`, making its magnitude the shortest turn
+    /// between the measurement and the setpoint. Its sign matches the sign of the proportional
+    /// term of the controller's output, which is positive when the measurement is
+    /// counterclockwise of the setpoint. A controller that has yet to be updated has an error of
+    /// zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use evian_control::loops::{AngularPid, Feedback};
+    /// use evian_math::Angle;
+    ///
+    /// let mut pid = AngularPid::new(1.0, 0.0, 0.0, None);
+    /// let dt = Duration::from_millis(10);
+    ///
+    /// pid.update(Angle::from_degrees(350.0), Angle::from_degrees(10.0), dt);
+    ///
+    /// // The shortest turn from 350° to 10° is 20° counterclockwise.
+    /// assert!((pid.error().as_degrees() + 20.0).abs() < 1e-9);
+    /// ```
+    #[must_use]
+    pub const fn error(&self) -> Angle {
+        self.prev_error
+    }
+
     /// Sets the PID gains to provided values.
     pub const fn set_gains(&mut self, kp: f64, ki: f64, kd: f64) {
         self.kp = kp;
@@ -340,6 +1031,32 @@ impl AngularPid {
     pub const fn set_output_limit(&mut self, range: Option<f64>) {
         self.output_limit = range;
     }
+
+    /// Sets the conditions under which the controller accumulates its integral.
+    ///
+    /// Error bands are given in radians. See [`IntegralConditions`] for more information.
+    pub const fn set_integral_conditions(&mut self, conditions: IntegralConditions) {
+        self.integral_conditions = conditions;
+    }
+
+    /// Fully resets the controller's internal state.
+    ///
+    /// This clears the accumulated integral and previous error, returning the controller to the
+    /// same state it was in when first created. Gains and other configuration options are left
+    /// unchanged.
+    pub const fn reset(&mut self) {
+        self.integral = 0.0;
+        self.prev_error = Angle::from_radians(0.0);
+        self.prev_output = 0.0;
+        self.soft_reset_pending = false;
+    }
+
+    /// Resets the controller's derivative state while preserving its accumulated integral.
+    ///
+    /// See [`Pid::soft_reset`] for more information.
+    pub const fn soft_reset(&mut self) {
+        self.soft_reset_pending = true;
+    }
 }
 
 // MARK: Loop
@@ -351,21 +1068,37 @@ impl Feedback for AngularPid {
     fn update(&mut self, measurement: Angle, setpoint: Angle, dt: Duration) -> f64 {
         let error = (setpoint - measurement).wrapped_half();
 
+        // Following a soft reset, our previous error is meaningless, so it can't be used to
+        // detect crossing the setpoint or to compute a derivative.
+        let soft_reset = core::mem::take(&mut self.soft_reset_pending);
+
         // If an integration range is used and we are within it, add to the integral.
         // If we are outside of the range, or if we have crossed the setpoint, reset integration.
         #[allow(clippy::float_cmp)]
         if self
             .integration_range
             .is_none_or(|range| error.as_radians().abs() < range.as_radians())
-            && error.signum() == self.prev_error.signum()
+            && (soft_reset || error.signum() == self.prev_error.signum())
         {
-            self.integral += error.as_radians() * dt.as_secs_f64();
+            if self.integral_conditions.allows(
+                error.as_radians(),
+                self.prev_error.as_radians(),
+                self.prev_output,
+                self.output_limit.map(|range| (-range, range)),
+                soft_reset,
+            ) {
+                self.integral += error.as_radians() * dt.as_secs_f64();
+            }
         } else {
             self.integral = 0.0;
         }
 
         // Calculate derivative (change in error / change in time)
-        let derivative = (error - self.prev_error).as_radians() / dt.as_secs_f64();
+        let derivative = if soft_reset {
+            0.0
+        } else {
+            (error - self.prev_error).as_radians() / dt.as_secs_f64()
+        };
         self.prev_error = error;
 
         let mut output =
@@ -375,6 +1108,20 @@ impl Feedback for AngularPid {
             output = output.clamp(-range, range);
         }
 
+        self.prev_output = output;
+
         output
     }
+
+    /// Returns `true` if the error from the controller's most recent update is within
+    /// `tolerance` radians.
+    ///
+    /// A controller that has yet to be updated has an error of zero.
+    fn at_setpoint(&self, tolerance: f64) -> bool {
+        self.prev_error.as_radians().abs() < tolerance
+    }
+
+    fn reset(&mut self) {
+        AngularPid::reset(self);
+    }
 }
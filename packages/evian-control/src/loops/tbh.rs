@@ -3,6 +3,45 @@ use std::time::Duration;
 use crate::loops::Feedback;
 
 /// Take-back-half flywheel velocity controller.
+///
+/// Take-back-half (TBH) is an integrating controller for systems such as flywheels, where the
+/// output needed to hold a velocity is roughly constant. The output is accumulated from the error
+/// on every update, and each time the error changes sign (meaning the system has crossed its
+/// setpoint), the output is "taken back" halfway to its value at the previous crossing. This
+/// quickly converges to the output needed to hold the setpoint, with only a single gain to tune.
+///
+/// `TakeBackHalf` implements [`Feedback`] with both its [state](Feedback::State) and
+/// [signal](Feedback::Signal) as `f64`, where the measurement and setpoint are velocities (in any
+/// consistent unit) and the output is a motor power. It can therefore be used anywhere a
+/// feedback controller is expected.
+///
+/// # Examples
+///
+/// The following spins up a simulated flywheel using a function that accepts any feedback
+/// controller:
+///
+/// ```
+/// use std::time::Duration;
+/// use evian_control::loops::{Feedback, TakeBackHalf};
+///
+/// fn spin_up(controller: &mut impl Feedback<State = f64, Signal = f64>, target: f64) -> f64 {
+///     let mut velocity = 0.0;
+///
+///     for _ in 0..3000 {
+///         let output = controller.update(velocity, target, Duration::from_millis(10));
+///
+///         // At full power, the flywheel spins at 600 RPM.
+///         velocity += (600.0 * output - velocity) * 0.05;
+///     }
+///
+///     velocity
+/// }
+///
+/// let mut tbh = TakeBackHalf::new(0.00005);
+///
+/// assert!((spin_up(&mut tbh, 400.0) - 400.0).abs() < 1.0);
+/// assert!(tbh.at_setpoint(1.0));
+/// ```
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct TakeBackHalf {
     kh: f64,
@@ -49,6 +88,20 @@ impl Feedback for TakeBackHalf {
 
         self.prev_error = error;
 
-        self.tbh
+        self.integral
+    }
+
+    /// Returns `true` if the error from the controller's most recent update is within
+    /// `tolerance`.
+    ///
+    /// A controller that has yet to be updated has an error of zero.
+    fn at_setpoint(&self, tolerance: f64) -> bool {
+        self.prev_error.abs() < tolerance
+    }
+
+    fn reset(&mut self) {
+        self.tbh = 0.0;
+        self.integral = 0.0;
+        self.prev_error = 0.0;
     }
 }
@@ -0,0 +1,271 @@
+//! Motion profiles.
+//!
+//! A *motion profile* describes how a system should move from one state to another over time,
+//! typically by limiting its velocity and acceleration so that the move is smooth and physically
+//! achievable. Rather than commanding a controller directly towards a final setpoint, a profile
+//! is sampled over the course of a motion to produce a moving setpoint for the controller to
+//! track.
+//!
+//! The [`MotionProfile`] trait allows motions and controllers to accept any profile generically,
+//! so custom profiles can be used anywhere a profile is expected. [`TrapezoidalProfile`] is
+//! provided as a built-in profile.
+
+use std::time::Duration;
+
+/// The state of a system at a point in time along a [`MotionProfile`].
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct ProfileState {
+    /// Position of the system.
+    pub position: f64,
+
+    /// Velocity of the system in position units per second.
+    pub velocity: f64,
+
+    /// Acceleration of the system in position units per second squared.
+    pub acceleration: f64,
+}
+
+/// A time-parameterized description of how a system should move.
+///
+/// # Examples
+///
+/// A profile that moves at a constant velocity to a target:
+///
+/// ```
+/// use std::time::Duration;
+/// use evian_control::profile::{MotionProfile, ProfileState};
+///
+/// struct ConstantVelocity {
+///     distance: f64,
+///     velocity: f64,
+/// }
+///
+/// impl MotionProfile for ConstantVelocity {
+///     fn sample(&self, t: Duration) -> ProfileState {
+///         let t = t.min(self.total_duration()).as_secs_f64();
+///         let moving = t < self.distance / self.velocity;
+///
+///         ProfileState {
+///             position: self.velocity * t,
+///             velocity: if moving { self.velocity } else { 0.0 },
+///             acceleration: 0.0,
+///         }
+///     }
+///
+///     fn total_duration(&self) -> Duration {
+///         Duration::from_secs_f64(self.distance / self.velocity)
+///     }
+/// }
+///
+/// let profile = ConstantVelocity { distance: 24.0, velocity: 12.0 };
+///
+/// assert_eq!(profile.total_duration(), Duration::from_secs(2));
+/// assert_eq!(profile.sample(Duration::from_secs(1)).position, 12.0);
+/// assert_eq!(profile.final_state().position, 24.0);
+/// ```
+pub trait MotionProfile {
+    /// Returns the desired state of the system `t` after the start of the profile.
+    ///
+    /// Implementors should return the profile's final state for any `t` past the profile's
+    /// [total duration](MotionProfile::total_duration).
+    fn sample(&self, t: Duration) -> ProfileState;
+
+    /// Returns how long the profile takes to complete.
+    fn total_duration(&self) -> Duration;
+
+    /// Returns the state of the system once the profile has completed.
+    fn final_state(&self) -> ProfileState {
+        self.sample(self.total_duration())
+    }
+}
+
+/// A profile that accelerates to a maximum velocity, cruises, then decelerates to a stop.
+///
+/// Plotted over time, the profile's velocity forms a trapezoid. If the distance is too short to
+/// reach the maximum velocity, the cruise phase is skipped and the profile turns around at a
+/// lower peak velocity, forming a triangle instead.
+///
+/// # Asymmetric Limits
+///
+/// By default, the profile decelerates at the same rate it accelerates. Many systems can safely
+/// stop harder than they can start (or the other way around), so a separate deceleration limit
+/// can be set with [`with_max_deceleration`](TrapezoidalProfile::with_max_deceleration). The
+/// profile still ends exactly at its target distance with zero velocity, and if the cruise phase
+/// is skipped, the peak velocity is the highest one that both limits allow.
+///
+/// # Examples
+///
+/// A 36 inch move that can brake twice as hard as it accelerates:
+///
+/// ```
+/// use std::time::Duration;
+/// use evian_control::profile::{MotionProfile, TrapezoidalProfile};
+///
+/// let profile = TrapezoidalProfile::new(36.0, 48.0, 12.0).with_max_deceleration(24.0);
+///
+/// // The move is too short to reach 48 in/s, so the profile peaks at 24 in/s after 2 seconds
+/// // of acceleration, then takes only 1 second to stop.
+/// assert_eq!(profile.peak_velocity(), 24.0);
+/// assert_eq!(profile.total_duration(), Duration::from_secs(3));
+///
+/// let peak = profile.sample(Duration::from_secs(2));
+/// assert_eq!(peak.position, 24.0);
+/// assert_eq!(peak.velocity, 24.0);
+///
+/// let end = profile.final_state();
+/// assert_eq!(end.position, 36.0);
+/// assert_eq!(end.velocity, 0.0);
+/// ```
+///
+/// With a lower velocity limit, the profile cruises between its acceleration and deceleration
+/// phases:
+///
+/// ```
+/// use std::time::Duration;
+/// use evian_control::profile::{MotionProfile, TrapezoidalProfile};
+///
+/// let profile = TrapezoidalProfile::new(36.0, 12.0, 12.0).with_max_deceleration(24.0);
+///
+/// // 1 second accelerating (6 in), 2.25 seconds cruising (27 in), 0.5 seconds braking (3 in).
+/// assert_eq!(profile.peak_velocity(), 12.0);
+/// assert_eq!(profile.total_duration(), Duration::from_millis(3750));
+/// assert_eq!(profile.sample(Duration::from_millis(3250)).position, 33.0);
+/// assert_eq!(profile.final_state().position, 36.0);
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct TrapezoidalProfile {
+    distance: f64,
+    max_velocity: f64,
+    max_acceleration: f64,
+    max_deceleration: f64,
+}
+
+impl TrapezoidalProfile {
+    /// Creates a new profile that moves `distance` from its starting position, accelerating and
+    /// decelerating at `max_acceleration`.
+    ///
+    /// `distance` may be negative to move backwards. `max_velocity` and `max_acceleration`
+    /// should be positive.
+    #[must_use]
+    pub const fn new(distance: f64, max_velocity: f64, max_acceleration: f64) -> Self {
+        Self {
+            distance,
+            max_velocity,
+            max_acceleration,
+            max_deceleration: max_acceleration,
+        }
+    }
+
+    /// Sets the rate that the profile decelerates at, independently of its acceleration.
+    ///
+    /// `max_deceleration` should be positive. See the [type-level
+    /// documentation](TrapezoidalProfile#asymmetric-limits) for more information.
+    #[must_use]
+    pub const fn with_max_deceleration(mut self, max_deceleration: f64) -> Self {
+        self.max_deceleration = max_deceleration;
+        self
+    }
+
+    /// Returns the distance that the profile moves.
+    #[must_use]
+    pub const fn distance(&self) -> f64 {
+        self.distance
+    }
+
+    /// Returns the profile's velocity limit.
+    #[must_use]
+    pub const fn max_velocity(&self) -> f64 {
+        self.max_velocity
+    }
+
+    /// Returns the profile's acceleration limit.
+    #[must_use]
+    pub const fn max_acceleration(&self) -> f64 {
+        self.max_acceleration
+    }
+
+    /// Returns the profile's deceleration limit.
+    #[must_use]
+    pub const fn max_deceleration(&self) -> f64 {
+        self.max_deceleration
+    }
+
+    /// Returns the highest speed reached by the profile.
+    ///
+    /// This is the profile's velocity limit if the distance is long enough to reach it, or the
+    /// speed at which the acceleration and deceleration phases meet otherwise.
+    #[must_use]
+    pub fn peak_velocity(&self) -> f64 {
+        let (accel, decel) = (self.max_acceleration, self.max_deceleration);
+
+        // Speed reached by accelerating and decelerating over the whole distance, from
+        // v²/2a + v²/2b = d.
+        let triangular = (2.0 * self.distance.abs() * accel * decel / (accel + decel)).sqrt();
+
+        triangular.min(self.max_velocity)
+    }
+
+    /// Returns the duration of the acceleration, cruise, and deceleration phases in seconds.
+    fn phases(&self) -> (f64, f64, f64) {
+        let peak = self.peak_velocity();
+        if peak == 0.0 {
+            return (0.0, 0.0, 0.0);
+        }
+
+        let accel_time = peak / self.max_acceleration;
+        let decel_time = peak / self.max_deceleration;
+        let cruise_time = (self.distance.abs() / peak - (accel_time + decel_time) / 2.0).max(0.0);
+
+        (accel_time, cruise_time, decel_time)
+    }
+}
+
+impl MotionProfile for TrapezoidalProfile {
+    fn sample(&self, t: Duration) -> ProfileState {
+        let direction = self.distance.signum();
+
+        if t >= self.total_duration() {
+            return ProfileState {
+                position: self.distance,
+                velocity: 0.0,
+                acceleration: 0.0,
+            };
+        }
+
+        let peak = self.peak_velocity();
+        let (accel_time, cruise_time, decel_time) = self.phases();
+        let t = t.as_secs_f64();
+
+        let (position, velocity, acceleration) = if t < accel_time {
+            (
+                0.5 * self.max_acceleration * t * t,
+                self.max_acceleration * t,
+                self.max_acceleration,
+            )
+        } else if t < accel_time + cruise_time {
+            (peak * (t - accel_time / 2.0), peak, 0.0)
+        } else {
+            // Measure the deceleration phase back from the end, so the profile stops exactly on
+            // its target.
+            let remaining = (accel_time + cruise_time + decel_time - t).max(0.0);
+
+            (
+                self.distance.abs() - 0.5 * self.max_deceleration * remaining * remaining,
+                self.max_deceleration * remaining,
+                -self.max_deceleration,
+            )
+        };
+
+        ProfileState {
+            position: position * direction,
+            velocity: velocity * direction,
+            acceleration: acceleration * direction,
+        }
+    }
+
+    fn total_duration(&self) -> Duration {
+        let (accel_time, cruise_time, decel_time) = self.phases();
+
+        Duration::from_secs_f64(accel_time + cruise_time + decel_time)
+    }
+}
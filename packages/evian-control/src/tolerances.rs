@@ -12,6 +12,10 @@
 //! like friction, sensor noise, and mechanical limitations. Under the [`Tolerances`] struct, a system is considered
 //! "settled" when it meets specified error and velocity tolerances for a given duration, after when a timeout is
 //! reached.
+//!
+//! Holonomic motions that target a full pose (both a position and a heading) can instead use
+//! [`PoseTolerances`], which applies the same settling logic to translational and rotational error
+//! simultaneously.
 
 use std::time::{Duration, Instant};
 
@@ -31,9 +35,23 @@ use std::time::{Duration, Instant};
 ///   2. The system has maintained these tolerances for the specified duration.
 ///
 /// If the system leaves the tolerance window before the duration is met, the tolerance timer resets.
+/// A [debounce](Tolerances::debounce) can optionally be configured to allow the system to briefly
+/// leave the tolerance window for a number of consecutive checks without resetting the timer.
+///
+/// # Relative Error
+///
+/// In addition to the absolute [`error_tolerance`](Tolerances::error_tolerance), an error
+/// tolerance may be given as a fraction of the system's *initial* error using
+/// [`Tolerances::relative_error`]. The initial error is captured from the first call to
+/// [`Tolerances::check`], so a tolerance of `0.04` allows 4 inches of error on a 100 inch move,
+/// but only 0.24 inches on a 6 inch move.
+///
+/// When both an absolute and relative error tolerance are set, the [`ErrorToleranceMode`]
+/// determines whether the error must satisfy either one of them (the default) or both. If only
+/// one of them is set, then that tolerance alone is used.
 #[derive(Default, Debug, Copy, Clone, PartialEq, PartialOrd)]
 pub struct Tolerances {
-    tolerance_timestamp: Option<Instant>,
+    timer: SettlingTimer,
 
     /// Duration for which `error_tolerance` and `velocity_tolerance` must be satisfied.
     pub duration: Option<Duration>,
@@ -41,8 +59,20 @@ pub struct Tolerances {
     /// Minimum error range.
     pub error_tolerance: Option<f64>,
 
+    /// Minimum error range as a fraction of the initial error.
+    pub relative_error_tolerance: Option<f64>,
+
+    /// How `error_tolerance` and `relative_error_tolerance` are combined when both are set.
+    pub error_mode: ErrorToleranceMode,
+
+    /// Error magnitude from the first check, used to compute the relative error tolerance.
+    initial_error: Option<f64>,
+
     /// Minimum velocity range.
     pub velocity_tolerance: Option<f64>,
+
+    /// Number of consecutive out-of-tolerance checks allowed before the tolerance timer resets.
+    pub debounce: u32,
 }
 
 impl Tolerances {
@@ -53,10 +83,14 @@ impl Tolerances {
     #[must_use]
     pub const fn new() -> Self {
         Self {
-            tolerance_timestamp: None,
+            timer: SettlingTimer::new(),
             duration: None,
             error_tolerance: None,
+            relative_error_tolerance: None,
+            error_mode: ErrorToleranceMode::Either,
+            initial_error: None,
             velocity_tolerance: None,
+            debounce: 0,
         }
     }
 
@@ -70,10 +104,51 @@ impl Tolerances {
         *self
     }
 
+    /// Sets the maximum acceptable error for settling as a fraction of the initial error.
+    ///
+    /// The initial error is the magnitude of the error given to the first call to
+    /// [`Tolerances::check`], which is typically the distance the motion has to travel. This is
+    /// useful for motions where the acceptable error scales with the size of the movement.
+    ///
+    /// If an absolute [error tolerance](Tolerances::error) is also set, the two are combined
+    /// according to the tolerances' [error mode](Tolerances::error_mode).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Instant;
+    /// use evian_control::Tolerances;
+    ///
+    /// let mut tolerances = Tolerances::new().error(1.0).relative_error(0.04);
+    /// let now = Instant::now();
+    ///
+    /// // The first check captures the initial error of a 100 inch move.
+    /// assert!(!tolerances.check(100.0, 0.0, now).is_settled());
+    ///
+    /// // 3 inches of error is outside of the absolute tolerance, but within 4% of the move.
+    /// assert!(tolerances.check(3.0, 0.0, now).is_settled());
+    /// ```
+    #[must_use]
+    pub const fn relative_error(&mut self, fraction: f64) -> Self {
+        self.relative_error_tolerance = Some(fraction);
+        *self
+    }
+
+    /// Sets how the absolute and relative error tolerances are combined when both are set.
+    ///
+    /// Defaults to [`ErrorToleranceMode::Either`].
+    #[must_use]
+    pub const fn error_mode(&mut self, mode: ErrorToleranceMode) -> Self {
+        self.error_mode = mode;
+        *self
+    }
+
     /// Sets the maximum acceptable velocity for settling.
     ///
     /// The velocity tolerance defines how slow the system must be moving to be
-    /// considered "stable".
+    /// considered "stable". This is given in the same units as the velocity passed to
+    /// [`check`](Tolerances::check), which for drivetrain motions is the tracking system's
+    /// velocity in wheel units per second (or radians per second for turns).
     #[must_use]
     pub const fn velocity(&mut self, tolerance: f64) -> Self {
         self.velocity_tolerance = Some(tolerance);
@@ -90,48 +165,488 @@ impl Tolerances {
         *self
     }
 
+    /// Sets how many consecutive checks the system may spend outside of tolerance before the
+    /// tolerance timer resets.
+    ///
+    /// By default, a single out-of-tolerance reading will restart the settling timer. This makes
+    /// settling prone to delays from noisy sensors, where a measurement may spike out of tolerance
+    /// for a single tick. A debounce of `n` allows up to `n` consecutive violations to occur
+    /// without resetting the timer, without loosening the actual tolerances. The system will
+    /// never be considered settled while it is out of tolerance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use evian_control::Tolerances;
+    ///
+    /// let mut tolerances = Tolerances::new()
+    ///     .error(1.0)
+    ///     .duration(Duration::from_millis(20))
+    ///     .debounce(1);
+    /// let start = Instant::now();
+    ///
+    /// assert!(!tolerances.check(0.5, 0.0, start).is_settled());
+    ///
+    /// // A single noisy spike out of tolerance doesn't restart the timer...
+    /// let spike = start + Duration::from_millis(10);
+    /// assert!(!tolerances.check(5.0, 0.0, spike).is_settled());
+    ///
+    /// // ...so we settle once we're back in tolerance.
+    /// let later = start + Duration::from_millis(25);
+    /// assert!(tolerances.check(0.5, 0.0, later).is_settled());
+    /// ```
+    #[must_use]
+    pub const fn debounce(&mut self, debounce: u32) -> Self {
+        self.debounce = debounce;
+        *self
+    }
+
     /// Checks if the system has settled based on current error and velocity.
     ///
     /// This method should be called periodically (typically in a control loop)
-    /// with current system measurements. It will return `true` when either:
+    /// with current system measurements. The returned [`ToleranceCheck`] describes which
+    /// settling conditions were met, and will be [settled](ToleranceCheck::is_settled) when either:
     ///
     /// - The specified timeout has elapsed since the first call to this function, OR
     /// - Both:
     ///   1. The error and velocity are within their respective tolerances.
     ///   2. The system has maintained these tolerances for the specified duration.
+    ///
     /// # Parameters
     ///
     /// * `error` - Difference between the setpoint and measured state of the system.
     /// * `velocity` - Measurement of how fast the system response is changing over time.
-    pub fn check(&mut self, error: f64, velocity: f64) -> bool {
+    /// * `now` - The time of this check. Motions pass the current time of their clock here, so
+    ///   that the settling duration is measured in the same time as the rest of the motion.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Instant;
+    /// use evian_control::Tolerances;
+    ///
+    /// let mut tolerances = Tolerances::new().error(1.0).velocity(0.5);
+    ///
+    /// let check = tolerances.check(0.25, 2.0, Instant::now());
+    ///
+    /// // Close enough to the target, but still moving too fast to settle.
+    /// assert!(check.error_satisfied);
+    /// assert!(!check.velocity_satisfied);
+    /// assert!(!check.is_settled());
+    /// ```
+    pub fn check(&mut self, error: f64, velocity: f64, now: Instant) -> ToleranceCheck {
+        let initial_error = *self.initial_error.get_or_insert(error.abs());
+
         // Check if we are within the tolerance range for either error and velocity.
-        let in_tolerances = self
+        let absolute_satisfied = self
             .error_tolerance
-            .is_none_or(|tolerance| error.abs() < tolerance)
-            && self
-                .velocity_tolerance
-                .is_none_or(|tolerance| velocity.abs() < tolerance);
+            .map(|tolerance| error.abs() < tolerance);
+        let relative_satisfied = self
+            .relative_error_tolerance
+            .map(|fraction| error.abs() <= fraction * initial_error);
+        let error_satisfied = match (absolute_satisfied, relative_satisfied) {
+            (Some(absolute), Some(relative)) => match self.error_mode {
+                ErrorToleranceMode::Either => absolute || relative,
+                ErrorToleranceMode::Both => absolute && relative,
+            },
+            (Some(satisfied), None) | (None, Some(satisfied)) => satisfied,
+            (None, None) => true,
+        };
+        let velocity_satisfied = self
+            .velocity_tolerance
+            .is_none_or(|tolerance| velocity.abs() < tolerance);
+
+        let (duration_satisfied, remaining_duration) = self.timer.update(
+            error_satisfied && velocity_satisfied,
+            self.duration,
+            self.debounce,
+            now,
+        );
+
+        ToleranceCheck {
+            error_satisfied,
+            velocity_satisfied,
+            duration_satisfied,
+            remaining_duration,
+        }
+    }
+
+    /// Estimates how far the system has progressed from its initial error towards its setpoint,
+    /// from `0.0` (no progress) to `1.0` (no error remaining).
+    ///
+    /// Like the [relative error tolerance](Tolerances::relative_error), this is relative to the
+    /// magnitude of the error given to the first call to [`Tolerances::check`]. Before the first
+    /// check, this returns `0.0`.
+    ///
+    /// Progress isn't monotonic. The error can grow as the system moves (such as when it
+    /// overshoots or is pushed away from its setpoint), and progress will drop along with it,
+    /// so anything displaying progress should expect it to move backwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Instant;
+    /// use evian_control::Tolerances;
+    ///
+    /// let mut tolerances = Tolerances::new().error(1.0);
+    /// tolerances.check(40.0, 0.0, Instant::now());
+    ///
+    /// assert_eq!(tolerances.progress(30.0), 0.25);
+    /// assert_eq!(tolerances.progress(-10.0), 0.75);
+    /// assert_eq!(tolerances.progress(50.0), 0.0);
+    /// ```
+    #[must_use]
+    pub fn progress(&self, error: f64) -> f64 {
+        match self.initial_error {
+            None => 0.0,
+            Some(0.0) => 1.0,
+            Some(initial_error) => (1.0 - error.abs() / initial_error).clamp(0.0, 1.0),
+        }
+    }
+
+    /// Returns how much of the tolerance [duration](Tolerances::duration) has elapsed in a
+    /// [`ToleranceCheck`], from `0.0` (out of tolerance) to `1.0` (settled).
+    ///
+    /// This drops back to `0.0` whenever the system leaves tolerance and its settling timer resets.
+    #[must_use]
+    pub fn settling_progress(&self, check: &ToleranceCheck) -> f64 {
+        match (check.remaining_duration, self.duration) {
+            _ if check.duration_satisfied => 1.0,
+            (None, _) => 0.0,
+            (Some(remaining), Some(duration)) if !duration.is_zero() => {
+                (1.0 - remaining.as_secs_f64() / duration.as_secs_f64()).clamp(0.0, 1.0)
+            }
+            (Some(_), _) => 1.0,
+        }
+    }
+}
+
+/// Tracks how long a system has been within tolerance.
+///
+/// This implements the duration and debounce logic shared by [`Tolerances`] and
+/// [`PoseTolerances`].
+#[derive(Default, Debug, Copy, Clone, PartialEq, PartialOrd)]
+struct SettlingTimer {
+    tolerance_timestamp: Option<Instant>,
+    violations: u32,
+}
+
+impl SettlingTimer {
+    const fn new() -> Self {
+        Self {
+            tolerance_timestamp: None,
+            violations: 0,
+        }
+    }
+
+    /// Updates the timer with whether the system is currently within tolerance, returning
+    /// whether the system has been in tolerance for `duration` at `now` along with the remaining
+    /// time until it will be.
+    fn update(
+        &mut self,
+        in_tolerance: bool,
+        duration: Option<Duration>,
+        debounce: u32,
+        now: Instant,
+    ) -> (bool, Option<Duration>) {
+        let mut duration_satisfied = false;
+        let mut remaining_duration = None;
+
+        if in_tolerance {
+            self.violations = 0;
 
-        if in_tolerances {
             // We are now within tolerance, so we record the timestamp that this occurred if
             // we previously weren't in tolerance.
             if self.tolerance_timestamp.is_none() {
-                self.tolerance_timestamp = Some(Instant::now());
+                self.tolerance_timestamp = Some(now);
             }
 
             // If we have a tolerance time (required time to be within tolerance to settle), then compare that with
             // the elapsed tolerance timer. If we've been settled for greater than that time, then we are now settled.
-            if self
-                .duration
-                .is_none_or(|time| self.tolerance_timestamp.unwrap().elapsed() > time)
-            {
+            let elapsed = now.saturating_duration_since(self.tolerance_timestamp.unwrap());
+            remaining_duration =
+                Some(duration.map_or(Duration::ZERO, |time| time.saturating_sub(elapsed)));
+
+            if duration.is_none_or(|time| elapsed > time) {
+                self.tolerance_timestamp = None;
+                duration_satisfied = true;
+                remaining_duration = Some(Duration::ZERO);
+            }
+        } else if let Some(timestamp) = self.tolerance_timestamp {
+            // Only reset the tolerance timer once we've been out of tolerance for more
+            // consecutive checks than the debounce allows.
+            self.violations += 1;
+
+            if self.violations > debounce {
                 self.tolerance_timestamp = None;
-                return true;
+                self.violations = 0;
+            } else {
+                let elapsed = now.saturating_duration_since(timestamp);
+                remaining_duration =
+                    Some(duration.map_or(Duration::ZERO, |time| time.saturating_sub(elapsed)));
             }
-        } else if self.tolerance_timestamp.is_some() {
-            self.tolerance_timestamp = None;
         }
 
-        false
+        (duration_satisfied, remaining_duration)
+    }
+}
+
+/// Determines how absolute and relative error tolerances are combined in [`Tolerances`].
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub enum ErrorToleranceMode {
+    /// The error is within tolerance if it satisfies either the absolute or relative tolerance.
+    #[default]
+    Either,
+
+    /// The error is only within tolerance if it satisfies both the absolute and relative
+    /// tolerance.
+    Both,
+}
+
+/// The result of a [`Tolerances::check`], describing which settling conditions were met.
+///
+/// This is mostly useful for determining why a system isn't settling.
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ToleranceCheck {
+    /// Whether the error was within the error tolerance (or no error tolerance is set).
+    pub error_satisfied: bool,
+
+    /// Whether the velocity was within the velocity tolerance (or no velocity tolerance is set).
+    pub velocity_satisfied: bool,
+
+    /// Whether the system has stayed within tolerance for the required duration.
+    pub duration_satisfied: bool,
+
+    /// Time the system must remain within tolerance before it is settled.
+    ///
+    /// This is `None` if the settling timer isn't running, such as when the system is out of
+    /// tolerance.
+    pub remaining_duration: Option<Duration>,
+}
+
+impl ToleranceCheck {
+    /// Returns `true` if every settling condition was met, meaning the system has settled.
+    #[must_use]
+    pub const fn is_settled(&self) -> bool {
+        self.error_satisfied && self.velocity_satisfied && self.duration_satisfied
+    }
+}
+
+/// Describes when a holonomic system has stabilized reasonably near a target pose.
+///
+/// Where [`Tolerances`] checks a single scalar error, a holonomic motion targeting a pose has
+/// both a translational error (the distance to the target position) and a heading error to
+/// satisfy at the same time. `PoseTolerances` checks each component against its own tolerance,
+/// along with the system's linear and angular velocity.
+///
+/// # Settling Logic
+///
+/// A system is considered settled once every configured tolerance is satisfied at the same time,
+/// and has remained satisfied for the specified [duration](PoseTolerances::duration). Any
+/// tolerance that isn't configured is always satisfied. As with [`Tolerances`], leaving the
+/// tolerance window resets the settling timer, unless a [debounce](PoseTolerances::debounce)
+/// is configured.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, Instant};
+/// use evian_control::PoseTolerances;
+///
+/// let mut tolerances = PoseTolerances::new()
+///     .translation(1.0)
+///     .heading(0.05)
+///     .velocity(0.5)
+///     .duration(Duration::from_millis(20));
+/// let start = Instant::now();
+///
+/// // At the target position, but still facing the wrong direction.
+/// let check = tolerances.check(0.5, 0.3, 0.0, 0.0, start);
+///
+/// assert!(check.translation_satisfied);
+/// assert!(!check.heading_satisfied);
+/// assert!(!check.is_settled());
+///
+/// // Every component is within tolerance, so we settle once the duration has passed.
+/// assert!(!tolerances.check(0.5, 0.01, 0.0, 0.0, start).is_settled());
+/// let later = start + Duration::from_millis(25);
+/// assert!(tolerances.check(0.5, 0.01, 0.0, 0.0, later).is_settled());
+/// ```
+#[derive(Default, Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct PoseTolerances {
+    timer: SettlingTimer,
+
+    /// Duration for which every tolerance must be satisfied.
+    pub duration: Option<Duration>,
+
+    /// Maximum distance from the target position.
+    pub translation_tolerance: Option<f64>,
+
+    /// Maximum heading error in radians.
+    pub heading_tolerance: Option<f64>,
+
+    /// Maximum linear velocity.
+    pub velocity_tolerance: Option<f64>,
+
+    /// Maximum angular velocity in radians per second.
+    pub angular_velocity_tolerance: Option<f64>,
+
+    /// Number of consecutive out-of-tolerance checks allowed before the tolerance timer resets.
+    pub debounce: u32,
+}
+
+impl PoseTolerances {
+    /// Creates a new [`PoseTolerances`] instance with no configured tolerances or timings.
+    ///
+    /// Until tolerances are configured using the builder methods, all tolerance
+    /// checks will pass immediately.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            timer: SettlingTimer::new(),
+            duration: None,
+            translation_tolerance: None,
+            heading_tolerance: None,
+            velocity_tolerance: None,
+            angular_velocity_tolerance: None,
+            debounce: 0,
+        }
+    }
+
+    /// Sets the maximum acceptable distance from the target position for settling.
+    #[must_use]
+    pub const fn translation(&mut self, tolerance: f64) -> Self {
+        self.translation_tolerance = Some(tolerance);
+        *self
+    }
+
+    /// Sets the maximum acceptable heading error in radians for settling.
+    #[must_use]
+    pub const fn heading(&mut self, tolerance: f64) -> Self {
+        self.heading_tolerance = Some(tolerance);
+        *self
+    }
+
+    /// Sets the maximum acceptable linear velocity for settling.
+    #[must_use]
+    pub const fn velocity(&mut self, tolerance: f64) -> Self {
+        self.velocity_tolerance = Some(tolerance);
+        *self
+    }
+
+    /// Sets the maximum acceptable angular velocity in radians per second for settling.
+    #[must_use]
+    pub const fn angular_velocity(&mut self, tolerance: f64) -> Self {
+        self.angular_velocity_tolerance = Some(tolerance);
+        *self
+    }
+
+    /// Sets how long the system must remain within tolerances to be considered settled.
+    #[must_use]
+    pub const fn duration(&mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        *self
+    }
+
+    /// Sets how many consecutive checks the system may spend outside of tolerance before the
+    /// tolerance timer resets.
+    ///
+    /// See [`Tolerances::debounce`] for more information.
+    #[must_use]
+    pub const fn debounce(&mut self, debounce: u32) -> Self {
+        self.debounce = debounce;
+        *self
+    }
+
+    /// Checks if the system has settled at its target pose.
+    ///
+    /// This method should be called periodically (typically in a control loop) with current
+    /// system measurements. The returned [`PoseToleranceCheck`] describes which settling
+    /// conditions were met.
+    ///
+    /// # Parameters
+    ///
+    /// * `translation_error` - Distance between the target position and the system's position.
+    /// * `heading_error` - Difference between the target heading and the system's heading in
+    ///   radians.
+    /// * `velocity` - Linear speed of the system.
+    /// * `angular_velocity` - Angular speed of the system in radians per second.
+    /// * `now` - The time of this check. See [`Tolerances::check`] for more information.
+    pub fn check(
+        &mut self,
+        translation_error: f64,
+        heading_error: f64,
+        velocity: f64,
+        angular_velocity: f64,
+        now: Instant,
+    ) -> PoseToleranceCheck {
+        let within = |tolerance: Option<f64>, value: f64| {
+            tolerance.is_none_or(|tolerance| value.abs() < tolerance)
+        };
+
+        let translation_satisfied = within(self.translation_tolerance, translation_error);
+        let heading_satisfied = within(self.heading_tolerance, heading_error);
+        let velocity_satisfied = within(self.velocity_tolerance, velocity);
+        let angular_velocity_satisfied =
+            within(self.angular_velocity_tolerance, angular_velocity);
+
+        let (duration_satisfied, remaining_duration) = self.timer.update(
+            translation_satisfied
+                && heading_satisfied
+                && velocity_satisfied
+                && angular_velocity_satisfied,
+            self.duration,
+            self.debounce,
+            now,
+        );
+
+        PoseToleranceCheck {
+            translation_satisfied,
+            heading_satisfied,
+            velocity_satisfied,
+            angular_velocity_satisfied,
+            duration_satisfied,
+            remaining_duration,
+        }
+    }
+}
+
+/// The result of a [`PoseTolerances::check`], describing which settling conditions were met.
+///
+/// This is mostly useful for determining why a system isn't settling.
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct PoseToleranceCheck {
+    /// Whether the translational error was within tolerance (or no tolerance is set).
+    pub translation_satisfied: bool,
+
+    /// Whether the heading error was within tolerance (or no tolerance is set).
+    pub heading_satisfied: bool,
+
+    /// Whether the linear velocity was within tolerance (or no tolerance is set).
+    pub velocity_satisfied: bool,
+
+    /// Whether the angular velocity was within tolerance (or no tolerance is set).
+    pub angular_velocity_satisfied: bool,
+
+    /// Whether the system has stayed within tolerance for the required duration.
+    pub duration_satisfied: bool,
+
+    /// Time the system must remain within tolerance before it is settled.
+    ///
+    /// This is `None` if the settling timer isn't running, such as when the system is out of
+    /// tolerance.
+    pub remaining_duration: Option<Duration>,
+}
+
+impl PoseToleranceCheck {
+    /// Returns `true` if every settling condition was met, meaning the system has settled.
+    #[must_use]
+    pub const fn is_settled(&self) -> bool {
+        self.translation_satisfied
+            && self.heading_satisfied
+            && self.velocity_satisfied
+            && self.angular_velocity_satisfied
+            && self.duration_satisfied
     }
 }
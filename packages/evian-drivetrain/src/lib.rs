@@ -12,11 +12,30 @@
 
 pub mod model;
 
-use evian_tracking::Tracking;
+use core::f64::consts::FRAC_PI_2;
 
-use model::DrivetrainModel;
+use evian_math::{Angle, Vec2};
+use evian_tracking::{Tracking, TracksHeading, TracksPosition};
+use vexide_devices::smart::imu::{CalibrateError, InertialSensor};
+
+use model::{DrivetrainModel, Holonomic};
 
 /// A mobile robot drivetrain capable of measuring data about itself.
+///
+/// # Running Motions
+///
+/// Only one motion should control a drivetrain at a time. Motions hold a mutable borrow of the
+/// drivetrain for as long as they are running, so the borrow checker already prevents two
+/// motions from being started on the same `Drivetrain` at once, and a drivetrain stored in a
+/// `RefCell` will panic if a second motion borrows it while the first is still running.
+///
+/// This can't catch two *different* drivetrains that share the same motors (such as models
+/// created with [`Differential::from_shared`] from the same motor collections). Motions run on
+/// both at once will overwrite each other's motor outputs on every update, producing erratic
+/// motion rather than an error, so shared motors should only ever be part of one drivetrain
+/// that's being driven at a time.
+///
+/// [`Differential::from_shared`]: model::Differential::from_shared
 #[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub struct Drivetrain<M: DrivetrainModel, T: Tracking> {
     /// Motor collection.
@@ -31,4 +50,144 @@ impl<M: DrivetrainModel, T: Tracking> Drivetrain<M, T> {
     pub const fn new(model: M, tracking: T) -> Self {
         Self { model, tracking }
     }
+
+    /// Calibrates an inertial sensor, then creates a new drivetrain from a collection of motors
+    /// and a tracking system that uses the sensor.
+    ///
+    /// `tracking` is called with the sensor once it has finished calibrating. Since the tracking
+    /// system is only created after calibration, it can never read from the sensor while it is
+    /// still calibrating. Calibration takes a few seconds, during which the robot must be kept
+    /// still, and this future doesn't resolve until it completes.
+    ///
+    /// Use [`Drivetrain::new`] to calibrate the sensor separately.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if [`InertialSensor::calibrate`] fails, such as if the sensor is
+    /// disconnected or takes too long to calibrate.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let drivetrain = Drivetrain::new_calibrated(
+    ///     Differential::new(left_motors, right_motors, 3.25, 12.0),
+    ///     InertialSensor::new(peripherals.port_15),
+    ///     |imu| {
+    ///         WheeledTracking::new(
+    ///             (0.0, 0.0),
+    ///             90.0.deg(),
+    ///             [TrackingWheel::new(forwards_enc, 2.0, 0.0, GearRatio::DIRECT)],
+    ///             [TrackingWheel::new(sideways_enc, 2.0, 0.0, GearRatio::DIRECT)],
+    ///             Some(imu),
+    ///         )
+    ///     },
+    /// )
+    /// .await
+    /// .expect("IMU failed to calibrate");
+    /// ```
+    pub async fn new_calibrated(
+        model: M,
+        mut imu: InertialSensor,
+        tracking: impl FnOnce(InertialSensor) -> T,
+    ) -> Result<Self, CalibrateError> {
+        imu.calibrate().await?;
+
+        Ok(Self::new(model, tracking(imu)))
+    }
+
+    /// Borrows the drivetrain's model and tracking system separately.
+    ///
+    /// This allows custom motions to command the model and read from the tracking system at the
+    /// same time without holding a borrow of the whole drivetrain.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let (model, tracking) = drivetrain.split_mut();
+    ///
+    /// let heading_error = (target - tracking.heading()).wrapped_half();
+    /// _ = model.drive_arcade(0.0, heading_error.as_radians() * KP);
+    /// ```
+    pub const fn split_mut(&mut self) -> (&mut M, &mut T) {
+        (&mut self.model, &mut self.tracking)
+    }
+}
+
+impl<M: DrivetrainModel, T: TracksPosition> Drivetrain<M, T> {
+    /// Returns the field heading pointing from the drivetrain's current position towards
+    /// `point`.
+    ///
+    /// This is the heading that the robot would need to turn to in order to face `point`,
+    /// following the same convention as [`TracksHeading::heading`] (0° points along the positive
+    /// x-axis, increasing counterclockwise). The returned angle is in the range `[-π, π]`. If
+    /// the robot is already at `point`, `0°` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evian_drivetrain::{Drivetrain, model::DrivetrainModel};
+    /// use evian_math::{IntoAngle, Vec2};
+    /// use evian_tracking::{Tracking, TracksPosition};
+    ///
+    /// struct Model;
+    /// impl DrivetrainModel for Model {
+    ///     type Error = ();
+    /// }
+    ///
+    /// struct Fixed(Vec2<f64>);
+    /// impl Tracking for Fixed {}
+    /// impl TracksPosition for Fixed {
+    ///     fn position(&self) -> Vec2<f64> {
+    ///         self.0
+    ///     }
+    /// }
+    ///
+    /// let drivetrain = Drivetrain::new(Model, Fixed(Vec2::new(24.0, 24.0)));
+    ///
+    /// assert_eq!(drivetrain.heading_to((48.0, 48.0)), 45.0.deg());
+    /// assert_eq!(drivetrain.heading_to((0.0, 48.0)), 135.0.deg());
+    /// assert_eq!(drivetrain.heading_to((0.0, 0.0)), (-135.0).deg());
+    /// assert_eq!(drivetrain.heading_to((48.0, 0.0)), (-45.0).deg());
+    /// ```
+    pub fn heading_to(&self, point: impl Into<Vec2<f64>>) -> Angle {
+        (point.into() - self.tracking.position()).angle()
+    }
+}
+
+impl<M: Holonomic, T: TracksHeading> Drivetrain<M, T> {
+    /// Drives a holonomic drivetrain relative to the field rather than the robot, using the
+    /// heading reported by the drivetrain's tracking system.
+    ///
+    /// `translation` is given in field coordinates, where positive y points towards a heading of
+    /// 90° and positive x points towards a heading of 0°. This means that pushing a joystick
+    /// forwards will always drive the robot in the same direction across the field, regardless
+    /// of which way the robot is facing. `turn` is passed through unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the underlying call to [`Holonomic::drive_vector`] fails.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let state = controller.state().unwrap_or_default();
+    ///
+    /// _ = drivetrain.drive_field_oriented(
+    ///     (state.left_stick.x(), state.left_stick.y()),
+    ///     state.right_stick.x(),
+    /// );
+    /// ```
+    pub fn drive_field_oriented(
+        &mut self,
+        translation: impl Into<Vec2<f64>>,
+        turn: f64,
+    ) -> Result<(), M::Error> {
+        let heading = self.tracking.heading();
+
+        // Holonomic drive vectors are robot-relative with positive y being forwards, so we rotate
+        // the field-relative translation such that the robot's heading lines up with the y-axis.
+        let vector = translation.into().rotated(FRAC_PI_2 - heading.as_radians());
+
+        self.model.drive_vector(vector, turn)
+    }
 }
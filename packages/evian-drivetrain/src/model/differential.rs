@@ -1,11 +1,19 @@
 extern crate alloc;
 
-use core::cell::RefCell;
-use alloc::rc::Rc;
+use core::{cell::RefCell, f64::consts::PI, time::Duration};
+use alloc::{rc::Rc, vec::Vec};
 
-use vexide_devices::smart::{PortError, motor::Motor};
+use evian_tracking::wheeled::GearRatio;
+use vexide::time::sleep;
+use vexide_devices::{
+    math::Direction,
+    smart::{
+        PortError, SmartDevice, SmartPort,
+        motor::{BrakeMode, Gearset, Motor},
+    },
+};
 
-use super::{DrivetrainModel, Tank};
+use super::{DesaturationPolicy, DrivetrainModel, Tank, apply_deadband};
 
 // MARK: Motors
 
@@ -22,16 +30,60 @@ use super::{DrivetrainModel, Tank};
 ///   robot will turn in place.
 ///
 /// Differential drivetrains are *nonholonomic*, meaning they cannot strafe laterally.
+///
+/// # Geometry
+///
+/// Every differential drivetrain is created with the physical measurements of its wheels,
+/// which are used as the single source of truth for both driving the robot and converting
+/// wheel rotations into distance for odometry:
+///
+/// - The **wheel diameter** is the diameter of the drive wheels, measured in wheel units (the
+///   same units used by tracking and motions, typically inches).
+/// - The **track width** is the distance between the centers of the left and right wheels,
+///   measured in the same units as the wheel diameter.
+///
+/// These can be passed to a tracking system using [`Differential::wheel_diameter`] and
+/// [`Differential::track_width`] so that the drivetrain and tracking always agree.
+///
+/// If the motors are geared to the wheels, the drivetrain's [gearing](Differential::with_gearing)
+/// should also be set so that [velocity commands](Differential::drive_tank_velocity) can be
+/// converted into motor speeds.
+///
+/// # Output Deadband
+///
+/// Very small outputs (such as those produced by a feedback controller as it settles) often
+/// aren't enough to move the robot, and only cause the motors to whine and heat up. An
+/// [output deadband](Differential::with_output_deadband) can be configured to snap any side's
+/// output to zero when its magnitude falls below a threshold.
+///
+/// # Stopping
+///
+/// Motions stop the drivetrain by driving it at zero output once they finish. By default, this
+/// sets the motors to zero volts, which lets the robot coast. A
+/// [brake mode](Differential::with_brake_mode) can be set to actively brake or hold position
+/// instead.
 pub struct Differential {
     /// Left motors.
     pub left: Rc<RefCell<dyn AsMut<[Motor]>>>,
 
     /// Right motors.
     pub right: Rc<RefCell<dyn AsMut<[Motor]>>>,
+
+    wheel_diameter: f64,
+    track_width: f64,
+    gearing: GearRatio,
+    left_trim: f64,
+    right_trim: f64,
+    brake_mode: Option<BrakeMode>,
+    output_deadband: f64,
+    arcade_desaturation_policy: DesaturationPolicy,
 }
 
 impl Differential {
-    /// Creates a new drivetrain with the provided left/right motors.
+    /// Creates a new drivetrain with the provided left/right motors and geometry.
+    ///
+    /// See the [type-level documentation](Differential#geometry) for how to measure
+    /// `wheel_diameter` and `track_width`.
     ///
     /// # Examples
     ///
@@ -45,16 +97,96 @@ impl Differential {
     ///         Motor::new(peripherals.port_3, Gearset::Green, Direction::Reverse),
     ///         Motor::new(peripherals.port_4, Gearset::Green, Direction::Reverse),
     ///     ],
+    ///     3.25,
+    ///     12.0,
     /// );
     /// ```
     pub fn new<L: AsMut<[Motor]> + 'static, R: AsMut<[Motor]> + 'static>(
         left: L,
         right: R,
+        wheel_diameter: f64,
+        track_width: f64,
     ) -> Self {
         Self {
             left: Rc::new(RefCell::new(left)),
             right: Rc::new(RefCell::new(right)),
+            wheel_diameter,
+            track_width,
+            gearing: GearRatio::DIRECT,
+            left_trim: 1.0,
+            right_trim: 1.0,
+            brake_mode: None,
+            output_deadband: 0.0,
+            arcade_desaturation_policy: DesaturationPolicy::Uniform,
+        }
+    }
+
+    /// Creates a new drivetrain from the ports of its left/right motors, using the same gearset
+    /// for every motor.
+    ///
+    /// Each port is paired with the [`Direction`] of the motor plugged into it. Any number of
+    /// motors may be used on each side of the drivetrain, and the two sides don't need to have the
+    /// same number of motors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than one motor uses the same port.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let motors = Differential::from_ports(
+    ///     [
+    ///         (peripherals.port_1, Direction::Forward),
+    ///         (peripherals.port_2, Direction::Forward),
+    ///     ],
+    ///     [
+    ///         (peripherals.port_3, Direction::Reverse),
+    ///         (peripherals.port_4, Direction::Reverse),
+    ///     ],
+    ///     Gearset::Blue,
+    ///     3.25,
+    ///     12.0,
+    /// )
+    /// .with_brake_mode(BrakeMode::Brake);
+    /// ```
+    pub fn from_ports(
+        left: impl IntoIterator<Item = (SmartPort, Direction)>,
+        right: impl IntoIterator<Item = (SmartPort, Direction)>,
+        gearset: Gearset,
+        wheel_diameter: f64,
+        track_width: f64,
+    ) -> Self {
+        let left: Vec<(SmartPort, Direction)> = left.into_iter().collect();
+        let right: Vec<(SmartPort, Direction)> = right.into_iter().collect();
+
+        let mut port_numbers: Vec<u8> = left
+            .iter()
+            .chain(&right)
+            .map(|(port, _)| port.number())
+            .collect();
+        port_numbers.sort_unstable();
+
+        if let Some(duplicate) = port_numbers.windows(2).find(|pair| pair[0] == pair[1]) {
+            panic!(
+                "Differential drivetrain motors must use distinct ports, but port {} was used more than once.",
+                duplicate[0]
+            );
         }
+
+        let into_motors = |ports: Vec<(SmartPort, Direction)>| -> Vec<Motor> {
+            ports
+                .into_iter()
+                .map(|(port, direction)| Motor::new(port, gearset, direction))
+                .collect()
+        };
+
+        Self::new(
+            into_motors(left),
+            into_motors(right),
+            wheel_diameter,
+            track_width,
+        )
     }
 
     /// Creates a new drivetrain with shared ownership of the left/right motors.
@@ -79,6 +211,8 @@ impl Differential {
     ///         Motor::new(peripherals.port_3, Gearset::Green, Direction::Reverse),
     ///         Motor::new(peripherals.port_4, Gearset::Green, Direction::Reverse),
     ///     ])),
+    ///     3.25,
+    ///     12.0,
     /// );
     /// ```
     ///
@@ -98,13 +232,504 @@ impl Differential {
     ///         Motor::new(peripherals.port_3, Gearset::Green, Direction::Reverse),
     ///         Motor::new(peripherals.port_4, Gearset::Green, Direction::Reverse),
     ///     ],
+    ///     3.25,
+    ///     12.0,
     /// );
     /// ```
     pub fn from_shared<L: AsMut<[Motor]> + 'static, R: AsMut<[Motor]> + 'static>(
         left: Rc<RefCell<L>>,
         right: Rc<RefCell<R>>,
+        wheel_diameter: f64,
+        track_width: f64,
     ) -> Self {
-        Self { left, right }
+        Self {
+            left,
+            right,
+            wheel_diameter,
+            track_width,
+            gearing: GearRatio::DIRECT,
+            left_trim: 1.0,
+            right_trim: 1.0,
+            brake_mode: None,
+            output_deadband: 0.0,
+            arcade_desaturation_policy: DesaturationPolicy::Uniform,
+        }
+    }
+
+    /// Returns the diameter of the drivetrain's wheels in wheel units.
+    #[must_use]
+    pub const fn wheel_diameter(&self) -> f64 {
+        self.wheel_diameter
+    }
+
+    /// Returns the distance between the centers of the left and right wheels in wheel units.
+    #[must_use]
+    pub const fn track_width(&self) -> f64 {
+        self.track_width
+    }
+
+    /// Sets the gear ratio between the drivetrain's motors and its wheels.
+    ///
+    /// This is expressed as a `motor:wheel` [`GearRatio`] (where the motor takes the place of the
+    /// ratio's sensor), and is used to convert wheel velocities into motor velocities in
+    /// [`Differential::drive_tank_velocity`]. By default, the motors are assumed to be mounted
+    /// directly to the wheels.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// // 36 tooth gears on the motors driving 48 tooth gears on the wheels.
+    /// let motors = Differential::new(left_motors, right_motors, 3.25, 12.0)
+    ///     .with_gearing(GearRatio::new(48.0, 36.0));
+    /// ```
+    #[must_use]
+    pub fn with_gearing(mut self, gearing: impl Into<GearRatio>) -> Self {
+        self.gearing = gearing.into();
+        self
+    }
+
+    /// Returns the gear ratio between the drivetrain's motors and its wheels.
+    #[must_use]
+    pub const fn gearing(&self) -> GearRatio {
+        self.gearing
+    }
+
+    /// Sets the gear ratio between the drivetrain's motors and its wheels.
+    ///
+    /// See [`Differential::with_gearing`] for more information.
+    pub fn set_gearing(&mut self, gearing: impl Into<GearRatio>) {
+        self.gearing = gearing.into();
+    }
+
+    /// Applies a trim to the output of each side of the drivetrain.
+    ///
+    /// Trim values are multipliers applied to the left and right motor outputs before they are
+    /// sent to the motors, and are `1.0` (no trim) by default. This can be used to compensate for
+    /// a consistent mechanical asymmetry between each side of the drivetrain, such as one side
+    /// being slightly faster than the other.
+    ///
+    /// Trim is only meant to correct for steady bias, and is not a substitute for closed-loop
+    /// heading control.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// // Left side is about 3% faster than the right side.
+    /// let motors = Differential::new(left_motors, right_motors, 3.25, 12.0).with_trim(0.97, 1.0);
+    /// ```
+    #[must_use]
+    pub const fn with_trim(mut self, left_scale: f64, right_scale: f64) -> Self {
+        self.left_trim = left_scale;
+        self.right_trim = right_scale;
+        self
+    }
+
+    /// Returns the trim applied to the left and right sides of the drivetrain as a tuple.
+    #[must_use]
+    pub const fn trim(&self) -> (f64, f64) {
+        (self.left_trim, self.right_trim)
+    }
+
+    /// Sets the trim applied to the left and right sides of the drivetrain.
+    ///
+    /// See [`Differential::with_trim`] for more information.
+    pub const fn set_trim(&mut self, left_scale: f64, right_scale: f64) {
+        self.left_trim = left_scale;
+        self.right_trim = right_scale;
+    }
+
+    /// Sets the brake mode used by every motor on the drivetrain when commanded to stop.
+    ///
+    /// When a brake mode is set, any side of the drivetrain that is driven at exactly zero output
+    /// will be stopped using that [`BrakeMode`] rather than by setting its motors to zero volts.
+    /// By default, no brake mode is used, so a stopped side is set to zero volts and the robot
+    /// coasts. This includes the end of every motion, which stops the drivetrain by driving it at
+    /// zero output.
+    #[must_use]
+    pub const fn with_brake_mode(mut self, mode: BrakeMode) -> Self {
+        self.brake_mode = Some(mode);
+        self
+    }
+
+    /// Returns the brake mode used when the drivetrain is commanded to stop, if any.
+    #[must_use]
+    pub const fn brake_mode(&self) -> Option<BrakeMode> {
+        self.brake_mode
+    }
+
+    /// Sets the brake mode used when the drivetrain is commanded to stop.
+    ///
+    /// See [`Differential::with_brake_mode`] for more information.
+    pub const fn set_brake_mode(&mut self, mode: Option<BrakeMode>) {
+        self.brake_mode = mode;
+    }
+
+    /// Sets a deadband below which the output of each side of the drivetrain is snapped to zero.
+    ///
+    /// The deadband is applied to the final output of each side, after trim and any
+    /// desaturation done by [`Arcade::drive_arcade`](super::Arcade::drive_arcade). Outputs
+    /// snapped to zero are treated as a stop, so they'll use the drivetrain's
+    /// [brake mode](Differential::with_brake_mode) if one is set. By default, the deadband is
+    /// `0.0` (disabled).
+    ///
+    /// Keep this threshold small. Small outputs are also what a motion uses to hold its position
+    /// against an external push (such as from another robot), so a deadband that is too large
+    /// can prevent the robot from resisting being moved.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let motors = Differential::new(left_motors, right_motors, 3.25, 12.0)
+    ///     .with_output_deadband(0.03);
+    /// ```
+    #[must_use]
+    pub const fn with_output_deadband(mut self, threshold: f64) -> Self {
+        self.output_deadband = threshold;
+        self
+    }
+
+    /// Returns the output deadband of the drivetrain.
+    #[must_use]
+    pub const fn output_deadband(&self) -> f64 {
+        self.output_deadband
+    }
+
+    /// Sets the output deadband of the drivetrain.
+    ///
+    /// See [`Differential::with_output_deadband`] for more information.
+    pub const fn set_output_deadband(&mut self, threshold: f64) {
+        self.output_deadband = threshold;
+    }
+
+    /// Sets the [`DesaturationPolicy`] used by [`Arcade::drive_arcade`] when throttle and steer
+    /// add up to more than full power.
+    ///
+    /// By default, [`DesaturationPolicy::Uniform`] is used. See the [`Arcade`] trait for the
+    /// tradeoffs between each policy.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let motors = Differential::new(left_motors, right_motors, 3.25, 12.0)
+    ///     .with_arcade_desaturation_policy(DesaturationPolicy::PreserveRotation);
+    /// ```
+    ///
+    /// [`Arcade`]: super::Arcade
+    /// [`Arcade::drive_arcade`]: super::Arcade::drive_arcade
+    #[must_use]
+    pub const fn with_arcade_desaturation_policy(mut self, policy: DesaturationPolicy) -> Self {
+        self.arcade_desaturation_policy = policy;
+        self
+    }
+
+    /// Returns the [`DesaturationPolicy`] used by arcade commands.
+    #[must_use]
+    pub const fn arcade_desaturation_policy(&self) -> DesaturationPolicy {
+        self.arcade_desaturation_policy
+    }
+
+    /// Sets the [`DesaturationPolicy`] used by arcade commands.
+    ///
+    /// See [`Differential::with_arcade_desaturation_policy`] for more information.
+    pub const fn set_arcade_desaturation_policy(&mut self, policy: DesaturationPolicy) {
+        self.arcade_desaturation_policy = policy;
+    }
+
+    /// Drives each side of the drivetrain at a given voltage.
+    ///
+    /// Unlike [`Tank::drive_tank`], which takes a normalized power from `-1.0` to `1.0` and
+    /// scales it by each motor's maximum voltage, this method sends `left` and `right` to the
+    /// motors directly in volts. This is useful when outputs are already computed in volts, such
+    /// as by a feedforward model characterized in volts, since no conversion between units is
+    /// needed.
+    ///
+    /// # Units
+    ///
+    /// The maximum voltage a motor accepts depends on the type of motor rather than its gearset
+    /// (12V for V5 motors and 8V for EXP motors), so the same voltage produces the same output
+    /// regardless of gearset. Voltages are clamped to each motor's
+    /// [maximum voltage](Motor::max_voltage), meaning a drivetrain mixing V5 and EXP motors may
+    /// have its EXP motors clamped while its V5 motors aren't.
+    ///
+    /// Since [`desaturate`](evian_math::desaturate) operates on normalized powers, voltages
+    /// passed to this method aren't desaturated. If a combined command may exceed the motors'
+    /// limits, it should be desaturated with a maximum of the motors' maximum voltage (such as
+    /// `desaturate([left, right], 12.0)`) before being passed here so that the ratio between
+    /// each side is preserved.
+    ///
+    /// Trim and brake mode are applied as they are in [`Tank::drive_tank`]. The
+    /// [output deadband](Differential::with_output_deadband) is specified in normalized units, so
+    /// it's compared against each voltage as a fraction of the motor's maximum voltage.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if setting the voltage of any motor fails.
+    pub fn drive_tank_voltage(&mut self, left: f64, right: f64) -> Result<(), PortError> {
+        let mut rtn = Ok(());
+
+        let left = left * self.left_trim;
+        let right = right * self.right_trim;
+
+        for motor in self.left.borrow_mut().as_mut() {
+            let max_voltage = motor.max_voltage();
+            let voltage = max_voltage
+                * apply_deadband((left / max_voltage).clamp(-1.0, 1.0), self.output_deadband);
+
+            let result = match self.brake_mode {
+                Some(mode) if voltage == 0.0 => motor.brake(mode),
+                _ => motor.set_voltage(voltage),
+            };
+
+            if result.is_err() {
+                rtn = result;
+            }
+        }
+
+        for motor in self.right.borrow_mut().as_mut() {
+            let max_voltage = motor.max_voltage();
+            let voltage = max_voltage
+                * apply_deadband((right / max_voltage).clamp(-1.0, 1.0), self.output_deadband);
+
+            let result = match self.brake_mode {
+                Some(mode) if voltage == 0.0 => motor.brake(mode),
+                _ => motor.set_voltage(voltage),
+            };
+
+            if result.is_err() {
+                rtn = result;
+            }
+        }
+
+        rtn
+    }
+
+    /// Drives each side of the drivetrain at a given wheel velocity.
+    ///
+    /// `left` and `right` are given in wheel units per second (the same units as
+    /// [`TracksVelocity`](evian_tracking::TracksVelocity)), and are converted into motor RPM
+    /// using the drivetrain's wheel diameter and [gearing](Differential::with_gearing). Each
+    /// motor then holds its velocity using its onboard velocity controller, rather than being
+    /// driven at a fixed voltage as in [`Tank::drive_tank`]. Velocities beyond what a motor's
+    /// gearset can reach are limited by the motor.
+    ///
+    /// Trim and brake mode are applied as they are in [`Tank::drive_tank`]. The
+    /// [output deadband](Differential::with_output_deadband) is specified in normalized units,
+    /// so it isn't applied to velocities.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if setting the velocity of any motor fails.
+    pub fn drive_tank_velocity(&mut self, left: f64, right: f64) -> Result<(), PortError> {
+        let mut rtn = Ok(());
+
+        // Converts a wheel velocity into the motor RPM needed to drive the wheel at it.
+        let wheel_circumference = self.wheel_diameter * PI;
+        let to_rpm = |velocity: f64| {
+            (velocity / wheel_circumference / self.gearing.multiplier() * 60.0).round() as i32
+        };
+
+        let left = to_rpm(left * self.left_trim);
+        let right = to_rpm(right * self.right_trim);
+
+        for motor in self.left.borrow_mut().as_mut() {
+            let result = match self.brake_mode {
+                Some(mode) if left == 0 => motor.brake(mode),
+                _ => motor.set_velocity(left),
+            };
+
+            if result.is_err() {
+                rtn = result;
+            }
+        }
+
+        for motor in self.right.borrow_mut().as_mut() {
+            let result = match self.brake_mode {
+                Some(mode) if right == 0 => motor.brake(mode),
+                _ => motor.set_velocity(right),
+            };
+
+            if result.is_err() {
+                rtn = result;
+            }
+        }
+
+        rtn
+    }
+
+    /// Turns the robot in place at a given angular velocity.
+    ///
+    /// `angular_velocity` is given in radians per second, with positive values turning
+    /// anticlockwise (the same convention as [`TracksVelocity`](evian_tracking::TracksVelocity)).
+    /// Using the drivetrain's track width, each side is driven at
+    ///
+    /// ```text
+    /// right = angular_velocity * track_width / 2
+    /// left = -right
+    /// ```
+    ///
+    /// through [`Differential::drive_tank_velocity`]. Unlike turning with
+    /// [`Arcade::drive_arcade`](super::Arcade::drive_arcade), where steering is a normalized
+    /// power whose resulting turn rate depends on the robot, this commands a specific turn rate.
+    ///
+    /// Wheel scrub makes a real drivetrain turn slightly slower than its kinematics predict, so
+    /// turns that must be precise should still be closed around a heading measurement.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if setting the velocity of any motor fails.
+    pub fn point_turn(&mut self, angular_velocity: f64) -> Result<(), PortError> {
+        let wheel_velocity = angular_velocity * self.track_width / 2.0;
+
+        self.drive_tank_velocity(-wheel_velocity, wheel_velocity)
+    }
+}
+
+// MARK: Self-Test
+
+/// The outcome of checking a single motor with [`Differential::verify`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum MotorStatus {
+    /// The motor spun in the direction it was commanded to.
+    Agrees,
+
+    /// The motor spun opposite to the direction it was commanded to, meaning it was being
+    /// back-driven by the other motors on its side. This usually means the motor was created
+    /// with the wrong [`Direction`].
+    Reversed,
+
+    /// The motor barely moved. This can happen if the motors on a side are evenly split between
+    /// directions and stall against each other, or if the drivetrain is jammed.
+    NotMoving,
+
+    /// The motor's velocity couldn't be read, typically because it is disconnected.
+    Unreadable,
+}
+
+/// The result of checking a single motor with [`Differential::verify`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MotorReport {
+    /// The smart port the motor is plugged into.
+    pub port: u8,
+
+    /// The motor's measured velocity in RPM at the end of its pulse, if it could be read.
+    pub velocity: Option<f64>,
+
+    /// Whether the motor's measured direction agreed with its commanded direction.
+    pub status: MotorStatus,
+}
+
+/// A report on the motors of a [`Differential`] drivetrain, created by [`Differential::verify`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifyReport {
+    /// Reports for each left motor.
+    pub left: Vec<MotorReport>,
+
+    /// Reports for each right motor.
+    pub right: Vec<MotorReport>,
+}
+
+impl VerifyReport {
+    /// Returns `true` if every motor spun in the direction it was commanded to.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.flagged().next().is_none()
+    }
+
+    /// Returns an iterator over every motor that didn't spin in the direction it was commanded
+    /// to.
+    pub fn flagged(&self) -> impl Iterator<Item = &MotorReport> {
+        self.left
+            .iter()
+            .chain(&self.right)
+            .filter(|report| report.status != MotorStatus::Agrees)
+    }
+}
+
+impl Differential {
+    /// Normalized power that each side of the drivetrain is pulsed at by [`Differential::verify`].
+    pub const VERIFY_OUTPUT: f64 = 0.2;
+
+    /// Duration that each side of the drivetrain is pulsed for by [`Differential::verify`].
+    pub const VERIFY_DURATION: Duration = Duration::from_millis(300);
+
+    /// Velocity in RPM below which [`Differential::verify`] considers a motor to not be moving.
+    pub const VERIFY_VELOCITY_THRESHOLD: f64 = 5.0;
+
+    /// Checks for motors that are spinning against the rest of their side of the drivetrain.
+    ///
+    /// A common setup mistake is creating one motor with the wrong [`Direction`], causing it to
+    /// fight the other motors on its side. This method briefly pulses each side of the
+    /// drivetrain forward (one at a time) at [a low power](Self::VERIFY_OUTPUT), then reads back
+    /// every motor's velocity and reports any motor whose measured direction disagrees with its
+    /// commanded direction. A motor that is overpowered by the rest of its side will be
+    /// back-driven, so its velocity will have the opposite sign of its command.
+    ///
+    /// This is intended to be run during pre-match setup with the robot on the ground and clear of
+    /// obstacles, since the robot will turn slightly while each side is pulsed. Both sides are
+    /// stopped once verification completes.
+    ///
+    /// # Limitations
+    ///
+    /// Each motor's velocity is measured relative to its own configured direction, so this can
+    /// only detect motors that disagree with the other motors on their side. A side whose motors
+    /// are *all* reversed will spin backwards while every motor reports that it is agreeing with
+    /// its command. To catch this, check that the robot turns clockwise while the left side is
+    /// pulsed (or verify that a tracking system reports positive forward travel when driving
+    /// forward).
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let report = drivetrain.model.verify().await;
+    ///
+    /// for motor in report.flagged() {
+    ///     println!("Motor on port {} is {:?}", motor.port, motor.status);
+    /// }
+    /// ```
+    pub async fn verify(&mut self) -> VerifyReport {
+        let left = Self::pulse(&self.left).await;
+        let right = Self::pulse(&self.right).await;
+
+        VerifyReport { left, right }
+    }
+
+    /// Pulses one side of the drivetrain forward, returning a report for each of its motors.
+    async fn pulse(motors: &RefCell<dyn AsMut<[Motor]>>) -> Vec<MotorReport> {
+        // Borrows of the motors aren't held across awaits, since they may be shared with another
+        // drivetrain.
+        for motor in motors.borrow_mut().as_mut() {
+            _ = motor.set_voltage(Self::VERIFY_OUTPUT * motor.max_voltage());
+        }
+
+        sleep(Self::VERIFY_DURATION).await;
+
+        let reports = motors
+            .borrow_mut()
+            .as_mut()
+            .iter_mut()
+            .map(|motor| {
+                let velocity = motor.velocity().ok();
+                _ = motor.set_voltage(0.0);
+
+                MotorReport {
+                    port: motor.port_number(),
+                    velocity,
+                    status: match velocity {
+                        None => MotorStatus::Unreadable,
+                        Some(v) if v.abs() < Self::VERIFY_VELOCITY_THRESHOLD => {
+                            MotorStatus::NotMoving
+                        }
+                        Some(v) if v < 0.0 => MotorStatus::Reversed,
+                        Some(_) => MotorStatus::Agrees,
+                    },
+                }
+            })
+            .collect();
+
+        // Let the robot come to rest before the next side is pulsed.
+        sleep(Self::VERIFY_DURATION).await;
+
+        reports
     }
 }
 
@@ -118,8 +743,14 @@ impl Tank for Differential {
     fn drive_tank(&mut self, left: f64, right: f64) -> Result<(), Self::Error> {
         let mut rtn = Ok(());
 
+        let left = apply_deadband(left * self.left_trim, self.output_deadband);
+        let right = apply_deadband(right * self.right_trim, self.output_deadband);
+
         for motor in self.left.borrow_mut().as_mut() {
-            let result = motor.set_voltage(left * motor.max_voltage());
+            let result = match self.brake_mode {
+                Some(mode) if left == 0.0 => motor.brake(mode),
+                _ => motor.set_voltage(left * motor.max_voltage()),
+            };
 
             if result.is_err() {
                 rtn = result;
@@ -127,7 +758,10 @@ impl Tank for Differential {
         }
 
         for motor in self.right.borrow_mut().as_mut() {
-            let result = motor.set_voltage(right * motor.max_voltage());
+            let result = match self.brake_mode {
+                Some(mode) if right == 0.0 => motor.brake(mode),
+                _ => motor.set_voltage(right * motor.max_voltage()),
+            };
 
             if result.is_err() {
                 rtn = result;
@@ -136,4 +770,8 @@ impl Tank for Differential {
 
         rtn
     }
+
+    fn arcade_desaturation_policy(&self) -> DesaturationPolicy {
+        self.arcade_desaturation_policy
+    }
 }
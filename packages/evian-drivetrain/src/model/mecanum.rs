@@ -3,12 +3,66 @@ extern crate alloc;
 use core::cell::RefCell;
 use alloc::rc::Rc;
 
-use evian_math::{Vec2, desaturate};
-use vexide_devices::smart::{PortError, motor::Motor};
+use evian_math::{Angle, Vec2, desaturate};
+use evian_tracking::{Gyro, RotarySensor, mecanum::MecanumTracking, wheeled::GearRatio};
+use vexide_devices::smart::{
+    PortError,
+    motor::{BrakeMode, Motor},
+};
 
-use super::{DrivetrainModel, Holonomic, Tank};
+use super::{
+    DesaturationPolicy, DrivetrainModel, Holonomic, Tank, apply_deadband, desaturate_holonomic,
+    holonomic_components, set_motor_power, set_wheel_powers,
+};
 
 /// Mecanum drivetrain model.
+///
+/// Mecanum drivetrains are *holonomic*, and are primarily driven through the [`Holonomic`] trait.
+///
+/// # Compatibility with Nonholonomic Motions
+///
+/// This model also implements [`Tank`] (and therefore [`Arcade`]) by driving the left and right
+/// wheels as if the robot were a differential drivetrain. This allows mecanum drivetrains to reuse
+/// motions written for [`Tank`] or [`Arcade`] drivetrains, such as `drive_distance` or
+/// `turn_to_heading`. Driving through this compatibility path is equivalent to calling
+/// [`Holonomic::drive_vector`] with a purely forward vector, meaning that the drivetrain's ability
+/// to strafe goes unused.
+///
+/// # Geometry
+///
+/// Every mecanum drivetrain is created with the physical measurements of its wheels, which are
+/// used as the single source of truth for both driving the robot and for odometry (such as by
+/// creating its tracking system through [`Mecanum::tracking`]):
+///
+/// - The **wheel diameter** is the diameter of the mecanum wheels, measured in wheel units (the
+///   same units used by tracking and motions, typically inches).
+/// - The **track width** is the distance between the centers of the left and right wheels,
+///   measured in the same units as the wheel diameter.
+/// - The **wheelbase** is the distance between the centers of the front and back wheels,
+///   measured in the same units as the wheel diameter.
+///
+/// # Desaturation
+///
+/// When a translation and turn are commanded that add up to more than a wheel can output,
+/// [`Holonomic::drive_vector`] scales the wheel outputs down using the drivetrain's
+/// [`DesaturationPolicy`]. By default, all four wheels are scaled uniformly, which slows down the
+/// robot's turning along with its translation. Using [`DesaturationPolicy::PreserveRotation`]
+/// instead reserves headroom for the rotational component first, which keeps heading control
+/// responsive during aggressive strafing.
+///
+/// # Output Deadband
+///
+/// Very small outputs often aren't enough to move the robot, and only cause the motors to whine
+/// and heat up. An [output deadband](Mecanum::with_output_deadband) can be configured to snap
+/// any wheel's output to zero when its magnitude falls below a threshold.
+///
+/// # Stopping
+///
+/// Motions stop the drivetrain by driving it at zero output once they finish. By default, this
+/// sets the motors to zero volts, which lets the robot coast. A
+/// [brake mode](Mecanum::with_brake_mode) can be set to actively brake or hold position instead.
+///
+/// [`Arcade`]: super::Arcade
 pub struct Mecanum {
     /// Motors driving the front-left wheel(s).
     pub front_left_motors: Rc<RefCell<dyn AsMut<[Motor]>>>,
@@ -21,49 +75,299 @@ pub struct Mecanum {
 
     /// Motors driving the back-right wheel(s).
     pub back_right_motors: Rc<RefCell<dyn AsMut<[Motor]>>>,
-}
 
-impl DrivetrainModel for Mecanum {
-    type Error = PortError;
+    wheel_diameter: f64,
+    track_width: f64,
+    wheelbase: f64,
+    desaturation_policy: DesaturationPolicy,
+    output_deadband: f64,
+    brake_mode: Option<BrakeMode>,
 }
 
-impl Holonomic for Mecanum {
-    fn drive_vector(&mut self, vector: Vec2<f64>, turn: f64) -> Result<(), Self::Error> {
-        let [fl, fr, bl, br] = desaturate(
+impl Mecanum {
+    /// Creates a new drivetrain with the provided front-left, front-right, back-left, and
+    /// back-right motors and geometry.
+    ///
+    /// See the [type-level documentation](Mecanum#geometry) for how to measure `wheel_diameter`,
+    /// `track_width`, and `wheelbase`.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let motors = Mecanum::new(
+    ///     [Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward)],
+    ///     [Motor::new(peripherals.port_2, Gearset::Green, Direction::Reverse)],
+    ///     [Motor::new(peripherals.port_3, Gearset::Green, Direction::Forward)],
+    ///     [Motor::new(peripherals.port_4, Gearset::Green, Direction::Reverse)],
+    ///     4.0,
+    ///     13.5,
+    ///     12.0,
+    /// )
+    /// .with_desaturation_policy(DesaturationPolicy::PreserveRotation);
+    /// ```
+    pub fn new<
+        FL: AsMut<[Motor]> + 'static,
+        FR: AsMut<[Motor]> + 'static,
+        BL: AsMut<[Motor]> + 'static,
+        BR: AsMut<[Motor]> + 'static,
+    >(
+        front_left: FL,
+        front_right: FR,
+        back_left: BL,
+        back_right: BR,
+        wheel_diameter: f64,
+        track_width: f64,
+        wheelbase: f64,
+    ) -> Self {
+        Self {
+            front_left_motors: Rc::new(RefCell::new(front_left)),
+            front_right_motors: Rc::new(RefCell::new(front_right)),
+            back_left_motors: Rc::new(RefCell::new(back_left)),
+            back_right_motors: Rc::new(RefCell::new(back_right)),
+            wheel_diameter,
+            track_width,
+            wheelbase,
+            desaturation_policy: DesaturationPolicy::Uniform,
+            output_deadband: 0.0,
+            brake_mode: None,
+        }
+    }
+
+    /// Returns the diameter of the drivetrain's wheels in wheel units.
+    #[must_use]
+    pub const fn wheel_diameter(&self) -> f64 {
+        self.wheel_diameter
+    }
+
+    /// Returns the distance between the centers of the left and right wheels in wheel units.
+    #[must_use]
+    pub const fn track_width(&self) -> f64 {
+        self.track_width
+    }
+
+    /// Returns the distance between the centers of the front and back wheels in wheel units.
+    #[must_use]
+    pub const fn wheelbase(&self) -> f64 {
+        self.wheelbase
+    }
+
+    /// Creates a [`MecanumTracking`] system that measures the drivetrain's own motors.
+    ///
+    /// The tracking system shares this drivetrain's motors and is created with its
+    /// [wheel diameter](Mecanum::wheel_diameter), so the model and tracking always agree on the
+    /// drivetrain's geometry. Each wheel's position is the average of the motors driving it.
+    ///
+    /// See [`MecanumTracking::new`] for more information on the parameters.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let motors = Mecanum::new(
+    ///     [Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward)],
+    ///     [Motor::new(peripherals.port_2, Gearset::Green, Direction::Reverse)],
+    ///     [Motor::new(peripherals.port_3, Gearset::Green, Direction::Forward)],
+    ///     [Motor::new(peripherals.port_4, Gearset::Green, Direction::Reverse)],
+    ///     4.0,
+    ///     13.5,
+    ///     12.0,
+    /// );
+    ///
+    /// let tracking = motors.tracking(
+    ///     Vec2::default(),
+    ///     90.0.deg(),
+    ///     1.0,
+    ///     InertialSensor::new(peripherals.port_5),
+    /// );
+    ///
+    /// let mut drivetrain = Drivetrain::new(motors, tracking);
+    /// ```
+    pub fn tracking<G: Gyro + 'static>(
+        &self,
+        origin: impl Into<Vec2<f64>>,
+        heading: Angle,
+        gearing: impl Into<GearRatio>,
+        gyro: G,
+    ) -> MecanumTracking {
+        MecanumTracking::new(
+            origin,
+            heading,
             [
-                vector.y + vector.x + turn,
-                vector.y - vector.x - turn,
-                vector.y - vector.x + turn,
-                vector.y + vector.x - turn,
+                WheelMotors(self.front_left_motors.clone()),
+                WheelMotors(self.front_right_motors.clone()),
+                WheelMotors(self.back_left_motors.clone()),
+                WheelMotors(self.back_right_motors.clone()),
             ],
-            1.0,
-        );
+            self.wheel_diameter,
+            gearing,
+            gyro,
+        )
+    }
 
-        let mut rtn = Ok(());
+    /// Sets the [`DesaturationPolicy`] used by [`Holonomic::drive_vector`] when the wheel
+    /// outputs exceed what the motors can output.
+    ///
+    /// By default, [`DesaturationPolicy::Uniform`] is used.
+    #[must_use]
+    pub const fn with_desaturation_policy(mut self, policy: DesaturationPolicy) -> Self {
+        self.desaturation_policy = policy;
+        self
+    }
 
-        for motor in self.front_left_motors.borrow_mut().as_mut() {
-            let result = motor.set_voltage(fl * motor.max_voltage());
+    /// Returns the [`DesaturationPolicy`] used by [`Holonomic::drive_vector`].
+    #[must_use]
+    pub const fn desaturation_policy(&self) -> DesaturationPolicy {
+        self.desaturation_policy
+    }
 
-            if result.is_err() {
-                rtn = result;
-            }
-        }
-        for motor in self.front_right_motors.borrow_mut().as_mut() {
-            let result = motor.set_voltage(fr * motor.max_voltage());
+    /// Changes the [`DesaturationPolicy`] used by [`Holonomic::drive_vector`].
+    pub const fn set_desaturation_policy(&mut self, policy: DesaturationPolicy) {
+        self.desaturation_policy = policy;
+    }
 
-            if result.is_err() {
-                rtn = result;
-            }
-        }
-        for motor in self.back_left_motors.borrow_mut().as_mut() {
-            let result = motor.set_voltage(bl * motor.max_voltage());
+    /// Sets the brake mode used by every motor on the drivetrain when commanded to stop.
+    ///
+    /// When a brake mode is set, any wheel that is driven at exactly zero output (including
+    /// outputs snapped to zero by the [output deadband](Mecanum::with_output_deadband)) will be
+    /// stopped using that [`BrakeMode`]. By default, no brake mode is used, so stopped wheels
+    /// are set to zero volts and the robot coasts.
+    #[must_use]
+    pub const fn with_brake_mode(mut self, mode: BrakeMode) -> Self {
+        self.brake_mode = Some(mode);
+        self
+    }
+
+    /// Returns the brake mode used when the drivetrain is commanded to stop, if any.
+    #[must_use]
+    pub const fn brake_mode(&self) -> Option<BrakeMode> {
+        self.brake_mode
+    }
+
+    /// Sets the brake mode used when the drivetrain is commanded to stop.
+    ///
+    /// See [`Mecanum::with_brake_mode`] for more information.
+    pub const fn set_brake_mode(&mut self, mode: Option<BrakeMode>) {
+        self.brake_mode = mode;
+    }
+
+    /// Sets a deadband below which the output of each wheel is snapped to zero.
+    ///
+    /// The deadband is applied to the final output of each wheel in [`Holonomic::drive_vector`]
+    /// and [`Tank::drive_tank`], after desaturation. It isn't applied by [`Mecanum::drive_raw`].
+    /// By default, the deadband is `0.0` (disabled).
+    ///
+    /// Keep this threshold small. Small outputs are also what a motion uses to hold its position
+    /// against an external push (such as from another robot), so a deadband that is too large
+    /// can prevent the robot from resisting being moved.
+    #[must_use]
+    pub const fn with_output_deadband(mut self, threshold: f64) -> Self {
+        self.output_deadband = threshold;
+        self
+    }
+
+    /// Returns the output deadband of the drivetrain.
+    #[must_use]
+    pub const fn output_deadband(&self) -> f64 {
+        self.output_deadband
+    }
+
+    /// Sets the output deadband of the drivetrain.
+    ///
+    /// See [`Mecanum::with_output_deadband`] for more information.
+    pub const fn set_output_deadband(&mut self, threshold: f64) {
+        self.output_deadband = threshold;
+    }
+
+    /// Drives the robot using a translation vector and rotational power, desaturating the
+    /// wheel outputs using a given [`DesaturationPolicy`].
+    ///
+    /// [`Holonomic::drive_vector`] is equivalent to calling this method with the drivetrain's
+    /// [configured policy](Mecanum::desaturation_policy).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if setting the voltage of any motor fails.
+    pub fn drive_vector_with_policy(
+        &mut self,
+        vector: Vec2<f64>,
+        turn: f64,
+        policy: DesaturationPolicy,
+    ) -> Result<(), PortError> {
+        // Mecanum rollers push each wheel along a 45° diagonal.
+        let (translation, rotation) = holonomic_components(vector, turn, Vec2::new(1.0, 1.0));
+        let powers = desaturate_holonomic(translation, rotation, 1.0, policy);
+
+        self.set_wheel_powers(powers.map(|power| apply_deadband(power, self.output_deadband)))
+    }
+
+    /// Drives each wheel of the drivetrain at a given power, bypassing inverse kinematics.
+    ///
+    /// `powers` are given in front-left, front-right, back-left, back-right order, ranging from
+    /// `-1.0` to `1.0`. No kinematic mixing is applied, so positive power spins each wheel
+    /// forwards. If any power exceeds `1.0` in magnitude, all four are scaled down uniformly
+    /// using [`desaturate`].
+    ///
+    /// This is mainly useful for diagnosing wiring or motor direction issues, or for custom
+    /// control schemes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if setting the voltage of any motor fails.
+    pub fn drive_raw(&mut self, powers: [f64; 4]) -> Result<(), PortError> {
+        self.set_wheel_powers(desaturate(powers, 1.0))
+    }
+
+    /// Drives the left and right wheels of the drivetrain at a given voltage.
+    ///
+    /// This is the voltage equivalent of [`Tank::drive_tank`], which takes a normalized power
+    /// from `-1.0` to `1.0` and scales it by each motor's maximum voltage. Here, `left` and
+    /// `right` are sent to the motors directly in volts, which is useful when outputs are
+    /// computed by a feedforward model characterized in volts.
+    ///
+    /// # Units
+    ///
+    /// The maximum voltage a motor accepts depends on the type of motor rather than its gearset
+    /// (12V for V5 motors and 8V for EXP motors). Voltages are clamped to each motor's
+    /// [maximum voltage](Motor::max_voltage).
+    ///
+    /// Since [`desaturate`] operates on normalized powers, voltages passed to this method aren't
+    /// desaturated. If a combined command may exceed the motors' limits, it should be
+    /// desaturated with a maximum of the motors' maximum voltage (such as
+    /// `desaturate([left, right], 12.0)`) before being passed here. The
+    /// [output deadband](Mecanum::with_output_deadband) is compared against each voltage as a
+    /// fraction of the motor's maximum voltage.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if setting the voltage of any motor fails.
+    pub fn drive_tank_voltage(&mut self, left: f64, right: f64) -> Result<(), PortError> {
+        let mut rtn = Ok(());
+
+        for motor in self
+            .front_left_motors
+            .borrow_mut()
+            .as_mut()
+            .iter_mut()
+            .chain(self.back_left_motors.borrow_mut().as_mut())
+        {
+            let max_voltage = motor.max_voltage();
+            let power = apply_deadband((left / max_voltage).clamp(-1.0, 1.0), self.output_deadband);
+            let result = set_motor_power(motor, power, self.brake_mode);
 
             if result.is_err() {
                 rtn = result;
             }
         }
-        for motor in self.back_right_motors.borrow_mut().as_mut() {
-            let result = motor.set_voltage(br * motor.max_voltage());
+        for motor in self
+            .front_right_motors
+            .borrow_mut()
+            .as_mut()
+            .iter_mut()
+            .chain(self.back_right_motors.borrow_mut().as_mut())
+        {
+            let max_voltage = motor.max_voltage();
+            let power =
+                apply_deadband((right / max_voltage).clamp(-1.0, 1.0), self.output_deadband);
+            let result = set_motor_power(motor, power, self.brake_mode);
 
             if result.is_err() {
                 rtn = result;
@@ -72,12 +376,39 @@ impl Holonomic for Mecanum {
 
         rtn
     }
+
+    /// Sets the voltage of each wheel's motors from a power in FL/FR/BL/BR order.
+    fn set_wheel_powers(&mut self, powers: [f64; 4]) -> Result<(), PortError> {
+        set_wheel_powers(
+            [
+                &*self.front_left_motors,
+                &*self.front_right_motors,
+                &*self.back_left_motors,
+                &*self.back_right_motors,
+            ],
+            powers,
+            self.brake_mode,
+        )
+    }
+}
+
+impl DrivetrainModel for Mecanum {
+    type Error = PortError;
+}
+
+impl Holonomic for Mecanum {
+    fn drive_vector(&mut self, vector: Vec2<f64>, turn: f64) -> Result<(), Self::Error> {
+        self.drive_vector_with_policy(vector, turn, self.desaturation_policy)
+    }
 }
 
 impl Tank for Mecanum {
     fn drive_tank(&mut self, left: f64, right: f64) -> Result<(), Self::Error> {
         let mut rtn = Ok(());
 
+        let left = apply_deadband(left, self.output_deadband);
+        let right = apply_deadband(right, self.output_deadband);
+
         for motor in self
             .front_left_motors
             .borrow_mut()
@@ -85,7 +416,7 @@ impl Tank for Mecanum {
             .iter_mut()
             .chain(self.back_left_motors.borrow_mut().as_mut())
         {
-            let result = motor.set_voltage(left * motor.max_voltage());
+            let result = set_motor_power(motor, left, self.brake_mode);
 
             if result.is_err() {
                 rtn = result;
@@ -98,7 +429,7 @@ impl Tank for Mecanum {
             .iter_mut()
             .chain(self.back_right_motors.borrow_mut().as_mut())
         {
-            let result = motor.set_voltage(right * motor.max_voltage());
+            let result = set_motor_power(motor, right, self.brake_mode);
 
             if result.is_err() {
                 rtn = result;
@@ -108,3 +439,14 @@ impl Tank for Mecanum {
         rtn
     }
 }
+
+/// The motors driving one wheel of a [`Mecanum`] drivetrain, measured as a single sensor.
+struct WheelMotors(Rc<RefCell<dyn AsMut<[Motor]>>>);
+
+impl RotarySensor for WheelMotors {
+    type Error = PortError;
+
+    fn position(&self) -> Result<Angle, Self::Error> {
+        self.0.borrow_mut().as_mut().position()
+    }
+}
@@ -3,13 +3,21 @@
 //! This module provides types for modeling a robot's motion capabilities through various drivetrain
 //! configurations.
 
+use core::cell::RefCell;
+
 use evian_math::{desaturate, Vec2};
+use vexide_devices::smart::{
+    PortError,
+    motor::{BrakeMode, Motor},
+};
 
 mod differential;
 mod mecanum;
+mod x_drive;
 
-pub use differential::Differential;
+pub use differential::{Differential, MotorReport, MotorStatus, VerifyReport};
 pub use mecanum::Mecanum;
+pub use x_drive::XDrive;
 
 /// A collection of motors driving a wheeled mobile robot.
 ///
@@ -23,6 +31,154 @@ pub trait DrivetrainModel {
     type Error;
 }
 
+/// Strategy for scaling down holonomic drivetrain outputs that exceed the limits of the motors.
+///
+/// When the translational and rotational components of a holonomic command add up to more than
+/// a motor can output, some part of the command must be reduced. This enum controls which
+/// component is prioritized.
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum DesaturationPolicy {
+    /// Scale translation and rotation down uniformly, preserving the direction of the
+    /// robot's motion but reducing its speed.
+    #[default]
+    Uniform,
+
+    /// Preserve as much of the rotational component as possible, scaling down translation
+    /// first. This is useful for maintaining accurate heading control during fast holonomic
+    /// motions.
+    PreserveRotation,
+
+    /// Preserve as much of the translational component as possible, scaling down rotation
+    /// first.
+    PreserveTranslation,
+}
+
+/// Combines the translational and rotational components of a holonomic drivetrain's wheel
+/// outputs, scaling them down according to a [`DesaturationPolicy`] so that none exceed a given
+/// maximum magnitude.
+///
+/// `translation` and `rotation` contain each wheel's output due to translation and rotation
+/// respectively. With [`DesaturationPolicy::Uniform`], this is equivalent to calling
+/// [`desaturate`] on their sum. Otherwise, the preserved component is only scaled down if it
+/// alone exceeds `max`, and the other component is scaled down as little as possible to make
+/// room for it.
+///
+/// # Examples
+///
+/// ```
+/// use evian_drivetrain::model::{DesaturationPolicy, desaturate_holonomic};
+///
+/// let translation = [1.0, 1.0];
+/// let rotation = [0.5, -0.5];
+///
+/// // Uniform scaling reduces turning power along with translation...
+/// assert_eq!(
+///     desaturate_holonomic(translation, rotation, 1.0, DesaturationPolicy::Uniform),
+///     [1.0, 1.0 / 3.0],
+/// );
+///
+/// // ...while preserving rotation keeps the full difference between the wheels.
+/// assert_eq!(
+///     desaturate_holonomic(translation, rotation, 1.0, DesaturationPolicy::PreserveRotation),
+///     [1.0, 0.0],
+/// );
+/// ```
+pub fn desaturate_holonomic<const N: usize>(
+    translation: [f64; N],
+    rotation: [f64; N],
+    max: f64,
+    policy: DesaturationPolicy,
+) -> [f64; N] {
+    // Largest scale factor in [0, 1] that can be applied to `scaled` without any wheel's
+    // output exceeding `max` when added to `fixed`.
+    let max_scale = |fixed: &[f64; N], scaled: &[f64; N]| {
+        fixed
+            .iter()
+            .zip(scaled)
+            .filter(|(_, scaled)| **scaled != 0.0)
+            .fold(1.0, |scale: f64, (fixed, scaled)| {
+                scale.min((max - fixed * scaled.signum()) / scaled.abs())
+            })
+            .max(0.0)
+    };
+
+    let (preserved, reduced) = match policy {
+        DesaturationPolicy::Uniform => {
+            return desaturate(core::array::from_fn(|i| translation[i] + rotation[i]), max);
+        }
+        DesaturationPolicy::PreserveRotation => (rotation, translation),
+        DesaturationPolicy::PreserveTranslation => (translation, rotation),
+    };
+
+    let preserved = desaturate(preserved, max);
+    let scale = max_scale(&preserved, &reduced);
+
+    core::array::from_fn(|i| preserved[i] + reduced[i] * scale)
+}
+
+/// Snaps an output to zero if its magnitude is below a deadband `threshold`.
+pub(crate) fn apply_deadband(output: f64, threshold: f64) -> f64 {
+    if output.abs() < threshold { 0.0 } else { output }
+}
+
+/// Computes each wheel's output due to translation and rotation for a four-wheel holonomic
+/// drivetrain, in FL/FR/BL/BR order.
+///
+/// `direction` is the direction that the front-left wheel pushes the robot when driven forwards,
+/// scaled so that its largest component is `1.0`. The back-right wheel pushes in the same
+/// direction, while the front-right and back-left wheels push in this direction mirrored across
+/// the robot's forward axis.
+pub(crate) fn holonomic_components(
+    vector: Vec2<f64>,
+    turn: f64,
+    direction: Vec2<f64>,
+) -> ([f64; 4], [f64; 4]) {
+    let diagonal = direction.y * vector.y + direction.x * vector.x;
+    let antidiagonal = direction.y * vector.y - direction.x * vector.x;
+
+    (
+        [diagonal, antidiagonal, antidiagonal, diagonal],
+        [turn, -turn, turn, -turn],
+    )
+}
+
+/// Sets the voltage of a motor from a normalized power, stopping it using `brake_mode` instead
+/// if one is set and the power is exactly zero.
+pub(crate) fn set_motor_power(
+    motor: &mut Motor,
+    power: f64,
+    brake_mode: Option<BrakeMode>,
+) -> Result<(), PortError> {
+    match brake_mode {
+        Some(mode) if power == 0.0 => motor.brake(mode),
+        _ => motor.set_voltage(power * motor.max_voltage()),
+    }
+}
+
+/// Sets the voltage of each wheel's motors on a four-wheel drivetrain from a power in
+/// FL/FR/BL/BR order.
+///
+/// Wheels with a power of exactly zero are stopped using `brake_mode` if one is set.
+pub(crate) fn set_wheel_powers(
+    motors: [&RefCell<dyn AsMut<[Motor]>>; 4],
+    powers: [f64; 4],
+    brake_mode: Option<BrakeMode>,
+) -> Result<(), PortError> {
+    let mut rtn = Ok(());
+
+    for (motors, power) in motors.into_iter().zip(powers) {
+        for motor in motors.borrow_mut().as_mut() {
+            let result = set_motor_power(motor, power, brake_mode);
+
+            if result.is_err() {
+                rtn = result;
+            }
+        }
+    }
+
+    rtn
+}
+
 /// A drivetrain model that supports holonomic inverse kinematics.
 ///
 /// Holonomic drivetrains (such as mecanum or swerve) can move freely in any direction without
@@ -36,6 +192,68 @@ pub trait Holonomic: DrivetrainModel {
 }
 
 /// A drivetrain model that supports "arcade drive" (forward/turn) inverse kinematics.
+///
+/// Every [`Tank`] drivetrain implements this trait by mixing `throttle` and `steer` into left
+/// and right powers of `throttle + steer` and `throttle - steer`.
+///
+/// # Desaturation
+///
+/// When throttle and steer add up to more than full power, the mixed powers must be scaled down,
+/// according to the drivetrain's [arcade desaturation
+/// policy](Tank::arcade_desaturation_policy). Throttle is treated as the translational component
+/// of the command and steer as its rotational component:
+///
+/// - [`DesaturationPolicy::Uniform`] (the default) scales both sides down by the same factor.
+///   This keeps the robot driving along the same arc, but reduces both its speed and how hard it
+///   turns.
+/// - [`DesaturationPolicy::PreserveTranslation`] keeps as much throttle as possible and sheds
+///   steer first. The robot keeps its speed, at the cost of turning less than commanded (and not
+///   at all at full throttle).
+/// - [`DesaturationPolicy::PreserveRotation`] keeps as much steer as possible and sheds throttle
+///   first. The robot keeps turning as hard as commanded, at the cost of slowing down, which
+///   helps heading controllers stay accurate during fast motions.
+///
+/// # Examples
+///
+/// ```
+/// use evian_drivetrain::model::{Arcade, DesaturationPolicy, DrivetrainModel, Tank};
+///
+/// struct Model {
+///     policy: DesaturationPolicy,
+///     powers: (f64, f64),
+/// }
+///
+/// impl DrivetrainModel for Model {
+///     type Error = ();
+/// }
+///
+/// impl Tank for Model {
+///     fn drive_tank(&mut self, left: f64, right: f64) -> Result<(), ()> {
+///         self.powers = (left, right);
+///         Ok(())
+///     }
+///
+///     fn arcade_desaturation_policy(&self) -> DesaturationPolicy {
+///         self.policy
+///     }
+/// }
+///
+/// let mut model = Model { policy: DesaturationPolicy::Uniform, powers: (0.0, 0.0) };
+///
+/// // Uniform scaling reduces throttle and steer together...
+/// model.drive_arcade(1.0, 1.0).unwrap();
+/// assert_eq!(model.powers, (1.0, 0.0));
+///
+/// // ...while preserving throttle drops steer entirely at full throttle...
+/// model.policy = DesaturationPolicy::PreserveTranslation;
+/// model.drive_arcade(1.0, 1.0).unwrap();
+/// assert_eq!(model.powers, (1.0, 1.0));
+///
+/// // ...and preserving steer turns in place.
+/// model.policy = DesaturationPolicy::PreserveRotation;
+/// model.drive_arcade(1.0, 1.0).unwrap();
+/// assert_eq!(model.powers, (1.0, -1.0));
+/// ```
 pub trait Arcade: DrivetrainModel {
     /// Drives the robot using arcade-style controls.
     fn drive_arcade(&mut self, throttle: f64, steer: f64) -> Result<(), Self::Error>;
@@ -45,11 +263,25 @@ pub trait Arcade: DrivetrainModel {
 pub trait Tank: DrivetrainModel {
     /// Drives the robot using left and right wheel powers. 
     fn drive_tank(&mut self, left: f64, right: f64) -> Result<(), Self::Error>;
+
+    /// Returns the [`DesaturationPolicy`] used to scale down [arcade](Arcade::drive_arcade)
+    /// commands that exceed full power.
+    ///
+    /// See the [`Arcade`] trait for how each policy behaves. The default implementation returns
+    /// [`DesaturationPolicy::Uniform`].
+    fn arcade_desaturation_policy(&self) -> DesaturationPolicy {
+        DesaturationPolicy::Uniform
+    }
 }
 
 impl<T: Tank> Arcade for T {
     fn drive_arcade(&mut self, throttle: f64, steer: f64) -> Result<(), Self::Error> {
-        let [left, right] = desaturate([throttle + steer, throttle - steer], 1.0);
+        let [left, right] = desaturate_holonomic(
+            [throttle, throttle],
+            [steer, -steer],
+            1.0,
+            self.arcade_desaturation_policy(),
+        );
 
         self.drive_tank(left, right)
     }
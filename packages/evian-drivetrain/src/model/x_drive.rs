@@ -0,0 +1,307 @@
+extern crate alloc;
+
+use core::cell::RefCell;
+use alloc::rc::Rc;
+
+use evian_math::{Angle, Vec2};
+use vexide_devices::smart::{
+    PortError,
+    motor::{BrakeMode, Motor},
+};
+
+use super::{
+    DesaturationPolicy, DrivetrainModel, Holonomic, Tank, apply_deadband, desaturate_holonomic,
+    holonomic_components, set_wheel_powers,
+};
+
+/// X-drive drivetrain model.
+///
+/// An X-drive is a holonomic drivetrain with four omni wheels, one at each corner of the robot,
+/// mounted at an angle so that the wheels form an "X" when viewed from above. Like
+/// [`Mecanum`](super::Mecanum) drivetrains, X-drives are primarily driven through the
+/// [`Holonomic`] trait, and also implement [`Tank`] (and therefore [`Arcade`]) by driving their
+/// left and right wheels as if the robot were a differential drivetrain.
+///
+/// # Wheel Placement
+///
+/// Each wheel is assumed to roll at the drivetrain's **wheel angle** from the robot's forward
+/// axis, with the front-left and back-right wheels rolling parallel to each other (pushing
+/// towards the front-right of the robot when driven forward), and the front-right and back-left
+/// wheels mirroring them (pushing towards the front-left). Each motor's [`Direction`] should be
+/// configured so that a positive voltage pushes its wheel in these directions.
+///
+/// Most X-drives mount their wheels at 45°, where the wheels push in the same directions as
+/// the rollers of a mecanum drivetrain, and the robot can drive at the same speed in every
+/// direction. On a rectangular chassis where each wheel is mounted perpendicular to the
+/// chassis diagonal, the wheel angle is instead `atan2(wheelbase, track_width)`, so the robot
+/// will drive faster in one direction than the other.
+///
+/// # Desaturation
+///
+/// When a translation and turn are commanded that add up to more than a wheel can output,
+/// [`Holonomic::drive_vector`] scales the wheel outputs down using the drivetrain's
+/// [`DesaturationPolicy`], in the same way as a mecanum drivetrain. See
+/// [`XDrive::wheel_powers`] for the kinematics used.
+///
+/// # Stopping
+///
+/// Motions stop the drivetrain by driving it at zero output once they finish. By default, this
+/// sets the motors to zero volts, which lets the robot coast. A
+/// [brake mode](XDrive::with_brake_mode) can be set to actively brake or hold position instead.
+///
+/// [`Arcade`]: super::Arcade
+/// [`Direction`]: vexide_devices::math::Direction
+pub struct XDrive {
+    /// Motors driving the front-left wheel(s).
+    pub front_left_motors: Rc<RefCell<dyn AsMut<[Motor]>>>,
+
+    /// Motors driving the front-right wheel(s).
+    pub front_right_motors: Rc<RefCell<dyn AsMut<[Motor]>>>,
+
+    /// Motors driving the back-left wheel(s).
+    pub back_left_motors: Rc<RefCell<dyn AsMut<[Motor]>>>,
+
+    /// Motors driving the back-right wheel(s).
+    pub back_right_motors: Rc<RefCell<dyn AsMut<[Motor]>>>,
+
+    wheel_diameter: f64,
+    wheel_angle: Angle,
+    desaturation_policy: DesaturationPolicy,
+    output_deadband: f64,
+    brake_mode: Option<BrakeMode>,
+}
+
+impl XDrive {
+    /// Creates a new drivetrain with the provided front-left, front-right, back-left, and
+    /// back-right motors, wheel diameter, and wheel angle.
+    ///
+    /// `wheel_diameter` is the diameter of the omni wheels in wheel units. See the [type-level
+    /// documentation](XDrive#wheel-placement) for how `wheel_angle` is measured.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let motors = XDrive::new(
+    ///     [Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward)],
+    ///     [Motor::new(peripherals.port_2, Gearset::Green, Direction::Reverse)],
+    ///     [Motor::new(peripherals.port_3, Gearset::Green, Direction::Forward)],
+    ///     [Motor::new(peripherals.port_4, Gearset::Green, Direction::Reverse)],
+    ///     3.25,
+    ///     45.0.deg(),
+    /// );
+    /// ```
+    pub fn new<
+        FL: AsMut<[Motor]> + 'static,
+        FR: AsMut<[Motor]> + 'static,
+        BL: AsMut<[Motor]> + 'static,
+        BR: AsMut<[Motor]> + 'static,
+    >(
+        front_left: FL,
+        front_right: FR,
+        back_left: BL,
+        back_right: BR,
+        wheel_diameter: f64,
+        wheel_angle: Angle,
+    ) -> Self {
+        Self {
+            front_left_motors: Rc::new(RefCell::new(front_left)),
+            front_right_motors: Rc::new(RefCell::new(front_right)),
+            back_left_motors: Rc::new(RefCell::new(back_left)),
+            back_right_motors: Rc::new(RefCell::new(back_right)),
+            wheel_diameter,
+            wheel_angle,
+            desaturation_policy: DesaturationPolicy::Uniform,
+            output_deadband: 0.0,
+            brake_mode: None,
+        }
+    }
+
+    /// Returns the diameter of the drivetrain's wheels in wheel units.
+    #[must_use]
+    pub const fn wheel_diameter(&self) -> f64 {
+        self.wheel_diameter
+    }
+
+    /// Returns the angle between each wheel's rolling direction and the robot's forward axis.
+    #[must_use]
+    pub const fn wheel_angle(&self) -> Angle {
+        self.wheel_angle
+    }
+
+    /// Sets the [`DesaturationPolicy`] used by [`Holonomic::drive_vector`] when the wheel
+    /// outputs exceed what the motors can output.
+    ///
+    /// By default, [`DesaturationPolicy::Uniform`] is used.
+    #[must_use]
+    pub const fn with_desaturation_policy(mut self, policy: DesaturationPolicy) -> Self {
+        self.desaturation_policy = policy;
+        self
+    }
+
+    /// Returns the [`DesaturationPolicy`] used by [`Holonomic::drive_vector`].
+    #[must_use]
+    pub const fn desaturation_policy(&self) -> DesaturationPolicy {
+        self.desaturation_policy
+    }
+
+    /// Changes the [`DesaturationPolicy`] used by [`Holonomic::drive_vector`].
+    pub const fn set_desaturation_policy(&mut self, policy: DesaturationPolicy) {
+        self.desaturation_policy = policy;
+    }
+
+    /// Sets the brake mode used by every motor on the drivetrain when commanded to stop.
+    ///
+    /// When a brake mode is set, any wheel that is driven at exactly zero output (including
+    /// outputs snapped to zero by the [output deadband](XDrive::with_output_deadband)) will be
+    /// stopped using that [`BrakeMode`]. By default, no brake mode is used, so stopped wheels
+    /// are set to zero volts and the robot coasts.
+    #[must_use]
+    pub const fn with_brake_mode(mut self, mode: BrakeMode) -> Self {
+        self.brake_mode = Some(mode);
+        self
+    }
+
+    /// Returns the brake mode used when the drivetrain is commanded to stop, if any.
+    #[must_use]
+    pub const fn brake_mode(&self) -> Option<BrakeMode> {
+        self.brake_mode
+    }
+
+    /// Sets the brake mode used when the drivetrain is commanded to stop.
+    ///
+    /// See [`XDrive::with_brake_mode`] for more information.
+    pub const fn set_brake_mode(&mut self, mode: Option<BrakeMode>) {
+        self.brake_mode = mode;
+    }
+
+    /// Sets a deadband below which the output of each wheel is snapped to zero.
+    ///
+    /// The deadband is applied to the final output of each wheel in [`Holonomic::drive_vector`]
+    /// and [`Tank::drive_tank`], after desaturation. By default, the deadband is `0.0`
+    /// (disabled).
+    #[must_use]
+    pub const fn with_output_deadband(mut self, threshold: f64) -> Self {
+        self.output_deadband = threshold;
+        self
+    }
+
+    /// Returns the output deadband of the drivetrain.
+    #[must_use]
+    pub const fn output_deadband(&self) -> f64 {
+        self.output_deadband
+    }
+
+    /// Sets the output deadband of the drivetrain.
+    ///
+    /// See [`XDrive::with_output_deadband`] for more information.
+    pub const fn set_output_deadband(&mut self, threshold: f64) {
+        self.output_deadband = threshold;
+    }
+
+    /// Computes the power of each wheel (in FL/FR/BL/BR order) needed to drive an X-drive with
+    /// the given wheel angle using a translation vector and rotational power.
+    ///
+    /// Each wheel's translational output is the component of `vector` along the direction that
+    /// wheel pushes the robot, scaled so that driving at full power along the robot's faster
+    /// axis drives the wheels at full power. `turn` is added to the left wheels and subtracted
+    /// from the right wheels, so positive values turn the robot clockwise. The outputs are then
+    /// desaturated using `policy`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evian_drivetrain::model::{DesaturationPolicy, XDrive};
+    /// use evian_math::{IntoAngle, Vec2};
+    ///
+    /// let powers = |angle: f64, vector: Vec2<f64>, turn: f64| {
+    ///     XDrive::wheel_powers(angle.deg(), vector, turn, DesaturationPolicy::Uniform)
+    /// };
+    /// let approx_eq = |a: [f64; 4], b: [f64; 4]| {
+    ///     a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-9)
+    /// };
+    ///
+    /// // Driving forwards spins every wheel forwards...
+    /// assert!(approx_eq(powers(45.0, Vec2::new(0.0, 1.0), 0.0), [1.0, 1.0, 1.0, 1.0]));
+    ///
+    /// // ...strafing right spins the diagonals against each other...
+    /// assert!(approx_eq(powers(45.0, Vec2::new(1.0, 0.0), 0.0), [1.0, -1.0, -1.0, 1.0]));
+    ///
+    /// // ...and turning spins each side against the other.
+    /// assert!(approx_eq(powers(45.0, Vec2::new(0.0, 0.0), 1.0), [1.0, -1.0, 1.0, -1.0]));
+    ///
+    /// // With wheels mounted closer to the forward axis, strafing is slower than driving forwards.
+    /// let strafe = 30.0_f64.to_radians().tan();
+    /// assert!(approx_eq(
+    ///     powers(30.0, Vec2::new(1.0, 0.0), 0.0),
+    ///     [strafe, -strafe, -strafe, strafe],
+    /// ));
+    /// ```
+    #[must_use]
+    pub fn wheel_powers(
+        wheel_angle: Angle,
+        vector: Vec2<f64>,
+        turn: f64,
+        policy: DesaturationPolicy,
+    ) -> [f64; 4] {
+        let (sin, cos) = wheel_angle.sin_cos();
+        let scale = sin.abs().max(cos.abs());
+
+        let (translation, rotation) =
+            holonomic_components(vector, turn, Vec2::new(sin / scale, cos / scale));
+
+        desaturate_holonomic(translation, rotation, 1.0, policy)
+    }
+
+    /// Drives the robot using a translation vector and rotational power, desaturating the
+    /// wheel outputs using a given [`DesaturationPolicy`].
+    ///
+    /// [`Holonomic::drive_vector`] is equivalent to calling this method with the drivetrain's
+    /// [configured policy](XDrive::desaturation_policy).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if setting the voltage of any motor fails.
+    pub fn drive_vector_with_policy(
+        &mut self,
+        vector: Vec2<f64>,
+        turn: f64,
+        policy: DesaturationPolicy,
+    ) -> Result<(), PortError> {
+        let powers = Self::wheel_powers(self.wheel_angle, vector, turn, policy);
+
+        self.set_wheel_powers(powers.map(|power| apply_deadband(power, self.output_deadband)))
+    }
+
+    /// Sets the voltage of each wheel's motors from a power in FL/FR/BL/BR order.
+    fn set_wheel_powers(&mut self, powers: [f64; 4]) -> Result<(), PortError> {
+        set_wheel_powers(
+            [
+                &*self.front_left_motors,
+                &*self.front_right_motors,
+                &*self.back_left_motors,
+                &*self.back_right_motors,
+            ],
+            powers,
+            self.brake_mode,
+        )
+    }
+}
+
+impl DrivetrainModel for XDrive {
+    type Error = PortError;
+}
+
+impl Holonomic for XDrive {
+    fn drive_vector(&mut self, vector: Vec2<f64>, turn: f64) -> Result<(), Self::Error> {
+        self.drive_vector_with_policy(vector, turn, self.desaturation_policy)
+    }
+}
+
+impl Tank for XDrive {
+    fn drive_tank(&mut self, left: f64, right: f64) -> Result<(), Self::Error> {
+        let left = apply_deadband(left, self.output_deadband);
+        let right = apply_deadband(right, self.output_deadband);
+
+        self.set_wheel_powers([left, right, left, right])
+    }
+}
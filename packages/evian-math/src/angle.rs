@@ -1,37 +1,251 @@
+use core::fmt;
+
 pub use vexide_devices::math::Angle;
 
 // MARK: Extension Trait
 
 /// Extension trait for easily creating [`Angle`]s from floating-point
 /// number literals.
+///
+/// # Non-Finite Values
+///
+/// An [`Angle`] created from `NaN` or an infinite value will silently poison any math it is
+/// used in, so the infallible constructors ([`deg`](IntoAngle::deg), [`rad`](IntoAngle::rad),
+/// etc.) panic when given a non-finite value in debug builds. In release builds, this check is
+/// skipped and the value is passed through unchanged.
+///
+/// When the value may legitimately be non-finite (such as a value read from a sensor), the
+/// fallible constructors ([`try_deg`](IntoAngle::try_deg), [`try_rad`](IntoAngle::try_rad),
+/// etc.) can be used instead, which return `None` for non-finite values in all builds.
+///
+/// # Examples
+///
+/// ```
+/// use evian::math::IntoAngle;
+///
+/// assert_eq!(90.0.try_deg(), Some(90.0.deg()));
+/// assert_eq!(f64::NAN.try_deg(), None);
+/// assert_eq!(f64::INFINITY.try_rad(), None);
+/// ```
 pub trait IntoAngle {
     /// Creates an [`Angle`] of `self` degrees.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if `self` is not finite.
     fn deg(self) -> Angle;
 
     /// Creates an [`Angle`] of `self` gradians.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if `self` is not finite.
     fn grad(self) -> Angle;
 
     /// Creates an [`Angle`] of `self` radians.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if `self` is not finite.
     fn rad(self) -> Angle;
 
     /// Creates an [`Angle`] of `self` turns (revolutions).
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if `self` is not finite.
     fn turns(self) -> Angle;
+
+    /// Creates an [`Angle`] of `self` degrees, returning `None` if `self` is not finite.
+    fn try_deg(self) -> Option<Angle>;
+
+    /// Creates an [`Angle`] of `self` gradians, returning `None` if `self` is not finite.
+    fn try_grad(self) -> Option<Angle>;
+
+    /// Creates an [`Angle`] of `self` radians, returning `None` if `self` is not finite.
+    fn try_rad(self) -> Option<Angle>;
+
+    /// Creates an [`Angle`] of `self` turns (revolutions), returning `None` if `self` is not
+    /// finite.
+    fn try_turns(self) -> Option<Angle>;
 }
 
 impl IntoAngle for f64 {
     fn deg(self) -> Angle {
+        debug_assert!(self.is_finite(), "attempted to create an angle of {self} degrees");
         Angle::from_degrees(self)
     }
 
     fn rad(self) -> Angle {
+        debug_assert!(self.is_finite(), "attempted to create an angle of {self} radians");
         Angle::from_radians(self)
     }
 
     fn grad(self) -> Angle {
+        debug_assert!(self.is_finite(), "attempted to create an angle of {self} gradians");
         Angle::from_gradians(self)
     }
 
     fn turns(self) -> Angle {
+        debug_assert!(self.is_finite(), "attempted to create an angle of {self} turns");
         Angle::from_turns(self)
     }
+
+    fn try_deg(self) -> Option<Angle> {
+        self.is_finite().then(|| Angle::from_degrees(self))
+    }
+
+    fn try_grad(self) -> Option<Angle> {
+        self.is_finite().then(|| Angle::from_gradians(self))
+    }
+
+    fn try_rad(self) -> Option<Angle> {
+        self.is_finite().then(|| Angle::from_radians(self))
+    }
+
+    fn try_turns(self) -> Option<Angle> {
+        self.is_finite().then(|| Angle::from_turns(self))
+    }
+}
+
+// MARK: Mirroring
+
+/// Extension trait for reflecting headings when mirroring geometry across an axis.
+///
+/// These pair with [`Vec2::mirror_x`](crate::Vec2::mirror_x) and
+/// [`Vec2::mirror_y`](crate::Vec2::mirror_y) to mirror a full pose of the robot.
+///
+/// # Examples
+///
+/// ```
+/// use evian::math::{IntoAngle, MirrorAngle, Vec2};
+///
+/// let (position, heading) = (Vec2::new(24.0, 12.0), 30.0.deg());
+///
+/// // Mirror a pose to the other side of the field.
+/// let mirrored = (position.mirror_x(0.0), heading.mirror_x());
+///
+/// assert_eq!(mirrored.0, Vec2::new(-24.0, 12.0));
+/// assert!((mirrored.1.as_degrees() - 150.0).abs() < 1e-9);
+/// ```
+pub trait MirrorAngle {
+    /// Reflects a heading across a vertical line, such that a robot facing in the positive x
+    /// direction will face in the negative x direction.
+    ///
+    /// The resulting angle is wrapped to `[0, 2π)`.
+    fn mirror_x(self) -> Angle;
+
+    /// Reflects a heading across a horizontal line, such that a robot facing in the positive y
+    /// direction will face in the negative y direction.
+    ///
+    /// The resulting angle is wrapped to `[0, 2π)`.
+    fn mirror_y(self) -> Angle;
+}
+
+impl MirrorAngle for Angle {
+    fn mirror_x(self) -> Angle {
+        (Angle::HALF_TURN - self).wrapped_full()
+    }
+
+    fn mirror_y(self) -> Angle {
+        (-self).wrapped_full()
+    }
+}
+
+// MARK: Comparison
+
+/// Extension trait for comparing [`Angle`]s within a tolerance.
+pub trait ApproxEqAngle {
+    /// Returns `true` if `self` and `other` are within `tolerance` of each other.
+    ///
+    /// The difference between the two angles is wrapped to `[-π, π]` before being compared, so
+    /// angles on either side of a full turn (such as 359° and 1°) are considered close together.
+    /// The sign of `tolerance` is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evian::math::{ApproxEqAngle, IntoAngle};
+    ///
+    /// assert!(10.0.deg().approx_eq(11.0.deg(), 2.0.deg()));
+    /// assert!(!10.0.deg().approx_eq(13.0.deg(), 2.0.deg()));
+    ///
+    /// // Comparisons account for wraparound.
+    /// assert!(359.0.deg().approx_eq(1.0.deg(), 3.0.deg()));
+    /// assert!(1.0.deg().approx_eq(359.0.deg(), 3.0.deg()));
+    /// assert!(!359.0.deg().approx_eq(3.0.deg(), 3.0.deg()));
+    /// assert!((-180.0).deg().approx_eq(180.0.deg(), 0.001.deg()));
+    /// assert!(720.0.deg().approx_eq(0.0.deg(), 0.001.deg()));
+    /// ```
+    fn approx_eq(self, other: Angle, tolerance: Angle) -> bool;
+}
+
+impl ApproxEqAngle for Angle {
+    fn approx_eq(self, other: Angle, tolerance: Angle) -> bool {
+        (self - other).wrapped_half().as_radians().abs() <= tolerance.as_radians().abs()
+    }
+}
+
+/// Returns the shortest signed rotation from `from` to `to`, wrapped to `[-π, π]`.
+///
+/// Positive rotations are counterclockwise, so this gives the change in heading between two
+/// readings, or the shortest turn needed to face a target heading.
+///
+/// This is *not* the same as `(to - from).wrapped_half()`. [`Angle::wrapped_half`] negates the
+/// angle it wraps, so that expression gives the shortest rotation from `to` to `from` instead.
+/// Prefer this function wherever a signed difference between two angles is needed.
+///
+/// # Examples
+///
+/// ```
+/// use evian_math::{IntoAngle, signed_delta};
+///
+/// let delta = signed_delta(350.0.deg(), 10.0.deg());
+/// assert!((delta.as_degrees() - 20.0).abs() < 1e-9);
+///
+/// let delta = signed_delta(10.0.deg(), 350.0.deg());
+/// assert!((delta.as_degrees() + 20.0).abs() < 1e-9);
+/// ```
+#[must_use]
+pub fn signed_delta(from: Angle, to: Angle) -> Angle {
+    -(to - from).wrapped_half()
+}
+
+// MARK: Display
+
+/// Extension trait for displaying [`Angle`]s in human-readable units.
+pub trait DisplayAngle {
+    /// Returns a wrapper that implements [`Display`](fmt::Display) by printing the angle in
+    /// degrees with a `°` suffix.
+    ///
+    /// Formatter options such as precision are applied to the number of degrees.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evian::math::{DisplayAngle, IntoAngle};
+    ///
+    /// assert_eq!(format!("{}", 90.0.deg().display_degrees()), "90°");
+    /// assert_eq!(format!("{:.1}", 90.0.deg().display_degrees()), "90.0°");
+    /// ```
+    fn display_degrees(&self) -> DisplayDegrees;
+}
+
+impl DisplayAngle for Angle {
+    fn display_degrees(&self) -> DisplayDegrees {
+        DisplayDegrees(*self)
+    }
+}
+
+/// Helper struct for displaying an [`Angle`] in degrees.
+///
+/// This is created by [`DisplayAngle::display_degrees`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DisplayDegrees(Angle);
+
+impl fmt::Display for DisplayDegrees {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0.as_degrees(), f)?;
+        f.write_str("°")
+    }
 }
@@ -6,6 +6,13 @@ use crate::Vec2;
 
 pub use bezier::CubicBezier;
 
+/// Number of straight segments used to estimate a curve's arc length.
+const ARC_LENGTH_STEPS: usize = 1000;
+
+/// Fraction of a curve's domain spanned by the chord used to find its direction where both of
+/// its derivatives are zero.
+const TANGENT_CHORD_FRACTION: f64 = 1e-3;
+
 /// Trait describing a parametric curve.
 pub trait Curve {
     /// The maximum parameter value in the curve function's domain.
@@ -19,4 +26,172 @@ pub trait Curve {
 
     /// Samples the curve's second derivative at a given parameter.
     fn second_derivative(&self, t: f64) -> Vec2<f64>;
+
+    /// Samples a curve running parallel to this one at a fixed perpendicular distance.
+    ///
+    /// `samples` points are taken at evenly spaced parameters across `[0, MAX_T]`, and each
+    /// point is moved `distance` units along the curve's normal at that parameter. Positive
+    /// `distance` values offset the curve to the left of its direction of travel, while negative
+    /// values offset it to the right. The resulting points are written to the first `samples`
+    /// elements of `out`.
+    ///
+    /// Note that offsetting is performed pointwise, so tight concave sections of the curve (where
+    /// the radius of curvature is smaller than `distance`) will produce an offset curve that
+    /// loops back over itself.
+    ///
+    /// The curve's direction is undefined wherever its derivative is zero, such as at the end of a
+    /// [`CubicBezier`] whose control point coincides with its anchor. At these parameters, the
+    /// direction is taken from the second derivative instead (which the curve's direction
+    /// approaches near the stationary point), or from a short chord along the curve if that is
+    /// zero too. A curve that never leaves a single point has no direction at all, so its points
+    /// are returned without an offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out` is shorter than `samples`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evian_math::{Vec2, curve::{CubicBezier, Curve}};
+    ///
+    /// let line = CubicBezier::new((0.0, 0.0), (1.0, 0.0), (2.0, 0.0), (3.0, 0.0));
+    /// let mut out = [Vec2::default(); 4];
+    /// line.offset(2.0, 4, &mut out);
+    ///
+    /// assert_eq!(out[0], Vec2::new(0.0, 2.0));
+    /// assert_eq!(out[3], Vec2::new(3.0, 2.0));
+    ///
+    /// // The derivative of this curve is zero at both ends.
+    /// let line = CubicBezier::new((0.0, 0.0), (0.0, 0.0), (3.0, 0.0), (3.0, 0.0));
+    /// line.offset(2.0, 4, &mut out);
+    ///
+    /// assert_eq!(out[0], Vec2::new(0.0, 2.0));
+    /// assert_eq!(out[3], Vec2::new(3.0, 2.0));
+    ///
+    /// // Both derivatives of this curve are zero at its start, even with a single sample.
+    /// let line = CubicBezier::new((0.0, 0.0), (0.0, 0.0), (0.0, 0.0), (3.0, 0.0));
+    /// line.offset(2.0, 1, &mut out);
+    /// assert!((out[0] - Vec2::new(0.0, 2.0)).length() < 1e-9);
+    ///
+    /// // A curve that is a single point can't be offset.
+    /// let point = CubicBezier::new((1.0, 1.0), (1.0, 1.0), (1.0, 1.0), (1.0, 1.0));
+    /// point.offset(2.0, 1, &mut out);
+    /// assert_eq!(out[0], Vec2::new(1.0, 1.0));
+    /// ```
+    fn offset(&self, distance: f64, samples: usize, out: &mut [Vec2<f64>]) {
+        let step = if samples > 1 {
+            Self::MAX_T / (samples - 1) as f64
+        } else {
+            0.0
+        };
+
+        for (i, point) in out[..samples].iter_mut().enumerate() {
+            let t = step * i as f64;
+            let mut tangent = self.derivative(t);
+
+            if tangent.length_squared() == 0.0 {
+                // Near a stationary point, the derivative points along the second derivative
+                // after it and against it before it, so the end of the curve needs flipping.
+                tangent = if t < Self::MAX_T {
+                    self.second_derivative(t)
+                } else {
+                    -self.second_derivative(t)
+                };
+            }
+
+            if tangent.length_squared() == 0.0 {
+                // Step a small, fixed distance towards the interior of the curve rather than to
+                // the next sample, which may not exist when only one sample is taken.
+                let dt = Self::MAX_T * TANGENT_CHORD_FRACTION;
+                tangent = if t < Self::MAX_T {
+                    self.point(t + dt) - self.point(t)
+                } else {
+                    self.point(t) - self.point(t - dt)
+                };
+            }
+
+            // A curve that never leaves a single point has no direction to offset in.
+            if tangent.length_squared() == 0.0 {
+                *point = self.point(t);
+                continue;
+            }
+
+            let tangent = tangent.unit();
+
+            // Rotating the unit tangent 90 degrees counterclockwise gives the left-hand normal.
+            *point = self.point(t) + Vec2::new(-tangent.y, tangent.x) * distance;
+        }
+    }
+
+    /// Samples points spaced evenly along the curve's arc length, returning how many points were
+    /// written to `out`.
+    ///
+    /// Sampling at evenly spaced parameters (as [`Curve::offset`] does) bunches points together
+    /// wherever the curve moves slowly with respect to `t`, such as in tight turns. This instead
+    /// writes the curve's start point followed by every point a multiple of `spacing` along the
+    /// curve from it. The curve's end point is only included if its arc length happens to be a
+    /// multiple of `spacing`.
+    ///
+    /// The arc length is estimated by summing the lengths of straight chords between many evenly
+    /// spaced parameters, and points are placed along those chords. If `out` is too small to fit
+    /// every point, it is filled with the points closest to the start of the curve.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `spacing` is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evian_math::{Vec2, curve::{CubicBezier, Curve}};
+    ///
+    /// // A straight curve that starts off slowly, so its parameter doesn't match its arc length.
+    /// let line = CubicBezier::new((0.0, 0.0), (0.0, 0.0), (0.0, 0.0), (3.5, 0.0));
+    /// assert!(line.point(0.5).x < 0.5);
+    ///
+    /// let mut out = [Vec2::default(); 8];
+    /// assert_eq!(line.resample_by_arc_length(1.0, &mut out), 4);
+    ///
+    /// for (i, point) in out[..4].iter().enumerate() {
+    ///     assert!((point.x - i as f64).abs() < 1e-9);
+    /// }
+    ///
+    /// // Only the points that fit are written.
+    /// let mut out = [Vec2::default(); 2];
+    /// assert_eq!(line.resample_by_arc_length(1.0, &mut out), 2);
+    /// ```
+    fn resample_by_arc_length(&self, spacing: f64, out: &mut [Vec2<f64>]) -> usize {
+        assert!(spacing > 0.0, "arc length spacing must be positive");
+
+        let Some(first) = out.first_mut() else {
+            return 0;
+        };
+
+        let mut prev = self.point(0.0);
+        *first = prev;
+
+        let mut written = 1;
+        let mut traveled = 0.0;
+
+        for i in 1..=ARC_LENGTH_STEPS {
+            let point = self.point(Self::MAX_T * i as f64 / ARC_LENGTH_STEPS as f64);
+            let chord = prev.distance(point);
+
+            // Place every point whose arc length falls within this chord.
+            while written < out.len() && traveled + chord >= spacing * written as f64 {
+                out[written] = prev.lerp(point, (spacing * written as f64 - traveled) / chord);
+                written += 1;
+            }
+
+            if written == out.len() {
+                break;
+            }
+
+            traveled += chord;
+            prev = point;
+        }
+
+        written
+    }
 }
@@ -2,12 +2,16 @@
 
 #![no_std]
 
+use num_traits::real::Real;
+
 mod angle;
 mod vec2;
 
 pub mod curve;
 
-pub use angle::{Angle, IntoAngle};
+pub use angle::{
+    Angle, ApproxEqAngle, DisplayAngle, DisplayDegrees, IntoAngle, MirrorAngle, signed_delta,
+};
 pub use vec2::Vec2;
 
 /// Scales down the values in an array so that none exceed a given maximum magnitude.
@@ -16,7 +20,14 @@ pub use vec2::Vec2;
 /// If that magnitude is greater than `max`, all elements are uniformly scaled down so
 /// that the largest magnitude equals `max`. If all elements are already within the limit,
 /// the array is unchanged.
+///
+/// A `max` of zero scales every element to zero, and an empty array is returned unchanged.
 /// 
+/// # Panics
+///
+/// Panics in debug builds if `max` is negative. In release builds, a negative `max` is treated
+/// as its absolute value.
+///
 /// # Examples
 /// 
 /// ```
@@ -30,13 +41,204 @@ pub use vec2::Vec2;
 /// let values = [0.5, -1.2, 0.8];
 /// let result = desaturate(values, 2.0);
 /// assert_eq!(result, values);
+///
+/// // A maximum of zero leaves nothing to scale to:
+/// assert_eq!(desaturate([3.0, -4.0, 0.0], 0.0), [0.0, 0.0, 0.0]);
+///
+/// // An empty array has nothing to scale:
+/// assert_eq!(desaturate([], 2.0), []);
 /// ```
 pub fn desaturate<const N: usize>(values: [f64; N], max: f64) -> [f64; N] {
+    desaturate_with_factor(values, max).0
+}
+
+/// Scales down the values in an array so that none exceed a given maximum magnitude, also
+/// returning the factor that the values were scaled by.
+///
+/// This behaves identically to [`desaturate`], but additionally returns the scale factor that
+/// was applied to every element (`1.0` if the array was unchanged). This is useful for keeping
+/// signals associated with the desaturated values consistent with them, such as scaling a
+/// feedforward velocity by the same amount as the output it was computed for.
+///
+/// # Panics
+///
+/// Panics in debug builds if `max` is negative. See [`desaturate`] for more information.
+///
+/// # Examples
+///
+/// ```
+/// use evian::math::desaturate_with_factor;
+///
+/// let (result, factor) = desaturate_with_factor([3.0, -4.0, 1.0], 2.0);
+/// assert_eq!(result, [1.5, -2.0, 0.5]);
+/// assert_eq!(factor, 0.5);
+///
+/// // Already within bounds, so unchanged:
+/// let (result, factor) = desaturate_with_factor([0.5, -1.2, 0.8], 2.0);
+/// assert_eq!(result, [0.5, -1.2, 0.8]);
+/// assert_eq!(factor, 1.0);
+///
+/// // Scaling to a maximum of zero:
+/// let (result, factor) = desaturate_with_factor([3.0, -4.0, 1.0], 0.0);
+/// assert_eq!(result, [0.0, 0.0, 0.0]);
+/// assert_eq!(factor, 0.0);
+/// ```
+pub fn desaturate_with_factor<const N: usize>(values: [f64; N], max: f64) -> ([f64; N], f64) {
+    debug_assert!(max >= 0.0, "desaturation maximum must not be negative (got {max})");
+
+    // A negative maximum would flip the sign of every element, which is never what's wanted.
+    let max = max.abs();
     let largest_magnitude = values.iter().map(|v| v.abs()).fold(0.0, f64::max);
 
     if largest_magnitude > max {
-        values.map(|v| v * max / largest_magnitude)
+        let factor = max / largest_magnitude;
+        (values.map(|v| v * factor), factor)
     } else {
-        values
+        (values, 1.0)
+    }
+}
+
+/// Restricts a value to the range `[min, max]`.
+///
+/// Unlike [`f64::clamp`], this function doesn't panic if `min` is greater than `max`, in which
+/// case `min` is returned. A `NaN` value is returned unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use evian_math::clamp;
+///
+/// assert_eq!(clamp(1.5, -1.0, 1.0), 1.0);
+/// assert_eq!(clamp(-3.0, -1.0, 1.0), -1.0);
+/// assert_eq!(clamp(0.25, -1.0, 1.0), 0.25);
+///
+/// // An empty range always returns its minimum.
+/// assert_eq!(clamp(5.0, 2.0, 1.0), 2.0);
+/// assert_eq!(clamp(-5.0, 2.0, 1.0), 2.0);
+/// ```
+pub fn clamp<T: Real>(value: T, min: T, max: T) -> T {
+    if min > max {
+        return min;
+    }
+
+    if value < min {
+        min
+    } else if value > max {
+        max
+    } else {
+        value
+    }
+}
+
+/// Linearly interpolates between `a` and `b`.
+///
+/// A `t` of `0.0` returns `a`, and a `t` of `1.0` returns `b`. Values of `t` outside of `[0, 1]`
+/// extrapolate past `a` or `b`.
+///
+/// # Examples
+///
+/// ```
+/// use evian::math::lerp;
+///
+/// assert_eq!(lerp(10.0, 20.0, 0.25), 12.5);
+/// assert_eq!(lerp(10.0, 20.0, 1.5), 25.0);
+/// ```
+pub fn lerp<T: Real>(a: T, b: T, t: T) -> T {
+    a + (b - a) * t
+}
+
+/// Maps a value from the range `[in_min, in_max]` to the range `[out_min, out_max]`.
+///
+/// Values outside of the input range are extrapolated rather than clamped, so the result should
+/// be passed through [`clamp`] if it must stay within the output range. Either range may be
+/// reversed (such as to invert a value). If `in_min` and `in_max` are equal, the result is
+/// non-finite.
+///
+/// # Examples
+///
+/// ```
+/// use evian::math::remap;
+///
+/// // Convert a joystick value to a motor voltage.
+/// assert_eq!(remap(0.5, -1.0, 1.0, -12.0, 12.0), 6.0);
+///
+/// // Reversed output range.
+/// assert_eq!(remap(0.25, 0.0, 1.0, 1.0, 0.0), 0.75);
+/// ```
+pub fn remap<T: Real>(value: T, in_min: T, in_max: T, out_min: T, out_max: T) -> T {
+    lerp(out_min, out_max, (value - in_min) / (in_max - in_min))
+}
+
+/// Raises the magnitude of a value to a power, keeping the value's sign.
+///
+/// This computes `input.signum() * input.abs().powf(exponent)`, which is useful for shaping
+/// joystick inputs, where a stick pushed backwards should produce a negative output. Raising
+/// the input directly to the power would instead lose its sign for even exponents, and return
+/// `NaN` for negative inputs with fractional exponents.
+///
+/// # Examples
+///
+/// ```
+/// use evian::math::signed_pow;
+///
+/// assert_eq!(signed_pow(0.5, 2.0), 0.25);
+/// assert_eq!(signed_pow(-0.5, 2.0), -0.25);
+/// assert_eq!(signed_pow(-0.5, 3.0), -0.125);
+///
+/// // Fractional exponents work for negative inputs too.
+/// assert_eq!(signed_pow(-0.25, 0.5), -0.5);
+/// assert_eq!(signed_pow(0.0, 1.5), 0.0);
+/// ```
+pub fn signed_pow<T: Real>(input: T, exponent: T) -> T {
+    input.signum() * input.abs().powf(exponent)
+}
+
+/// Mirrors every point in a path across the vertical line `x = axis`, writing the mirrored
+/// points into `out`.
+///
+/// See [`Vec2::mirror_x`] for more information.
+///
+/// # Panics
+///
+/// Panics if `path` and `out` have different lengths.
+///
+/// # Examples
+///
+/// ```
+/// use evian::math::{Vec2, mirror_path_x};
+///
+/// let path = [Vec2::new(0.0, 0.0), Vec2::new(24.0, 24.0)];
+/// let mut mirrored = [Vec2::default(); 2];
+///
+/// mirror_path_x(&path, 0.0, &mut mirrored);
+/// assert_eq!(mirrored, [Vec2::new(0.0, 0.0), Vec2::new(-24.0, 24.0)]);
+/// ```
+pub fn mirror_path_x<T>(path: &[Vec2<T>], axis: T, out: &mut [Vec2<T>])
+where
+    T: Copy + core::ops::Add<Output = T> + core::ops::Sub<Output = T>,
+{
+    assert_eq!(path.len(), out.len(), "Mirrored path buffer must match the path's length.");
+
+    for (point, mirrored) in path.iter().zip(out) {
+        *mirrored = point.mirror_x(axis);
+    }
+}
+
+/// Mirrors every point in a path across the horizontal line `y = axis`, writing the mirrored
+/// points into `out`.
+///
+/// See [`Vec2::mirror_y`] for more information.
+///
+/// # Panics
+///
+/// Panics if `path` and `out` have different lengths.
+pub fn mirror_path_y<T>(path: &[Vec2<T>], axis: T, out: &mut [Vec2<T>])
+where
+    T: Copy + core::ops::Add<Output = T> + core::ops::Sub<Output = T>,
+{
+    assert_eq!(path.len(), out.len(), "Mirrored path buffer must match the path's length.");
+
+    for (point, mirrored) in path.iter().zip(out) {
+        *mirrored = point.mirror_y(axis);
     }
 }
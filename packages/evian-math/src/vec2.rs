@@ -5,9 +5,38 @@ use core::{
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
+use crate::Angle;
+
 /// A vector in 2D cartesian space.
 ///
 /// The `Vec2` struct represents a two-dimensional vector with x and y components.
+///
+/// # Scalar Types
+///
+/// `Vec2` is generic over the type of its components, and the operations available depend on
+/// what that type supports:
+///
+/// - Construction, component access, and conversions are available for any `T`.
+/// - Arithmetic operators, [`lerp`](Vec2::lerp), [`dot`](Vec2::dot), [`cross`](Vec2::cross),
+///   and [`distance_squared`](Vec2::distance_squared) are available for any `T` implementing the
+///   relevant operator traits, including integers.
+/// - Operations requiring floating-point math, such as [`length`](Vec2::length),
+///   [`unit`](Vec2::unit), [`rotated`](Vec2::rotated), and [`from_polar`](Vec2::from_polar),
+///   require `T` to implement [`Real`], meaning they work with both `f64` and `f32`.
+/// - Conversions to and from an [`Angle`], such as [`angle`](Vec2::angle) and
+///   [`from_angle`](Vec2::from_angle), are only available for `Vec2<f64>`.
+///
+/// Most of evian uses `Vec2<f64>`, but `Vec2<f32>` may be used for memory-constrained code or
+/// sensor data without needing to convert:
+///
+/// ```
+/// use evian::math::Vec2;
+///
+/// let v = Vec2::<f32>::new(3.0, 4.0);
+///
+/// assert_eq!(v.length(), 5.0);
+/// assert_eq!(v.dot(Vec2::new(1.0, 0.0)), 3.0);
+/// ```
 #[derive(Default, Debug, Clone, Copy, Eq, PartialEq)]
 pub struct Vec2<T> {
     /// The cartesian x coordinate.
@@ -60,14 +89,83 @@ impl<T: Real + Copy + Mul<Output = T>> Vec2<T> {
     }
 }
 
-// MARK: Math
+impl Vec2<f64> {
+    /// Construct a unit vector pointing in the direction of an angle.
+    ///
+    /// The angle is measured counterclockwise from the positive x-axis, so this is equivalent
+    /// to calling [`Vec2::from_polar`] with a length of `1.0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evian::math::{IntoAngle, Vec2};
+    ///
+    /// let v = Vec2::from_angle(90.0.deg());
+    ///
+    /// assert!(v.x.abs() < 1e-12);
+    /// assert!((v.y - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn from_angle(angle: Angle) -> Self {
+        let (sin, cos) = angle.sin_cos();
 
-impl<T: Real + Copy> Vec2<T> {
-    /// Returns this vector's angle in radians relative to the origin (0, 0).
-    pub fn angle(&self) -> T {
-        self.y.atan2(self.x)
+        Self { x: cos, y: sin }
+    }
+
+    /// Returns the angle of this vector relative to the origin (0, 0).
+    ///
+    /// The angle is measured counterclockwise from the positive x-axis (computed as
+    /// `atan2(y, x)`), matching the convention used for headings throughout evian. The returned
+    /// angle is within the range `[-π, π]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evian::math::{IntoAngle, Vec2};
+    ///
+    /// assert_eq!(Vec2::new(1.0, 1.0).angle(), 45.0.deg());
+    /// assert_eq!(Vec2::new(-1.0, 1.0).angle(), 135.0.deg());
+    /// assert_eq!(Vec2::new(-1.0, -1.0).angle(), (-135.0).deg());
+    /// assert_eq!(Vec2::new(1.0, -1.0).angle(), (-45.0).deg());
+    ///
+    /// // Converting back to a vector gives a unit vector in the same direction.
+    /// let v = Vec2::from_angle(Vec2::new(3.0, -4.0).angle());
+    ///
+    /// assert!((v - Vec2::new(0.6, -0.8)).length() < 1e-12);
+    /// ```
+    pub fn angle(&self) -> Angle {
+        Angle::atan2(self.y, self.x)
     }
 
+    /// Converts this vector to polar coordinates, returning its length and
+    /// [angle](Vec2::angle).
+    ///
+    /// This is the inverse of [`Vec2::from_polar`]. The angle of a zero vector is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evian_math::{IntoAngle, Vec2};
+    ///
+    /// let (length, angle) = Vec2::new(0.0, 2.0).to_polar();
+    /// assert_eq!(length, 2.0);
+    /// assert_eq!(angle, 90.0.deg());
+    ///
+    /// // Converting back gives the original vector.
+    /// let v = Vec2::new(-3.0, 4.0);
+    /// let (length, angle) = v.to_polar();
+    /// assert!((Vec2::from_polar(length, angle.as_radians()) - v).length() < 1e-12);
+    ///
+    /// // A zero vector has no direction, so its angle is zero.
+    /// assert_eq!(Vec2::new(0.0, 0.0).to_polar(), (0.0, 0.0.deg()));
+    /// ```
+    pub fn to_polar(self) -> (f64, Angle) {
+        (self.length(), self.angle())
+    }
+}
+
+// MARK: Math
+
+impl<T: Real + Copy> Vec2<T> {
     /// Returns this vector's distance (magnitude) from the origin (0, 0).
     pub fn length(&self) -> T {
         self.x.hypot(self.y)
@@ -78,11 +176,56 @@ impl<T: Real + Copy + Sub<Output = T>> Vec2<T> {
     /// Returns the cartesian distance between one vector and another.
     ///
     /// This operation is equivalent to `(self - other).length()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evian::math::Vec2;
+    ///
+    /// assert_eq!(Vec2::new(1.0, 2.0).distance(Vec2::new(4.0, 6.0)), 5.0);
+    /// ```
     pub fn distance(&self, other: Vec2<T>) -> T {
         (*self - other).length()
     }
 }
 
+impl<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>> Vec2<T> {
+    /// Returns the square of this vector's length.
+    ///
+    /// This avoids the square root needed by [`Vec2::length`], making it cheaper when only
+    /// comparing lengths.
+    pub fn length_squared(&self) -> T {
+        self.x * self.x + self.y * self.y
+    }
+
+    /// Returns the square of the cartesian distance between one vector and another.
+    ///
+    /// This avoids the square root needed by [`Vec2::distance`], making it cheaper when only
+    /// comparing distances, such as when finding the nearest of several points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evian::math::Vec2;
+    ///
+    /// let position = Vec2::<f64>::new(0.0, 0.0);
+    /// let points = [Vec2::new(3.0, 4.0), Vec2::new(-1.0, 2.0), Vec2::new(6.0, 0.0)];
+    ///
+    /// assert_eq!(position.distance_squared(points[0]), 25.0);
+    ///
+    /// let nearest = points
+    ///     .into_iter()
+    ///     .min_by(|a, b| position.distance_squared(*a).total_cmp(&position.distance_squared(*b)));
+    /// assert_eq!(nearest, Some(Vec2::new(-1.0, 2.0)));
+    ///
+    /// // Also works for integer vectors.
+    /// assert_eq!(Vec2::new(1, 1).distance_squared(Vec2::new(4, 5)), 25);
+    /// ```
+    pub fn distance_squared(&self, other: Vec2<T>) -> T {
+        (*self - other).length_squared()
+    }
+}
+
 impl<T: Real + Copy + Div<Output = T>> Vec2<T> {
     /// Returns the unit (normalized) vector.
     ///
@@ -108,6 +251,91 @@ impl<T: Real + Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>> Vec2<
     }
 }
 
+impl<T: Neg<Output = T>> Vec2<T> {
+    /// Rotates this vector by exactly 90 degrees counterclockwise, returning `(-y, x)`.
+    ///
+    /// Unlike [`rotated`](Vec2::rotated), this involves no trigonometry, so the result is exact.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evian::math::Vec2;
+    ///
+    /// let v = Vec2::new(3.0, 4.0);
+    ///
+    /// assert_eq!(v.rotate_90_ccw(), Vec2::new(-4.0, 3.0));
+    /// assert_eq!(v.rotate_90_cw(), Vec2::new(4.0, -3.0));
+    /// assert_eq!(v.rotate_180(), Vec2::new(-3.0, -4.0));
+    /// ```
+    #[must_use]
+    pub fn rotate_90_ccw(self) -> Self {
+        Self {
+            x: -self.y,
+            y: self.x,
+        }
+    }
+
+    /// Rotates this vector by exactly 90 degrees clockwise, returning `(y, -x)`.
+    ///
+    /// Unlike [`rotated`](Vec2::rotated), this involves no trigonometry, so the result is exact.
+    #[must_use]
+    pub fn rotate_90_cw(self) -> Self {
+        Self {
+            x: self.y,
+            y: -self.x,
+        }
+    }
+
+    /// Rotates this vector by exactly 180 degrees, returning `(-x, -y)`.
+    ///
+    /// Unlike [`rotated`](Vec2::rotated), this involves no trigonometry, so the result is exact.
+    #[must_use]
+    pub fn rotate_180(self) -> Self {
+        Self {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Sub<Output = T>> Vec2<T> {
+    /// Mirrors this vector across the vertical line `x = axis`.
+    ///
+    /// This is useful for mirroring autonomous routines to the opposite side of a symmetric
+    /// field. The heading of a mirrored pose can be reflected using
+    /// [`MirrorAngle::mirror_x`](crate::MirrorAngle::mirror_x).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evian::math::Vec2;
+    ///
+    /// let v = Vec2::new(24.0, 12.0);
+    ///
+    /// assert_eq!(v.mirror_x(0.0), Vec2::new(-24.0, 12.0));
+    /// assert_eq!(v.mirror_y(72.0), Vec2::new(24.0, 132.0));
+    /// ```
+    #[must_use]
+    pub fn mirror_x(self, axis: T) -> Self {
+        Self {
+            x: axis + axis - self.x,
+            y: self.y,
+        }
+    }
+
+    /// Mirrors this vector across the horizontal line `y = axis`.
+    ///
+    /// The heading of a mirrored pose can be reflected using
+    /// [`MirrorAngle::mirror_y`](crate::MirrorAngle::mirror_y).
+    #[must_use]
+    pub fn mirror_y(self, axis: T) -> Self {
+        Self {
+            x: self.x,
+            y: axis + axis - self.y,
+        }
+    }
+}
+
 impl<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>> Vec2<T> {
     /// Linearly interpolates between two vectors.
     #[must_use]
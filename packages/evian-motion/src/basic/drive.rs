@@ -5,148 +5,441 @@ use std::{
     time::{Duration, Instant},
 };
 
-use vexide::time::{Sleep, sleep};
-
 use evian_control::{
     Tolerances,
     loops::{AngularPid, Feedback, Pid},
 };
 use evian_drivetrain::{Drivetrain, model::Arcade};
-use evian_math::Angle;
-use evian_tracking::{TracksForwardTravel, TracksHeading, TracksVelocity};
+use evian_math::{Angle, signed_delta};
+use evian_tracking::{TracksForwardTravel, TracksHeading, TracksPosition, TracksVelocity};
+
+use super::RotationLimit;
+use crate::brake::ActiveBrake;
+use crate::checked::{CheckedMotion, ErrorSlot};
+use crate::clock::{Clock, VexideClock};
+use crate::outcome::{MotionOutcome, SettlingMotion};
+use crate::telemetry::{
+    MotionTelemetry, PoseBuffer, PoseRecorder, PoseSample, SettleCondition, TickCallback,
+    is_settled,
+};
 
-pub(crate) struct DriveState {
-    pub sleep: Sleep,
+/// Largest setpoint offset (in radians) given to the angular controller during a relative turn.
+const MAX_RELATIVE_STEP: f64 = core::f64::consts::PI - 0.01;
+
+pub(crate) struct DriveState<S> {
+    pub sleep: S,
     pub initial_forward_travel: f64,
     pub start_time: Instant,
+    pub start_heading: Angle,
     pub prev_time: Instant,
     pub linear_settled: bool,
     pub angular_settled: bool,
+    pub brake: Option<ActiveBrake>,
+    pub relative_turn: Option<RelativeTurn>,
+    pub prev_angular_output: f64,
+    pub progress: f64,
+    pub settling_progress: f64,
+}
+
+/// Progress of a relative turn, started by [`Basic::turn_by`](crate::Basic::turn_by) or by a
+/// motion with a [rotation limit](DriveFuture::with_rotation_limit).
+pub(crate) struct RelativeTurn {
+    /// Total rotation of the turn, in radians.
+    pub delta: f64,
+
+    /// Heading during the previous update, used to accumulate how far the robot has rotated.
+    pub prev_heading: Angle,
+
+    /// Unwrapped rotation left to complete the turn, in radians.
+    pub remaining: f64,
 }
 
 /// Drives the robot forward or backwards for a distance at a given heading.
 #[must_use = "futures do nothing unless you `.await` or poll them"]
-pub struct DriveFuture<'a, M, L, A, T>
+pub struct DriveFuture<'a, M, L, A, T, C = VexideClock>
 where
     M: Arcade,
     L: Feedback<State = f64, Signal = f64> + Unpin,
     A: Feedback<State = Angle, Signal = f64> + Unpin,
     T: TracksForwardTravel + TracksHeading + TracksVelocity,
+    C: Clock,
 {
     pub(crate) target_distance: f64,
     pub(crate) target_heading: Angle,
+    pub(crate) turn_by: Option<Angle>,
     pub(crate) timeout: Option<Duration>,
     pub(crate) linear_tolerances: Tolerances,
     pub(crate) angular_tolerances: Tolerances,
     pub(crate) linear_controller: L,
     pub(crate) angular_controller: A,
     pub(crate) drivetrain: &'a mut Drivetrain<M, T>,
+    pub(crate) settle_condition: Option<SettleCondition<'a>>,
+    pub(crate) active_brake: bool,
+    pub(crate) min_angular_output: Option<f64>,
+    pub(crate) setpoint_ramp: Option<f64>,
+    pub(crate) max_turn_rate: Option<f64>,
+    pub(crate) angular_priority: bool,
+    pub(crate) rotation_limit: Option<RotationLimit>,
+    pub(crate) pose_recorder: Option<PoseRecorder<'a, T>>,
+    pub(crate) tick: Option<TickCallback<'a, T>>,
+    pub(crate) error: ErrorSlot<M::Error>,
+    pub(crate) outcome: Option<MotionOutcome>,
+
+    pub(crate) clock: C,
 
     /// Internal future state ("local variables").
-    pub(crate) state: Option<DriveState>,
+    pub(crate) state: Option<DriveState<C::Sleep>>,
 }
 
 // MARK: Future Poll
 
-impl<M, L, A, T> Future for DriveFuture<'_, M, L, A, T>
+impl<M, L, A, T, C> Future for DriveFuture<'_, M, L, A, T, C>
 where
     M: Arcade,
     L: Feedback<State = f64, Signal = f64> + Unpin,
     A: Feedback<State = Angle, Signal = f64> + Unpin,
     T: TracksForwardTravel + TracksHeading + TracksVelocity,
+    C: Clock,
 {
     type Output = ();
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.get_mut();
         let state = this.state.get_or_insert_with(|| {
-            let now = Instant::now();
+            let now = this.clock.now();
+            let start_heading = this.drivetrain.tracking.heading();
+
+            // Rotation limits need to know which way the robot turns, so turns to a heading are
+            // converted into relative turns, starting from the shortest rotation to the target.
+            let delta = match (this.turn_by, this.rotation_limit) {
+                (Some(delta), None) => Some(delta),
+                (delta, Some(limit)) => Some(limit.constrain(
+                    delta.unwrap_or_else(|| signed_delta(start_heading, this.target_heading)),
+                )),
+                (None, None) => None,
+            };
+
             DriveState {
-                sleep: sleep(Duration::from_millis(5)),
+                sleep: this.clock.sleep(Duration::from_millis(5)),
                 initial_forward_travel: this.drivetrain.tracking.forward_travel(),
                 start_time: now,
+                start_heading,
                 prev_time: now,
                 linear_settled: false,
                 angular_settled: false,
+                brake: None,
+                relative_turn: delta.map(|delta| RelativeTurn {
+                    delta: delta.as_radians(),
+                    prev_heading: start_heading,
+                    remaining: delta.as_radians(),
+                }),
+                prev_angular_output: 0.0,
+                progress: 0.0,
+                settling_progress: 0.0,
             }
         });
 
         if Pin::new(&mut state.sleep).poll(cx).is_pending() {
             return Poll::Pending;
         }
-        
-        let dt = state.prev_time.elapsed();
-        
+
+        if let Some(recorder) = &mut this.pose_recorder {
+            recorder.record(&this.drivetrain.tracking, this.clock.now());
+        }
+
+        if let Some(tick) = &mut this.tick {
+            tick.call(&this.drivetrain.tracking, this.clock.now());
+        }
+
+        if crate::pause_if_disabled(&this.clock, &mut state.sleep, cx, || {
+            this.error.record(this.drivetrain.model.drive_arcade(0.0, 0.0));
+        }) {
+            state.prev_time = this.clock.now();
+            return Poll::Pending;
+        }
+
+        let dt = this.clock.elapsed(state.prev_time);
+
         let forward_travel = this.drivetrain.tracking.forward_travel();
         let heading = this.drivetrain.tracking.heading();
 
         let linear_error = (this.target_distance + state.initial_forward_travel) - forward_travel;
-        let angular_error = (this.target_heading - heading).wrapped_half();
+
+        // How far the heading setpoint is allowed to have moved from the starting heading.
+        let ramp_limit = this
+            .setpoint_ramp
+            .map(|rate| rate * this.clock.elapsed(state.start_time).as_secs_f64());
+
+        let (angular_error, target_heading) = if let Some(turn) = &mut state.relative_turn {
+            // Relative turns track how far the robot has actually rotated, so that turns larger
+            // than a half rotation aren't cut short by wrapping.
+            turn.remaining -= signed_delta(turn.prev_heading, heading).as_radians();
+            turn.prev_heading = heading;
+
+            let mut step = turn.remaining;
+
+            // When ramping, only allow the setpoint to have progressed as far along the turn as
+            // the ramp has reached so far.
+            if let Some(limit) = ramp_limit {
+                let rotated = turn.delta - turn.remaining;
+                step = turn.delta.clamp(-limit, limit) - rotated;
+            }
+
+            // The angular controller will wrap its error, so the setpoint given to it can be at
+            // most (just under) a half rotation away in the direction we still need to turn.
+            let step = step.clamp(-MAX_RELATIVE_STEP, MAX_RELATIVE_STEP);
+
+            // The error is negated to match the sign of the absolute heading error below.
+            (Angle::from_radians(-turn.remaining), heading + Angle::from_radians(step))
+        } else {
+            let setpoint = if let Some(limit) = ramp_limit {
+                // Shortest rotation from the starting heading to the target.
+                let delta = signed_delta(state.start_heading, this.target_heading);
+                state.start_heading + Angle::from_radians(delta.as_radians().clamp(-limit, limit))
+            } else {
+                this.target_heading
+            };
+
+            ((this.target_heading - heading).wrapped_half(), setpoint)
+        };
         
         // println!("{}", linear_error);
 
-        if this
-            .linear_tolerances
-            .check(linear_error, this.drivetrain.tracking.linear_velocity())
-        {
+        let linear_velocity = this.drivetrain.tracking.linear_velocity();
+        let angular_velocity = this.drivetrain.tracking.angular_velocity();
+
+        let now = this.clock.now();
+        let linear_check = this.linear_tolerances.check(linear_error, linear_velocity, now);
+        let angular_check = this
+            .angular_tolerances
+            .check(angular_error.as_radians(), angular_velocity, now);
+
+        if linear_check.is_settled() {
             state.linear_settled = true;
         }
-        if this.angular_tolerances.check(
-            angular_error.as_radians(),
-            this.drivetrain.tracking.angular_velocity(),
-        ) {
+        if angular_check.is_settled() {
             state.angular_settled = true;
         }
 
-        if (state.linear_settled && state.angular_settled)
-            || this
-                .timeout
-                .is_some_and(|timeout| state.start_time.elapsed() > timeout)
-        {
-            drop(this.drivetrain.model.drive_arcade(0.0, 0.0));
+        // Turns don't drive any distance, so their progress is measured along the turn instead.
+        state.progress = if this.target_distance == 0.0 {
+            this.angular_tolerances.progress(angular_error.as_radians())
+        } else {
+            this.linear_tolerances.progress(linear_error)
+        };
+        state.settling_progress = f64::min(
+            if state.linear_settled {
+                1.0
+            } else {
+                this.linear_tolerances.settling_progress(&linear_check)
+            },
+            if state.angular_settled {
+                1.0
+            } else {
+                this.angular_tolerances.settling_progress(&angular_check)
+            },
+        );
+
+        // Once we've started braking, we've already settled.
+        let settled = state.brake.is_some()
+            || is_settled(
+                this.settle_condition.as_mut(),
+                state.linear_settled && state.angular_settled,
+                || MotionTelemetry {
+                    elapsed: this.clock.elapsed(state.start_time),
+                    linear_error,
+                    angular_error: Some(angular_error),
+                    linear_velocity,
+                    angular_velocity,
+                    linear_tolerances: Some(linear_check),
+                    angular_tolerances: Some(angular_check),
+                },
+            );
+
+        if settled && this.active_brake {
+            let brake = state
+                .brake
+                .get_or_insert_with(|| ActiveBrake::new(this.clock.now()));
+
+            if let Some((throttle, steer)) = brake.update(
+                this.clock.now(),
+                linear_velocity,
+                angular_velocity,
+                this.linear_tolerances.velocity_tolerance,
+                this.angular_tolerances.velocity_tolerance,
+            ) {
+                this.error.record(this.drivetrain.model.drive_arcade(throttle, steer));
+
+                state.sleep = this.clock.sleep(Duration::from_millis(5));
+                state.prev_time = this.clock.now();
+
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+        }
+
+        let timed_out = this
+            .timeout
+            .is_some_and(|timeout| this.clock.elapsed(state.start_time) > timeout);
+
+        if settled || timed_out {
+            this.outcome = Some(if settled {
+                MotionOutcome::Settled
+            } else {
+                MotionOutcome::TimedOut
+            });
+            this.error.record(this.drivetrain.model.drive_arcade(0.0, 0.0));
             return Poll::Ready(());
         }
 
-        let linear_output = this.linear_controller.update(
+        let mut linear_output = this.linear_controller.update(
             forward_travel,
             this.target_distance + state.initial_forward_travel,
             dt,
         );
-        let angular_output = this
+        let mut angular_output = this
             .angular_controller
-            .update(heading, this.target_heading, dt);
+            .update(heading, target_heading, dt);
+
+        angular_output = crate::apply_min_output(
+            angular_output,
+            this.min_angular_output,
+            angular_check.error_satisfied,
+        );
 
-        drop(
+        // Positive angular output turns the robot clockwise, while tracking reports
+        // counterclockwise-positive angular velocity.
+        if let Some(max_rate) = this.max_turn_rate {
+            angular_output = crate::limit_turn_rate(
+                angular_output,
+                state.prev_angular_output,
+                -angular_velocity,
+                max_rate,
+            );
+        }
+        state.prev_angular_output = angular_output;
+
+        if this.angular_priority {
+            (linear_output, angular_output) =
+                crate::prioritize_angular(linear_output, angular_output);
+        }
+
+        this.error.record(
             this.drivetrain
                 .model
                 .drive_arcade(linear_output, angular_output),
         );
 
-        state.sleep = sleep(Duration::from_millis(5));
-        state.prev_time = Instant::now();
+        state.sleep = this.clock.sleep(Duration::from_millis(5));
+        state.prev_time = this.clock.now();
 
         cx.waker().wake_by_ref();
         Poll::Pending
     }
 }
 
+impl<M, L, A, T, C> CheckedMotion for DriveFuture<'_, M, L, A, T, C>
+where
+    M: Arcade,
+    L: Feedback<State = f64, Signal = f64> + Unpin,
+    A: Feedback<State = Angle, Signal = f64> + Unpin,
+    T: TracksForwardTravel + TracksHeading + TracksVelocity,
+    C: Clock,
+{
+    type Error = M::Error;
+
+    fn take_error(&mut self) -> Option<M::Error> {
+        self.error.take()
+    }
+}
+
+impl<M, L, A, T, C> SettlingMotion for DriveFuture<'_, M, L, A, T, C>
+where
+    M: Arcade,
+    L: Feedback<State = f64, Signal = f64> + Unpin,
+    A: Feedback<State = Angle, Signal = f64> + Unpin,
+    T: TracksForwardTravel + TracksHeading + TracksVelocity,
+    C: Clock,
+{
+    fn outcome(&self) -> Option<MotionOutcome> {
+        self.outcome
+    }
+}
+
+// MARK: Progress
+
+impl<M, L, A, T, C> DriveFuture<'_, M, L, A, T, C>
+where
+    M: Arcade,
+    L: Feedback<State = f64, Signal = f64> + Unpin,
+    A: Feedback<State = Angle, Signal = f64> + Unpin,
+    T: TracksForwardTravel + TracksHeading + TracksVelocity,
+    C: Clock,
+{
+    /// Estimates how close this motion is to reaching its target, from `0.0` to `1.0`.
+    ///
+    /// This is the fraction of the motion's initial error that has been eliminated as of its most
+    /// recent update (see [`Tolerances::progress`]), measured along the drive for drives and along
+    /// the turn for turns. Before the motion is first polled, this returns `0.0`.
+    ///
+    /// Progress isn't monotonic, and will move backwards if the error grows (such as when the
+    /// robot overshoots its target or is pushed), so any UI displaying it should handle that.
+    ///
+    /// # Examples
+    ///
+    /// Since a motion can't be borrowed while it's being awaited, progress can be read by polling
+    /// the motion manually:
+    ///
+    /// ```ignore
+    /// let mut motion = basic.drive_distance(&mut drivetrain, 48.0);
+    ///
+    /// core::future::poll_fn(|cx| {
+    ///     leds.show_progress(motion.progress());
+    ///     Pin::new(&mut motion).poll(cx)
+    /// })
+    /// .await;
+    /// ```
+    #[must_use]
+    pub fn progress(&self) -> f64 {
+        self.state.as_ref().map_or(0.0, |state| state.progress)
+    }
+
+    /// Returns how much of its tolerance duration this motion has spent in tolerance, from `0.0`
+    /// to `1.0`.
+    ///
+    /// This is taken from whichever of the linear and angular settling timers is further from
+    /// finishing as of the motion's most recent update (see [`Tolerances::settling_progress`]).
+    /// It drops back to `0.0` whenever the robot leaves tolerance.
+    #[must_use]
+    pub fn settling_progress(&self) -> f64 {
+        self.state.as_ref().map_or(0.0, |state| state.settling_progress)
+    }
+}
+
 // MARK: Generic Modifiers
 
-impl<M, L, A, T> DriveFuture<'_, M, L, A, T>
+impl<'a, M, L, A, T, C> DriveFuture<'a, M, L, A, T, C>
 where
     M: Arcade,
     L: Feedback<State = f64, Signal = f64> + Unpin,
     A: Feedback<State = Angle, Signal = f64> + Unpin,
     T: TracksForwardTravel + TracksHeading + TracksVelocity,
+    C: Clock,
 {
     /// Modifies this motion's linear feedback controller.
-    pub fn with_linear_controller(&mut self, controller: L) -> &mut Self {
+    ///
+    /// The controller is [reset](Feedback::reset) before it is used.
+    pub fn with_linear_controller(&mut self, mut controller: L) -> &mut Self {
+        controller.reset();
         self.linear_controller = controller;
         self
     }
 
     /// Modifies this motion's angular feedback controller.
-    pub fn with_angular_controller(&mut self, controller: A) -> &mut Self {
+    ///
+    /// The controller is [reset](Feedback::reset) before it is used.
+    pub fn with_angular_controller(&mut self, mut controller: A) -> &mut Self {
+        controller.reset();
         self.angular_controller = controller;
         self
     }
@@ -163,6 +456,440 @@ where
         self
     }
 
+    /// Replaces the [`Clock`] used by this motion to keep time.
+    ///
+    /// See the [`clock`](crate::clock) module for more information. Any progress the motion has
+    /// made is discarded, so this should be called before the motion is first polled.
+    pub fn with_clock<C2: Clock>(self, clock: C2) -> DriveFuture<'a, M, L, A, T, C2> {
+        DriveFuture {
+            target_distance: self.target_distance,
+            target_heading: self.target_heading,
+            turn_by: self.turn_by,
+            timeout: self.timeout,
+            linear_tolerances: self.linear_tolerances,
+            angular_tolerances: self.angular_tolerances,
+            linear_controller: self.linear_controller,
+            angular_controller: self.angular_controller,
+            drivetrain: self.drivetrain,
+            settle_condition: self.settle_condition,
+            active_brake: self.active_brake,
+            min_angular_output: self.min_angular_output,
+            setpoint_ramp: self.setpoint_ramp,
+            max_turn_rate: self.max_turn_rate,
+            angular_priority: self.angular_priority,
+            rotation_limit: self.rotation_limit,
+            pose_recorder: self.pose_recorder,
+            tick: self.tick,
+            error: ErrorSlot::new(),
+            outcome: None,
+            clock,
+            state: None,
+        }
+    }
+
+    /// Adds a custom settling condition to this motion.
+    ///
+    /// The motion will complete once either `predicate` returns `true` or the motion's tolerances
+    /// are met, whichever happens first. This is useful for settling based on external conditions,
+    /// such as a limit switch being pressed.
+    pub fn with_settle_when(
+        &mut self,
+        predicate: impl FnMut(&MotionTelemetry) -> bool + 'a,
+    ) -> &mut Self {
+        self.settle_condition = Some(SettleCondition::new(predicate, false));
+        self
+    }
+
+    /// Replaces this motion's tolerances with a custom settling condition.
+    ///
+    /// The motion will only complete once `predicate` returns `true` (or once the motion times
+    /// out), regardless of whether its tolerances are met.
+    pub fn with_settle_only_when(
+        &mut self,
+        predicate: impl FnMut(&MotionTelemetry) -> bool + 'a,
+    ) -> &mut Self {
+        self.settle_condition = Some(SettleCondition::new(predicate, true));
+        self
+    }
+
+    /// Removes this motion's custom settling condition.
+    pub fn without_settle_condition(&mut self) -> &mut Self {
+        self.settle_condition = None;
+        self
+    }
+
+    /// Enables or disables active braking once this motion settles.
+    ///
+    /// By default, the drivetrain is left to coast once a motion settles, which may cause a
+    /// fast-moving robot to drift past its target. When active braking is enabled, the motion
+    /// will instead drive against the robot's residual velocity after settling, until the robot
+    /// has stopped (according to this motion's velocity tolerances) or a short time has passed.
+    ///
+    /// This requires a tracking system that reports signed linear velocity.
+    pub const fn with_active_brake(&mut self, enabled: bool) -> &mut Self {
+        self.active_brake = enabled;
+        self
+    }
+
+    /// Modifies this motion's minimum angular output.
+    ///
+    /// While the robot's heading is outside of this motion's angular error tolerance, the angular
+    /// output will never drop below this magnitude. This is useful for overcoming static friction
+    /// (sometimes called `kS`), which would otherwise cause small heading errors to produce
+    /// outputs too weak to turn the robot, leaving it settled a few degrees off. Once within the
+    /// error tolerance, the minimum is no longer applied so that it doesn't cause oscillation
+    /// around the target.
+    ///
+    /// # Examples
+    ///
+    /// The following simulates a robot that can't turn unless it is given at least 5% output.
+    /// A proportional controller alone stalls short of a 3° turn, while a minimum output drives
+    /// the error into tolerance:
+    ///
+    /// ```
+    /// use std::{pin::Pin, time::Duration};
+    ///
+    /// use evian_control::{Tolerances, loops::{AngularPid, Pid}};
+    /// use evian_drivetrain::Drivetrain;
+    /// use evian_math::IntoAngle;
+    /// use evian_motion::{
+    ///     Basic,
+    ///     clock::ManualClock,
+    ///     outcome::{MotionOutcome, SettlingMotion},
+    ///     sim::{self, SimRobot},
+    /// };
+    ///
+    /// let tolerance = 0.5_f64.to_radians();
+    ///
+    /// let simulate = |min_output: Option<f64>| {
+    ///     let robot = SimRobot::new().with_stiction(0.05);
+    ///     let mut drivetrain = Drivetrain::new(robot.clone(), robot.clone());
+    ///     let mut basic = Basic {
+    ///         linear_controller: Pid::new(1.0, 0.0, 0.0, None),
+    ///         angular_controller: AngularPid::new(0.5, 0.0, 0.0, None),
+    ///         linear_tolerances: Tolerances::new().error(0.5),
+    ///         angular_tolerances: Tolerances::new().error(tolerance),
+    ///         timeout: Some(Duration::from_secs(2)),
+    ///     };
+    ///
+    ///     let clock = ManualClock::new();
+    ///     let mut motion = basic
+    ///         .turn_to_heading(&mut drivetrain, 3.0.deg())
+    ///         .with_clock(clock.clone());
+    ///     if let Some(output) = min_output {
+    ///         motion.with_min_angular_output(output);
+    ///     }
+    ///
+    ///     sim::run(Pin::new(&mut motion), &clock);
+    ///
+    ///     let error = 3.0_f64.to_radians() - robot.unwrapped_heading().as_radians();
+    ///     (motion.outcome(), error)
+    /// };
+    ///
+    /// // Without a minimum output, the robot never starts turning.
+    /// let (outcome, error) = simulate(None);
+    /// assert_eq!(outcome, Some(MotionOutcome::TimedOut));
+    /// assert!(error > 2.0_f64.to_radians());
+    ///
+    /// // With one, the motion settles within its tolerance.
+    /// let (outcome, error) = simulate(Some(0.06));
+    /// assert_eq!(outcome, Some(MotionOutcome::Settled));
+    /// assert!(error.abs() < tolerance);
+    /// ```
+    pub const fn with_min_angular_output(&mut self, output: f64) -> &mut Self {
+        self.min_angular_output = Some(output);
+        self
+    }
+
+    /// Removes this motion's minimum angular output.
+    pub const fn without_min_angular_output(&mut self) -> &mut Self {
+        self.min_angular_output = None;
+        self
+    }
+
+    /// Ramps this motion's heading setpoint from the robot's starting heading towards its target
+    /// at `rate` radians per second.
+    ///
+    /// By default, the angular controller is given the target heading immediately, which can
+    /// cause an aggressively tuned controller to output full power and overshoot during large
+    /// turns. With a setpoint ramp, the controller instead chases a setpoint that moves towards
+    /// the target at a constant rate, easing the robot into the turn. This is a lighter-weight
+    /// alternative to a full motion profile, and only needs the rate to be tuned.
+    ///
+    /// The motion's angular error (used for settling) is still measured against the final target.
+    ///
+    /// # Examples
+    ///
+    /// The following records the setpoints given to the angular controller while turning a
+    /// simulated robot from 0 to 135 degrees with a ramp of 90 degrees per second:
+    ///
+    /// ```
+    /// use std::{cell::RefCell, rc::Rc, time::Duration};
+    ///
+    /// use evian_control::{Tolerances, loops::{AngularPid, Feedback, Pid}};
+    /// use evian_drivetrain::Drivetrain;
+    /// use evian_math::{Angle, IntoAngle};
+    /// use evian_motion::{
+    ///     Basic,
+    ///     clock::ManualClock,
+    ///     sim::{self, SimRobot},
+    /// };
+    ///
+    /// /// An angular PID controller that records every setpoint it is updated with.
+    /// #[derive(Clone)]
+    /// struct Recording(AngularPid, Rc<RefCell<Vec<Angle>>>);
+    ///
+    /// impl Feedback for Recording {
+    ///     type State = Angle;
+    ///     type Signal = f64;
+    ///
+    ///     fn update(&mut self, measurement: Angle, setpoint: Angle, dt: Duration) -> f64 {
+    ///         self.1.borrow_mut().push(setpoint);
+    ///         self.0.update(measurement, setpoint, dt)
+    ///     }
+    ///
+    ///     fn reset(&mut self) {
+    ///         self.0.reset();
+    ///     }
+    /// }
+    ///
+    /// /// Returns the setpoint (in degrees) given to the angular controller on each update.
+    /// fn setpoints(ramp: Option<f64>) -> Vec<f64> {
+    ///     let setpoints = Rc::new(RefCell::new(Vec::new()));
+    ///     let pid = AngularPid::new(1.0, 0.0, 0.0, None);
+    ///     let robot = SimRobot::new();
+    ///     let mut drivetrain = Drivetrain::new(robot.clone(), robot);
+    ///     let mut basic = Basic {
+    ///         linear_controller: Pid::new(1.0, 0.0, 0.0, None),
+    ///         angular_controller: Recording(pid, setpoints.clone()),
+    ///         linear_tolerances: Tolerances::new().error(0.5),
+    ///         angular_tolerances: Tolerances::new().error(0.01),
+    ///         timeout: Some(Duration::from_secs(3)),
+    ///     };
+    ///
+    ///     let clock = ManualClock::new();
+    ///     let mut motion = basic
+    ///         .turn_to_heading(&mut drivetrain, 135.0.deg())
+    ///         .with_clock(clock.clone());
+    ///     if let Some(rate) = ramp {
+    ///         motion.with_setpoint_ramp(rate);
+    ///     }
+    ///     sim::run(motion, &clock);
+    ///
+    ///     setpoints.take().iter().map(|setpoint| setpoint.as_degrees()).collect()
+    /// }
+    ///
+    /// // Without a ramp, the controller is given the target from the very first update...
+    /// assert!(setpoints(None).iter().all(|setpoint| (setpoint - 135.0).abs() < 1e-6));
+    ///
+    /// // ...while with one, the setpoint moves 0.45° each 5ms update until reaching the target.
+    /// let ramped = setpoints(Some(90.0_f64.to_radians()));
+    /// let at = |seconds: f64| ramped[(seconds / 0.005).round() as usize - 1];
+    ///
+    /// assert!((at(0.005) - 0.45).abs() < 1e-6);
+    /// assert!((at(0.5) - 45.0).abs() < 1e-6);
+    /// assert!((at(1.0) - 90.0).abs() < 1e-6);
+    /// assert!((at(1.5) - 135.0).abs() < 1e-6);
+    /// ```
+    pub const fn with_setpoint_ramp(&mut self, rate: f64) -> &mut Self {
+        self.setpoint_ramp = Some(rate);
+        self
+    }
+
+    /// Removes this motion's setpoint ramp.
+    pub const fn without_setpoint_ramp(&mut self) -> &mut Self {
+        self.setpoint_ramp = None;
+        self
+    }
+
+    /// Limits how fast the robot is allowed to turn during this motion, in radians per second.
+    ///
+    /// Unlike limiting the angular controller's output, this limits the robot's actual turn rate
+    /// as measured by [`TracksVelocity::angular_velocity`]. While the robot is turning faster
+    /// than its previous output would hold it at `rate`, the angular output is scaled down in
+    /// proportion, so the turn rate settles at the limit regardless of battery voltage or how
+    /// much power the drivetrain needs to turn. This is useful for tall robots that can tip over
+    /// during fast turns.
+    ///
+    /// Since the limit reacts to the measured turn rate, the robot can briefly exceed it while
+    /// the drivetrain is accelerating into a turn. For a stricter limit, this can be combined
+    /// with a [setpoint ramp](Self::with_setpoint_ramp).
+    ///
+    /// # Examples
+    ///
+    /// The following simulates a drivetrain that turns at up to 10 radians per second, turning
+    /// 150 degrees with the turn rate limited to 3 radians per second:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use evian_control::{Tolerances, loops::{AngularPid, Pid}};
+    /// use evian_drivetrain::Drivetrain;
+    /// use evian_math::IntoAngle;
+    /// use evian_motion::{
+    ///     Basic,
+    ///     clock::ManualClock,
+    ///     sim::{self, SimRobot},
+    /// };
+    ///
+    /// let max_rate = 3.0;
+    ///
+    /// let simulate = |limit: bool| {
+    ///     // The drivetrain takes about 0.1 seconds to reach the speed it's commanded to.
+    ///     let robot = SimRobot::new().with_lag(Duration::from_millis(100));
+    ///     let mut drivetrain = Drivetrain::new(robot.clone(), robot.clone());
+    ///     let mut basic = Basic {
+    ///         linear_controller: Pid::new(1.0, 0.0, 0.0, None),
+    ///         angular_controller: AngularPid::new(3.0, 0.0, 0.0, None),
+    ///         linear_tolerances: Tolerances::new().error(0.5),
+    ///         angular_tolerances: Tolerances::new().error(0.01).velocity(0.05),
+    ///         timeout: Some(Duration::from_secs(3)),
+    ///     };
+    ///
+    ///     let clock = ManualClock::new();
+    ///     let mut motion = basic
+    ///         .turn_to_heading(&mut drivetrain, 150.0.deg())
+    ///         .with_clock(clock.clone());
+    ///     if limit {
+    ///         motion.with_max_turn_rate(max_rate);
+    ///     }
+    ///
+    ///     sim::run(motion, &clock);
+    ///
+    ///     // Turning counterclockwise, the robot's turn rate is its angular velocity.
+    ///     robot.history().iter().map(|sample| sample.angular_velocity).collect::<Vec<_>>()
+    /// };
+    ///
+    /// let peak = |rates: &[f64]| rates.iter().copied().fold(0.0, f64::max);
+    ///
+    /// // Unlimited, the robot turns at nearly its full speed.
+    /// assert!(peak(&simulate(false)) > 9.0);
+    ///
+    /// // Limited, the turn rate briefly overshoots while spinning up, then holds at the limit.
+    /// let rates = simulate(true);
+    /// assert!(peak(&rates) < 1.35 * max_rate);
+    /// assert!(rates[60..170].iter().all(|rate| (rate - max_rate).abs() < 0.1 * max_rate));
+    /// ```
+    pub const fn with_max_turn_rate(&mut self, rate: f64) -> &mut Self {
+        self.max_turn_rate = Some(rate);
+        self
+    }
+
+    /// Removes this motion's turn rate limit.
+    pub const fn without_max_turn_rate(&mut self) -> &mut Self {
+        self.max_turn_rate = None;
+        self
+    }
+
+    /// Enables or disables prioritizing this motion's angular output over its linear output.
+    ///
+    /// Each update, the linear and angular controllers' outputs are passed to
+    /// [`Arcade::drive_arcade`] as throttle and steer. If their sum exceeds full power on either
+    /// side of the drivetrain, the model scales them down according to its own desaturation
+    /// policy, which for most models scales both down uniformly. Far from the target, the
+    /// linear output is often several times larger than full power, so the heading correction is
+    /// shrunk by the same large factor and the robot barely turns until it slows down.
+    ///
+    /// With angular priority enabled, the linear output is instead reduced just enough to leave
+    /// headroom for the full angular output (which is only scaled down if it alone exceeds full
+    /// power), matching the [`PreserveRotation`] desaturation policy. This keeps the robot on
+    /// heading at full drive speed, at the cost of driving slightly slower while correcting large
+    /// heading errors. This is disabled by default.
+    ///
+    /// The same mixing can be applied to every motion by giving the drivetrain model a
+    /// [`PreserveRotation`] policy, such as through a [`Differential`]'s arcade desaturation
+    /// policy.
+    ///
+    /// # Examples
+    ///
+    /// The following simulates half a second of a drive towards a target 48 inches away that
+    /// starts 30° off heading, on a robot that scales its throttle and steer down uniformly:
+    ///
+    /// ```
+    /// use std::{pin::pin, time::Duration};
+    ///
+    /// use evian_control::{Tolerances, loops::{AngularPid, Pid}};
+    /// use evian_drivetrain::Drivetrain;
+    /// use evian_math::IntoAngle;
+    /// use evian_motion::{
+    ///     Basic,
+    ///     clock::ManualClock,
+    ///     sim::{self, SimRobot},
+    /// };
+    ///
+    /// let heading_error_after = |angular_priority: bool| {
+    ///     let robot = SimRobot::new();
+    ///     let mut drivetrain = Drivetrain::new(robot.clone(), robot.clone());
+    ///     let mut basic = Basic {
+    ///         linear_controller: Pid::new(0.1, 0.0, 0.0, None),
+    ///         angular_controller: AngularPid::new(1.0, 0.0, 0.0, None),
+    ///         linear_tolerances: Tolerances::new().error(0.5),
+    ///         angular_tolerances: Tolerances::new().error(0.01),
+    ///         timeout: None,
+    ///     };
+    ///
+    ///     let clock = ManualClock::new();
+    ///     let mut motion = basic
+    ///         .drive_distance_at_heading(&mut drivetrain, 48.0, 30.0.deg())
+    ///         .with_clock(clock.clone());
+    ///     motion.with_angular_priority(angular_priority);
+    ///
+    ///     // The first step starts the motion, and the next 100 each drive it for 5ms.
+    ///     let mut motion = pin!(motion);
+    ///     for _ in 0..=100 {
+    ///         _ = sim::step(motion.as_mut(), &clock);
+    ///     }
+    ///
+    ///     30.0 - robot.unwrapped_heading().as_degrees()
+    /// };
+    ///
+    /// // Mixed uniformly, the heading correction is starved by the saturated linear output...
+    /// assert!(heading_error_after(false) > 5.0);
+    ///
+    /// // ...while prioritizing it corrects the heading almost immediately.
+    /// assert!(heading_error_after(true).abs() < 1.0);
+    /// ```
+    ///
+    /// [`PreserveRotation`]: evian_drivetrain::model::DesaturationPolicy::PreserveRotation
+    /// [`Differential`]: evian_drivetrain::model::Differential
+    pub const fn with_angular_priority(&mut self, enabled: bool) -> &mut Self {
+        self.angular_priority = enabled;
+        self
+    }
+
+    /// Limits how far this motion may rotate the robot, between `min_turns` (clockwise) and
+    /// `max_turns` (counterclockwise) full turns from its heading when the motion starts.
+    ///
+    /// This is useful for robots with a tether or cable that would be damaged by winding up.
+    /// Turns to a heading normally take the shortest way around, and [relative
+    /// turns](crate::Basic::turn_by) turn by exactly their given angle. With a rotation limit,
+    /// a turn that would pass the limit goes the other way around to reach the same heading, or
+    /// is clamped to the limit if neither way fits. See [`RotationLimit`] for more information.
+    ///
+    /// The limit only applies to the rotation made during this motion. To keep a tether within
+    /// its limits over a whole routine, pass each motion the rotation left on either side based
+    /// on how far previous motions have turned.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// // Facing 0°, this turns 270° counterclockwise rather than 90° clockwise.
+    /// basic
+    ///     .turn_to_heading(&mut drivetrain, (-90.0).deg())
+    ///     .with_rotation_limit(0.0, 1.0)
+    ///     .await;
+    /// ```
+    pub const fn with_rotation_limit(&mut self, min_turns: f64, max_turns: f64) -> &mut Self {
+        self.rotation_limit = Some(RotationLimit::new(min_turns, max_turns));
+        self
+    }
+
+    /// Removes this motion's rotation limit.
+    pub const fn without_rotation_limit(&mut self) -> &mut Self {
+        self.rotation_limit = None;
+        self
+    }
+
     /// Modifies this motion's linear tolerances.
     pub const fn with_linear_tolerances(&mut self, tolerances: Tolerances) -> &mut Self {
         self.linear_tolerances = tolerances;
@@ -212,6 +939,16 @@ where
         self
     }
 
+    /// Modifies how many consecutive out-of-tolerance checks this motion's linear and angular
+    /// tolerances allow before their settling timers reset.
+    ///
+    /// See [`Tolerances::debounce`] for more information.
+    pub const fn with_settle_debounce(&mut self, debounce: u32) -> &mut Self {
+        self.linear_tolerances.debounce = debounce;
+        self.angular_tolerances.debounce = debounce;
+        self
+    }
+
     /// Modifies this motion's angular tolerances.
     pub const fn with_angular_tolerances(&mut self, tolerances: Tolerances) -> &mut Self {
         self.angular_tolerances = tolerances;
@@ -255,13 +992,69 @@ where
     }
 }
 
+// MARK: Recording Modifiers
+
+impl<'a, M, L, A, T, C> DriveFuture<'a, M, L, A, T, C>
+where
+    M: Arcade,
+    L: Feedback<State = f64, Signal = f64> + Unpin,
+    A: Feedback<State = Angle, Signal = f64> + Unpin,
+    T: TracksForwardTravel + TracksPosition + TracksHeading + TracksVelocity,
+    C: Clock,
+{
+    /// Records the robot's pose into a [`PoseBuffer`] on every update of this motion.
+    ///
+    /// This requires a tracking system that tracks the robot's position.
+    pub fn record_into(&mut self, buffer: &'a mut PoseBuffer) -> &mut Self {
+        self.pose_recorder = Some(PoseRecorder::new(buffer));
+        self
+    }
+
+    /// Calls `callback` with the robot's pose on every update of this motion.
+    ///
+    /// This is useful for coordinating mechanisms with the drivetrain (such as firing a piston
+    /// once the robot has driven far enough) without spawning a separate task to race against
+    /// the motion. The callback runs on the motion's own loop right before each update, including
+    /// while the robot is disabled, so it's always in step with the motion.
+    ///
+    /// Since the motion can't update until the callback returns, the callback must return
+    /// quickly and must not block (such as by sleeping or waiting on a sensor). Anything that
+    /// takes longer should run in its own task instead.
+    ///
+    /// This requires a tracking system that tracks the robot's position.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// basic
+    ///     .drive_distance(&mut drivetrain, 48.0)
+    ///     .with_tick(|pose| {
+    ///         if pose.position.y > 24.0 {
+    ///             _ = clamp.set_high();
+    ///         }
+    ///     })
+    ///     .await;
+    /// ```
+    pub fn with_tick(&mut self, callback: impl FnMut(&PoseSample) + 'a) -> &mut Self {
+        self.tick = Some(TickCallback::new(callback));
+        self
+    }
+
+    /// Removes this motion's tick callback.
+    pub fn without_tick(&mut self) -> &mut Self {
+        self.tick = None;
+        self
+    }
+}
+
 // MARK: Linear PID Modifiers
 
-impl<M, A, T> DriveFuture<'_, M, Pid, A, T>
+impl<M, A, T, C> DriveFuture<'_, M, Pid, A, T, C>
 where
     M: Arcade,
     A: Feedback<State = Angle, Signal = f64> + Unpin,
     T: TracksForwardTravel + TracksHeading + TracksVelocity,
+    C: Clock,
 {
     /// Modifies this motion's linear PID gains.
     pub const fn with_linear_gains(&mut self, kp: f64, ki: f64, kd: f64) -> &mut Self {
@@ -315,11 +1108,12 @@ where
 
 // MARK: Angular PID Modifiers
 
-impl<M, L, T> DriveFuture<'_, M, L, AngularPid, T>
+impl<M, L, T, C> DriveFuture<'_, M, L, AngularPid, T, C>
 where
     M: Arcade,
     L: Feedback<State = f64, Signal = f64> + Unpin,
     T: TracksForwardTravel + TracksHeading + TracksVelocity,
+    C: Clock,
 {
     /// Modifies this motion's angular PID gains.
     pub const fn with_angular_gains(&mut self, kp: f64, ki: f64, kd: f64) -> &mut Self {
@@ -0,0 +1,142 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use evian_drivetrain::{Drivetrain, model::Arcade};
+use evian_tracking::Tracking;
+
+use crate::checked::{CheckedMotion, ErrorSlot};
+use crate::clock::{Clock, VexideClock};
+
+pub(crate) struct State<S> {
+    sleep: S,
+    start_time: Instant,
+}
+
+/// Drives the robot at fixed arcade outputs for a duration, without any feedback.
+///
+/// This is created by [`Basic::drive_for`](crate::Basic::drive_for).
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct DriveForFuture<'a, M, T, C = VexideClock>
+where
+    M: Arcade,
+    T: Tracking,
+    C: Clock,
+{
+    pub(crate) throttle: f64,
+    pub(crate) steer: f64,
+    pub(crate) duration: Duration,
+    pub(crate) drivetrain: &'a mut Drivetrain<M, T>,
+    pub(crate) error: ErrorSlot<M::Error>,
+
+    pub(crate) clock: C,
+
+    /// Internal future state ("local variables").
+    pub(crate) state: Option<State<C::Sleep>>,
+}
+
+// MARK: Future Poll
+
+impl<M, T, C> Future for DriveForFuture<'_, M, T, C>
+where
+    M: Arcade,
+    T: Tracking,
+    C: Clock,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let state = this.state.get_or_insert_with(|| State {
+            sleep: this.clock.sleep(Duration::from_millis(5)),
+            start_time: this.clock.now(),
+        });
+
+        if Pin::new(&mut state.sleep).poll(cx).is_pending() {
+            return Poll::Pending;
+        }
+
+        if this.clock.elapsed(state.start_time) >= this.duration {
+            this.error.record(this.drivetrain.model.drive_arcade(0.0, 0.0));
+            return Poll::Ready(());
+        }
+
+        if crate::pause_if_disabled(&this.clock, &mut state.sleep, cx, || {
+            this.error.record(this.drivetrain.model.drive_arcade(0.0, 0.0));
+        }) {
+            return Poll::Pending;
+        }
+
+        this.error.record(this.drivetrain.model.drive_arcade(this.throttle, this.steer));
+
+        state.sleep = this.clock.sleep(Duration::from_millis(5));
+
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+impl<M, T, C> CheckedMotion for DriveForFuture<'_, M, T, C>
+where
+    M: Arcade,
+    T: Tracking,
+    C: Clock,
+{
+    type Error = M::Error;
+
+    fn take_error(&mut self) -> Option<M::Error> {
+        self.error.take()
+    }
+}
+
+// MARK: Progress
+
+impl<M, T, C> DriveForFuture<'_, M, T, C>
+where
+    M: Arcade,
+    T: Tracking,
+    C: Clock,
+{
+    /// Returns how much of its duration this motion has spent driving, from `0.0` to `1.0`.
+    ///
+    /// Before the motion is first polled, this returns `0.0`.
+    #[must_use]
+    pub fn progress(&self) -> f64 {
+        self.state.as_ref().map_or(0.0, |state| {
+            if self.duration.is_zero() {
+                1.0
+            } else {
+                (self.clock.elapsed(state.start_time).as_secs_f64() / self.duration.as_secs_f64())
+                    .min(1.0)
+            }
+        })
+    }
+}
+
+// MARK: Modifiers
+
+impl<'a, M, T, C> DriveForFuture<'a, M, T, C>
+where
+    M: Arcade,
+    T: Tracking,
+    C: Clock,
+{
+    /// Replaces the [`Clock`] used by this motion to keep time.
+    ///
+    /// See the [`clock`](crate::clock) module for more information. Any progress the motion has
+    /// made is discarded, so this should be called before the motion is first polled.
+    pub fn with_clock<C2: Clock>(self, clock: C2) -> DriveForFuture<'a, M, T, C2> {
+        DriveForFuture {
+            throttle: self.throttle,
+            steer: self.steer,
+            duration: self.duration,
+            drivetrain: self.drivetrain,
+            error: ErrorSlot::new(),
+            clock,
+            state: None,
+        }
+    }
+}
@@ -0,0 +1,169 @@
+extern crate alloc;
+
+use alloc::rc::Rc;
+use core::cell::Cell;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use evian_control::loops::Feedback;
+use evian_drivetrain::model::Arcade;
+use evian_math::Angle;
+use evian_tracking::{TracksForwardTravel, TracksHeading, TracksVelocity};
+
+use super::DriveFuture;
+use crate::checked::CheckedMotion;
+use crate::clock::{Clock, VexideClock};
+
+/// Describes why a [`DriveUntilFuture`] completed.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum DriveUntilOutcome {
+    /// The motion's end condition returned `true` before the distance limit was reached.
+    ConditionMet,
+
+    /// The motion settled at its distance limit (or timed out) without its end condition ever
+    /// returning `true`.
+    LimitReached,
+}
+
+impl DriveUntilOutcome {
+    /// Returns `true` if the motion was ended by its end condition.
+    #[must_use]
+    pub const fn is_condition_met(&self) -> bool {
+        matches!(self, Self::ConditionMet)
+    }
+}
+
+/// Drives the robot forward or backwards until a condition is met, or until a maximum distance is
+/// reached.
+///
+/// This is created by [`Basic::drive_until`](crate::Basic::drive_until), and resolves to a
+/// [`DriveUntilOutcome`] describing which of the two happened first.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct DriveUntilFuture<'a, M, L, A, T, C = VexideClock>
+where
+    M: Arcade,
+    L: Feedback<State = f64, Signal = f64> + Unpin,
+    A: Feedback<State = Angle, Signal = f64> + Unpin,
+    T: TracksForwardTravel + TracksHeading + TracksVelocity,
+    C: Clock,
+{
+    pub(crate) motion: DriveFuture<'a, M, L, A, T, C>,
+
+    /// Set by the motion's settling condition once the end condition has returned `true`.
+    pub(crate) condition_met: Rc<Cell<bool>>,
+}
+
+impl<M, L, A, T, C> Future for DriveUntilFuture<'_, M, L, A, T, C>
+where
+    M: Arcade,
+    L: Feedback<State = f64, Signal = f64> + Unpin,
+    A: Feedback<State = Angle, Signal = f64> + Unpin,
+    T: TracksForwardTravel + TracksHeading + TracksVelocity,
+    C: Clock,
+{
+    type Output = DriveUntilOutcome;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        Pin::new(&mut this.motion).poll(cx).map(|()| {
+            if this.condition_met.get() {
+                DriveUntilOutcome::ConditionMet
+            } else {
+                DriveUntilOutcome::LimitReached
+            }
+        })
+    }
+}
+
+impl<M, L, A, T, C> CheckedMotion for DriveUntilFuture<'_, M, L, A, T, C>
+where
+    M: Arcade,
+    L: Feedback<State = f64, Signal = f64> + Unpin,
+    A: Feedback<State = Angle, Signal = f64> + Unpin,
+    T: TracksForwardTravel + TracksHeading + TracksVelocity,
+    C: Clock,
+{
+    type Error = M::Error;
+
+    fn take_error(&mut self) -> Option<M::Error> {
+        self.motion.take_error()
+    }
+}
+
+impl<'a, M, L, A, T, C> DriveUntilFuture<'a, M, L, A, T, C>
+where
+    M: Arcade,
+    L: Feedback<State = f64, Signal = f64> + Unpin,
+    A: Feedback<State = Angle, Signal = f64> + Unpin,
+    T: TracksForwardTravel + TracksHeading + TracksVelocity,
+    C: Clock,
+{
+    /// Estimates how close this motion is to its distance limit, from `0.0` to `1.0`.
+    ///
+    /// See [`DriveFuture::progress`] for more information.
+    #[must_use]
+    pub fn progress(&self) -> f64 {
+        self.motion.progress()
+    }
+
+    /// Returns how much of its tolerance duration this motion has spent in tolerance at its
+    /// distance limit, from `0.0` to `1.0`.
+    ///
+    /// See [`DriveFuture::settling_progress`] for more information.
+    #[must_use]
+    pub fn settling_progress(&self) -> f64 {
+        self.motion.settling_progress()
+    }
+
+    /// Modifies this motion's timeout duration.
+    ///
+    /// A motion that times out resolves to [`DriveUntilOutcome::LimitReached`].
+    pub const fn with_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.motion.with_timeout(timeout);
+        self
+    }
+
+    /// Removes this motion's timeout duration.
+    pub const fn without_timeout(&mut self) -> &mut Self {
+        self.motion.without_timeout();
+        self
+    }
+
+    /// Enables or disables active braking once this motion settles.
+    ///
+    /// See [`DriveFuture::with_active_brake`] for more information.
+    pub const fn with_active_brake(&mut self, enabled: bool) -> &mut Self {
+        self.motion.with_active_brake(enabled);
+        self
+    }
+
+    /// Modifies this motion's linear feedback controller.
+    pub fn with_linear_controller(&mut self, controller: L) -> &mut Self {
+        self.motion.with_linear_controller(controller);
+        self
+    }
+
+    /// Modifies this motion's angular feedback controller.
+    pub fn with_angular_controller(&mut self, controller: A) -> &mut Self {
+        self.motion.with_angular_controller(controller);
+        self
+    }
+
+    /// Replaces the [`Clock`] used by this motion to keep time.
+    ///
+    /// See the [`clock`](crate::clock) module for more information. Any progress the motion has
+    /// made is discarded, so this should be called before the motion is first polled.
+    pub fn with_clock<C2: Clock>(self, clock: C2) -> DriveUntilFuture<'a, M, L, A, T, C2> {
+        self.condition_met.set(false);
+
+        DriveUntilFuture {
+            motion: self.motion.with_clock(clock),
+            condition_met: self.condition_met,
+        }
+    }
+}
@@ -1,19 +1,86 @@
 //! Feedback-driven driving and turning.
 
+extern crate alloc;
+
+use alloc::rc::Rc;
+use core::cell::Cell;
 use std::time::Duration;
 
 use evian_control::{Tolerances, loops::Feedback};
 use evian_drivetrain::{Drivetrain, model::Arcade};
 use evian_math::{Angle, Vec2};
-use evian_tracking::{TracksForwardTravel, TracksHeading, TracksPosition, TracksVelocity};
+use evian_tracking::{
+    Tracking, TracksForwardTravel, TracksHeading, TracksPosition, TracksVelocity,
+};
+
+use crate::{checked::ErrorSlot, clock::VexideClock, telemetry::SettleCondition};
 
 mod drive;
+mod drive_for;
+mod drive_until;
+mod rotation_limit;
 mod turn_to_point;
 
 pub use drive::DriveFuture;
+pub use drive_for::DriveForFuture;
+pub use drive_until::{DriveUntilFuture, DriveUntilOutcome};
+pub use rotation_limit::RotationLimit;
 pub use turn_to_point::TurnToPointFuture;
 
 /// Feedback-driven driving and turning.
+///
+/// # Controller State
+///
+/// Each motion started from a `Basic` runs on its own freshly [reset](Feedback::reset) copy of
+/// the stored controllers, so no state (such as a PID's integral) carries over between motions
+/// and a routine behaves the same way every time it is run. Modifiers on the returned motion
+/// (such as [`DriveFuture::with_linear_kp`]) only change that motion's copy.
+///
+/// ```
+/// use std::{pin::pin, time::Duration};
+///
+/// use evian_control::{Tolerances, loops::{AngularPid, Feedback, Pid}};
+/// use evian_drivetrain::Drivetrain;
+/// use evian_motion::{
+///     Basic,
+///     clock::ManualClock,
+///     sim::{self, SimRobot},
+/// };
+///
+/// /// Returns the throttle from the first update of a 24 inch drive.
+/// fn first_throttle(basic: &mut Basic<Pid, AngularPid>) -> f64 {
+///     let robot = SimRobot::stuck();
+///     let mut drivetrain = Drivetrain::new(robot.clone(), robot.clone());
+///     let clock = ManualClock::new();
+///     let mut motion = pin!(
+///         basic
+///             .drive_distance(&mut drivetrain, 24.0)
+///             .with_clock(clock.clone())
+///     );
+///
+///     _ = sim::step(motion.as_mut(), &clock);
+///     _ = sim::step(motion.as_mut(), &clock);
+///
+///     robot.last().unwrap().throttle
+/// }
+///
+/// let mut basic = Basic {
+///     linear_controller: Pid::new(0.01, 0.1, 0.0, None),
+///     angular_controller: AngularPid::new(1.0, 0.0, 0.0, None),
+///     linear_tolerances: Tolerances::new().error(0.5),
+///     angular_tolerances: Tolerances::new().error(0.05),
+///     timeout: None,
+/// };
+/// let fresh = first_throttle(&mut basic);
+///
+/// // Build up integral in the stored controller...
+/// for _ in 0..100 {
+///     basic.linear_controller.update(0.0, 24.0, Duration::from_millis(10));
+/// }
+///
+/// // ...which the next motion doesn't inherit.
+/// assert_eq!(first_throttle(&mut basic), fresh);
+/// ```
 #[derive(PartialEq)]
 pub struct Basic<L, A>
 where
@@ -45,6 +112,14 @@ where
     /// turning to face a heading.
     ///
     /// Negative `target_distance` values will move the robot backwards.
+    ///
+    /// # Output Mixing
+    ///
+    /// The linear controller's output is used as the arcade throttle and the angular controller's
+    /// output as the steer. When both are large, the drivetrain model scales them down to fit
+    /// within full power, which by default reduces the heading correction along with the drive
+    /// speed. See [`DriveFuture::with_angular_priority`] for keeping the robot on heading at full
+    /// speed.
     pub fn drive_distance_at_heading<
         'a,
         M: Arcade,
@@ -58,12 +133,25 @@ where
         DriveFuture {
             target_distance,
             target_heading,
+            turn_by: None,
             timeout: self.timeout,
             linear_tolerances: self.linear_tolerances,
             angular_tolerances: self.angular_tolerances,
-            linear_controller: self.linear_controller.clone(),
-            angular_controller: self.angular_controller.clone(),
+            linear_controller: crate::fresh_controller(&self.linear_controller),
+            angular_controller: crate::fresh_controller(&self.angular_controller),
             drivetrain,
+            settle_condition: None,
+            active_brake: false,
+            min_angular_output: None,
+            setpoint_ramp: None,
+            max_turn_rate: None,
+            angular_priority: false,
+            rotation_limit: None,
+            pose_recorder: None,
+            tick: None,
+            error: ErrorSlot::new(),
+            outcome: None,
+            clock: VexideClock,
             state: None,
         }
     }
@@ -83,6 +171,97 @@ where
         self.drive_distance_at_heading(drivetrain, distance, drivetrain.tracking.heading())
     }
 
+    /// Moves the robot forwards until `condition` returns `true`, driving no further than
+    /// `max_distance` (measured in wheel units).
+    ///
+    /// `condition` is checked on every update of the motion, which stops early once it returns
+    /// `true`. Otherwise, the motion settles at `max_distance` as
+    /// [`drive_distance`](Basic::drive_distance) would. This is useful for driving until an
+    /// external sensor (such as a line tracker or limit switch) is triggered, while capping how
+    /// far the robot can travel if the sensor never triggers. The motion resolves to a
+    /// [`DriveUntilOutcome`] describing which of the two happened.
+    ///
+    /// Negative `max_distance` values will move the robot backwards.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let outcome = basic
+    ///     .drive_until(&mut drivetrain, 36.0, || bumper.is_pressed().unwrap_or(false))
+    ///     .await;
+    ///
+    /// if !outcome.is_condition_met() {
+    ///     // Drove the full 36 inches without hitting anything.
+    /// }
+    /// ```
+    pub fn drive_until<
+        'a,
+        M: Arcade,
+        T: TracksForwardTravel + TracksHeading + TracksVelocity,
+    >(
+        &mut self,
+        drivetrain: &'a mut Drivetrain<M, T>,
+        max_distance: f64,
+        mut condition: impl FnMut() -> bool + 'a,
+    ) -> DriveUntilFuture<'a, M, L, A, T> {
+        let condition_met = Rc::new(Cell::new(false));
+        let mut motion = self.drive_distance(drivetrain, max_distance);
+
+        motion.settle_condition = Some(SettleCondition::new(
+            {
+                let condition_met = condition_met.clone();
+                move |_| {
+                    let met = condition();
+                    condition_met.set(met);
+                    met
+                }
+            },
+            false,
+        ));
+
+        DriveUntilFuture {
+            motion,
+            condition_met,
+        }
+    }
+
+    /// Drives the robot at fixed arcade outputs for `duration`, then stops.
+    ///
+    /// `throttle` and `steer` are passed directly to [`Arcade::drive_arcade`] on every update,
+    /// with no feedback from the tracking system, so how far the robot travels depends on its
+    /// battery, load, and traction. This is intended as a fallback for simple routines or for
+    /// when sensors can't be trusted, rather than as a replacement for
+    /// [`drive_distance`](Basic::drive_distance).
+    ///
+    /// The drivetrain is driven at zero output once `duration` has passed, which stops it using
+    /// the model's configured stopping behavior (such as coasting or a brake mode).
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// // Push forwards into a wall for half a second.
+    /// basic
+    ///     .drive_for(&mut drivetrain, 0.5, 0.0, Duration::from_millis(500))
+    ///     .await;
+    /// ```
+    pub fn drive_for<'a, M: Arcade, T: Tracking>(
+        &mut self,
+        drivetrain: &'a mut Drivetrain<M, T>,
+        throttle: f64,
+        steer: f64,
+        duration: Duration,
+    ) -> DriveForFuture<'a, M, T> {
+        DriveForFuture {
+            throttle,
+            steer,
+            duration,
+            drivetrain,
+            error: ErrorSlot::new(),
+            clock: VexideClock,
+            state: None,
+        }
+    }
+
     /// Turns the robot in place to face a heading.
     pub fn turn_to_heading<
         'a,
@@ -96,6 +275,78 @@ where
         self.drive_distance_at_heading(drivetrain, 0.0, heading)
     }
 
+    /// Turns the robot in place by an angle relative to its heading when the motion starts.
+    ///
+    /// Unlike [`turn_to_heading`](Basic::turn_to_heading), which takes the shortest path to its
+    /// target, this always turns in the direction of `delta` (counterclockwise for positive
+    /// angles) and by its full amount. For instance, a turn of `270.0.deg()` will rotate the
+    /// robot three quarters of a turn counterclockwise rather than a quarter turn clockwise.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// // Facing 45°, this will settle facing 135°.
+    /// basic.turn_by(&mut drivetrain, 90.0.deg()).await;
+    /// ```
+    ///
+    /// Turning a simulated robot shows that turns go the long way around when asked to:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use evian_control::{Tolerances, loops::{AngularPid, Pid}};
+    /// use evian_drivetrain::Drivetrain;
+    /// use evian_math::{Angle, IntoAngle};
+    /// use evian_motion::{
+    ///     Basic,
+    ///     clock::ManualClock,
+    ///     sim::{self, SimRobot},
+    /// };
+    ///
+    /// /// Turns the robot by `delta` from `start`, returning its unwrapped heading once settled.
+    /// fn turn_by(start: Angle, delta: Angle) -> Angle {
+    ///     let robot = SimRobot::new().with_max_turn_rate(4.0).with_heading(start);
+    ///     let mut drivetrain = Drivetrain::new(robot.clone(), robot.clone());
+    ///     let mut basic = Basic {
+    ///         linear_controller: Pid::new(1.0, 0.0, 0.0, None),
+    ///         angular_controller: AngularPid::new(1.0, 0.0, 0.0, None),
+    ///         linear_tolerances: Tolerances::new().error(0.5),
+    ///         angular_tolerances: Tolerances::new().error(0.5_f64.to_radians()).velocity(0.05),
+    ///         timeout: Some(Duration::from_secs(5)),
+    ///     };
+    ///
+    ///     let clock = ManualClock::new();
+    ///     sim::run(basic.turn_by(&mut drivetrain, delta).with_clock(clock.clone()), &clock);
+    ///
+    ///     robot.unwrapped_heading()
+    /// }
+    ///
+    /// let near = |heading: Angle, degrees: f64| (heading.as_degrees() - degrees).abs() < 1.0;
+    ///
+    /// // Facing 45°, turning by 90° settles facing 135°.
+    /// assert!(near(turn_by(45.0.deg(), 90.0.deg()), 135.0));
+    ///
+    /// // Turning by 270° rotates three quarters of a turn counterclockwise, rather than taking
+    /// // the shortcut of a quarter turn clockwise to the same heading. Negative turns do the same
+    /// // in the other direction.
+    /// assert!(near(turn_by(Angle::ZERO, 270.0.deg()), 270.0));
+    /// assert!(near(turn_by(Angle::ZERO, (-270.0).deg()), -270.0));
+    /// ```
+    pub fn turn_by<
+        'a,
+        M: Arcade,
+        T: TracksForwardTravel + TracksHeading + TracksVelocity,
+    >(
+        &mut self,
+        drivetrain: &'a mut Drivetrain<M, T>,
+        delta: Angle,
+    ) -> DriveFuture<'a, M, L, A, T> {
+        let target_heading = (drivetrain.tracking.heading() + delta).wrapped_full();
+        let mut future = self.drive_distance_at_heading(drivetrain, 0.0, target_heading);
+        future.turn_by = Some(delta);
+        future
+    }
+
     /// Turns the robot in place to face a 2D point.
     pub fn turn_to_point<
         'a,
@@ -111,9 +362,17 @@ where
             timeout: self.timeout,
             linear_tolerances: self.linear_tolerances,
             angular_tolerances: self.angular_tolerances,
-            linear_controller: self.linear_controller.clone(),
-            angular_controller: self.angular_controller.clone(),
+            linear_controller: crate::fresh_controller(&self.linear_controller),
+            angular_controller: crate::fresh_controller(&self.angular_controller),
             drivetrain,
+            settle_condition: None,
+            active_brake: false,
+            min_angular_output: None,
+            pose_recorder: None,
+            tick: None,
+            error: ErrorSlot::new(),
+            outcome: None,
+            clock: VexideClock,
             state: None,
         }
     }
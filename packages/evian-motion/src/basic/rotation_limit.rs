@@ -0,0 +1,106 @@
+use core::f64::consts::TAU;
+
+use evian_math::Angle;
+
+/// Bounds on how far a turn may rotate the robot, measured in full turns.
+///
+/// Some robots can't spin freely, such as those with a tether or cable that would wind up
+/// around the robot. A rotation limit keeps a turn's total rotation within
+/// `[min_turns, max_turns]` (where positive turns are counterclockwise) by choosing which way to
+/// turn:
+///
+/// - A turn that already fits within the limit is left unchanged.
+/// - Otherwise, the turn is replaced by the smallest rotation within the limit that ends at the
+///   same heading, which means going the long way around.
+/// - If no such rotation exists (the limit spans less than a full turn), the turn is clamped to
+///   the limit, stopping short of its target.
+///
+/// This is used by [`DriveFuture::with_rotation_limit`](super::DriveFuture::with_rotation_limit).
+///
+/// # Examples
+///
+/// ```
+/// use evian_math::IntoAngle;
+/// use evian_motion::basic::RotationLimit;
+///
+/// // A robot that can make a full turn counterclockwise, but only a quarter turn clockwise.
+/// let limit = RotationLimit::new(-0.25, 1.0);
+///
+/// assert_eq!(limit.constrain(90.0.deg()), 90.0.deg());
+///
+/// // Turning 120° clockwise would pass the limit, so the robot turns 240° counterclockwise to
+/// // the same heading instead.
+/// assert!((limit.constrain((-120.0).deg()).as_degrees() - 240.0).abs() < 1e-9);
+///
+/// // A turn of 450° would pass a full turn, so the robot turns 90° instead.
+/// assert!((limit.constrain(450.0.deg()).as_degrees() - 90.0).abs() < 1e-9);
+///
+/// // With less than a full turn of freedom, there's no way to reach the target, so the turn is
+/// // clamped.
+/// let limit = RotationLimit::new(-0.25, 0.25);
+/// assert!((limit.constrain((-120.0).deg()).as_degrees() + 90.0).abs() < 1e-9);
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct RotationLimit {
+    min_turns: f64,
+    max_turns: f64,
+}
+
+impl RotationLimit {
+    /// Creates a new rotation limit allowing between `min_turns` and `max_turns` full turns.
+    ///
+    /// `min_turns` should be at most zero and `max_turns` at least zero, so that not turning at
+    /// all is always allowed.
+    #[must_use]
+    pub const fn new(min_turns: f64, max_turns: f64) -> Self {
+        Self {
+            min_turns,
+            max_turns,
+        }
+    }
+
+    /// Returns the furthest the robot may turn clockwise, as a (negative) number of turns.
+    #[must_use]
+    pub const fn min_turns(&self) -> f64 {
+        self.min_turns
+    }
+
+    /// Returns the furthest the robot may turn counterclockwise, as a number of turns.
+    #[must_use]
+    pub const fn max_turns(&self) -> f64 {
+        self.max_turns
+    }
+
+    /// Returns the rotation that the robot should turn by to complete a turn of `delta` without
+    /// passing this limit.
+    ///
+    /// See the [type-level documentation](RotationLimit) for how the rotation is chosen.
+    #[must_use]
+    pub fn constrain(&self, delta: Angle) -> Angle {
+        let (min, max) = (self.min_turns * TAU, self.max_turns * TAU);
+        let delta = delta.as_radians();
+
+        if (min..=max).contains(&delta) {
+            return Angle::from_radians(delta);
+        }
+
+        // The smallest non-negative and largest non-positive rotations within the limit that
+        // end at the same heading as `delta`, if there are any.
+        let positive = Some(delta + ((min.max(0.0) - delta) / TAU).ceil() * TAU)
+            .filter(|rotation| *rotation <= max);
+        let negative = Some(delta + ((max.min(0.0) - delta) / TAU).floor() * TAU)
+            .filter(|rotation| *rotation >= min);
+
+        Angle::from_radians(match (positive, negative) {
+            (Some(positive), Some(negative)) => {
+                if positive <= -negative {
+                    positive
+                } else {
+                    negative
+                }
+            }
+            (Some(rotation), None) | (None, Some(rotation)) => rotation,
+            (None, None) => delta.clamp(min, max),
+        })
+    }
+}
@@ -5,33 +5,44 @@ use std::{
     time::{Duration, Instant},
 };
 
-use vexide::time::{Sleep, sleep};
-
 use evian_control::{
     Tolerances,
     loops::{AngularPid, Feedback, Pid},
 };
 use evian_drivetrain::{Drivetrain, model::Arcade};
-use evian_math::{Angle, IntoAngle, Vec2};
+use evian_math::{Angle, Vec2};
 use evian_tracking::{TracksForwardTravel, TracksHeading, TracksPosition, TracksVelocity};
 
-pub(crate) struct State {
-    sleep: Sleep,
+use crate::brake::ActiveBrake;
+use crate::checked::{CheckedMotion, ErrorSlot};
+use crate::clock::{Clock, VexideClock};
+use crate::outcome::{MotionOutcome, SettlingMotion};
+use crate::telemetry::{
+    MotionTelemetry, PoseBuffer, PoseRecorder, PoseSample, SettleCondition, TickCallback,
+    is_settled,
+};
+
+pub(crate) struct State<S> {
+    sleep: S,
     initial_forward_travel: f64,
     start_time: Instant,
     prev_time: Instant,
     linear_settled: bool,
     angular_settled: bool,
+    brake: Option<ActiveBrake>,
+    progress: f64,
+    settling_progress: f64,
 }
 
 /// Turns the robot to face a point on the field.
 #[must_use = "futures do nothing unless you `.await` or poll them"]
-pub struct TurnToPointFuture<'a, M, L, A, T>
+pub struct TurnToPointFuture<'a, M, L, A, T, C = VexideClock>
 where
     M: Arcade,
     L: Feedback<State = f64, Signal = f64> + Unpin,
     A: Feedback<State = Angle, Signal = f64> + Unpin,
     T: TracksPosition + TracksHeading + TracksVelocity,
+    C: Clock,
 {
     pub(crate) point: Vec2<f64>,
     pub(crate) timeout: Option<Duration>,
@@ -40,33 +51,46 @@ where
     pub(crate) linear_controller: L,
     pub(crate) angular_controller: A,
     pub(crate) drivetrain: &'a mut Drivetrain<M, T>,
+    pub(crate) settle_condition: Option<SettleCondition<'a>>,
+    pub(crate) active_brake: bool,
+    pub(crate) min_angular_output: Option<f64>,
+    pub(crate) pose_recorder: Option<PoseRecorder<'a, T>>,
+    pub(crate) tick: Option<TickCallback<'a, T>>,
+    pub(crate) error: ErrorSlot<M::Error>,
+    pub(crate) outcome: Option<MotionOutcome>,
+
+    pub(crate) clock: C,
 
     /// Internal future state ("local variables").
-    pub(crate) state: Option<State>,
+    pub(crate) state: Option<State<C::Sleep>>,
 }
 
 // MARK: Future Poll
 
-impl<M, L, A, T> Future for TurnToPointFuture<'_, M, L, A, T>
+impl<M, L, A, T, C> Future for TurnToPointFuture<'_, M, L, A, T, C>
 where
     M: Arcade,
     L: Feedback<State = f64, Signal = f64> + Unpin,
     A: Feedback<State = Angle, Signal = f64> + Unpin,
     T: TracksForwardTravel + TracksHeading + TracksVelocity + TracksPosition,
+    C: Clock,
 {
     type Output = ();
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.get_mut();
         let state = this.state.get_or_insert_with(|| {
-            let now = Instant::now();
+            let now = this.clock.now();
             State {
-                sleep: sleep(Duration::from_millis(5)),
+                sleep: this.clock.sleep(Duration::from_millis(5)),
                 initial_forward_travel: this.drivetrain.tracking.forward_travel(),
                 start_time: now,
                 prev_time: now,
                 linear_settled: false,
                 angular_settled: false,
+                brake: None,
+                progress: 0.0,
+                settling_progress: 0.0,
             }
         });
 
@@ -74,76 +98,226 @@ where
             return Poll::Pending;
         }
 
-        let dt = state.prev_time.elapsed();
+        if let Some(recorder) = &mut this.pose_recorder {
+            recorder.record(&this.drivetrain.tracking, this.clock.now());
+        }
+
+        if let Some(tick) = &mut this.tick {
+            tick.call(&this.drivetrain.tracking, this.clock.now());
+        }
+
+        if crate::pause_if_disabled(&this.clock, &mut state.sleep, cx, || {
+            this.error.record(this.drivetrain.model.drive_arcade(0.0, 0.0));
+        }) {
+            state.prev_time = this.clock.now();
+            return Poll::Pending;
+        }
+
+        let dt = this.clock.elapsed(state.prev_time);
 
         let forward_travel = this.drivetrain.tracking.forward_travel();
-        let position = this.drivetrain.tracking.position();
         let heading = this.drivetrain.tracking.heading();
-        let target_heading = (this.point - position).angle().rad();
+        let target_heading = this.drivetrain.heading_to(this.point);
 
         let linear_error = state.initial_forward_travel - forward_travel;
         let angular_error = (heading - target_heading).wrapped_half();
 
-        if this
-            .linear_tolerances
-            .check(linear_error, this.drivetrain.tracking.linear_velocity())
-        {
+        let linear_velocity = this.drivetrain.tracking.linear_velocity();
+        let angular_velocity = this.drivetrain.tracking.angular_velocity();
+
+        let now = this.clock.now();
+        let linear_check = this.linear_tolerances.check(linear_error, linear_velocity, now);
+        let angular_check = this
+            .angular_tolerances
+            .check(angular_error.as_radians(), angular_velocity, now);
+
+        if linear_check.is_settled() {
             state.linear_settled = true;
         }
-        if this.angular_tolerances.check(
-            angular_error.as_radians(),
-            this.drivetrain.tracking.angular_velocity(),
-        ) {
+        if angular_check.is_settled() {
             state.angular_settled = true;
         }
 
-        if (state.linear_settled && state.angular_settled)
-            || this
-                .timeout
-                .is_some_and(|timeout| state.start_time.elapsed() > timeout)
-        {
-            drop(this.drivetrain.model.drive_arcade(0.0, 0.0));
+        state.progress = this.angular_tolerances.progress(angular_error.as_radians());
+        state.settling_progress = f64::min(
+            if state.linear_settled {
+                1.0
+            } else {
+                this.linear_tolerances.settling_progress(&linear_check)
+            },
+            if state.angular_settled {
+                1.0
+            } else {
+                this.angular_tolerances.settling_progress(&angular_check)
+            },
+        );
+
+        // Once we've started braking, we've already settled.
+        let settled = state.brake.is_some()
+            || is_settled(
+                this.settle_condition.as_mut(),
+                state.linear_settled && state.angular_settled,
+                || MotionTelemetry {
+                    elapsed: this.clock.elapsed(state.start_time),
+                    linear_error,
+                    angular_error: Some(angular_error),
+                    linear_velocity,
+                    angular_velocity,
+                    linear_tolerances: Some(linear_check),
+                    angular_tolerances: Some(angular_check),
+                },
+            );
+
+        if settled && this.active_brake {
+            let brake = state
+                .brake
+                .get_or_insert_with(|| ActiveBrake::new(this.clock.now()));
+
+            if let Some((throttle, steer)) = brake.update(
+                this.clock.now(),
+                linear_velocity,
+                angular_velocity,
+                this.linear_tolerances.velocity_tolerance,
+                this.angular_tolerances.velocity_tolerance,
+            ) {
+                this.error.record(this.drivetrain.model.drive_arcade(throttle, steer));
+
+                state.sleep = this.clock.sleep(Duration::from_millis(5));
+                state.prev_time = this.clock.now();
+
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+        }
+
+        let timed_out = this
+            .timeout
+            .is_some_and(|timeout| this.clock.elapsed(state.start_time) > timeout);
+
+        if settled || timed_out {
+            this.outcome = Some(if settled {
+                MotionOutcome::Settled
+            } else {
+                MotionOutcome::TimedOut
+            });
+            this.error.record(this.drivetrain.model.drive_arcade(0.0, 0.0));
             return Poll::Ready(());
         }
 
         let linear_output =
             this.linear_controller
                 .update(forward_travel, state.initial_forward_travel, dt);
-        let angular_output = this
+        let mut angular_output = this
             .angular_controller
             .update(-angular_error, Angle::ZERO, dt);
 
-        drop(
+        angular_output = crate::apply_min_output(
+            angular_output,
+            this.min_angular_output,
+            angular_check.error_satisfied,
+        );
+
+        this.error.record(
             this.drivetrain
                 .model
                 .drive_arcade(linear_output, angular_output),
         );
 
-        state.sleep = sleep(Duration::from_millis(5));
-        state.prev_time = Instant::now();
+        state.sleep = this.clock.sleep(Duration::from_millis(5));
+        state.prev_time = this.clock.now();
 
         cx.waker().wake_by_ref();
         Poll::Pending
     }
 }
 
+impl<M, L, A, T, C> CheckedMotion for TurnToPointFuture<'_, M, L, A, T, C>
+where
+    M: Arcade,
+    L: Feedback<State = f64, Signal = f64> + Unpin,
+    A: Feedback<State = Angle, Signal = f64> + Unpin,
+    T: TracksForwardTravel + TracksHeading + TracksVelocity + TracksPosition,
+    C: Clock,
+{
+    type Error = M::Error;
+
+    fn take_error(&mut self) -> Option<M::Error> {
+        self.error.take()
+    }
+}
+
+impl<M, L, A, T, C> SettlingMotion for TurnToPointFuture<'_, M, L, A, T, C>
+where
+    M: Arcade,
+    L: Feedback<State = f64, Signal = f64> + Unpin,
+    A: Feedback<State = Angle, Signal = f64> + Unpin,
+    T: TracksForwardTravel + TracksHeading + TracksVelocity + TracksPosition,
+    C: Clock,
+{
+    fn outcome(&self) -> Option<MotionOutcome> {
+        self.outcome
+    }
+}
+
+// MARK: Progress
+
+impl<M, L, A, T, C> TurnToPointFuture<'_, M, L, A, T, C>
+where
+    M: Arcade,
+    L: Feedback<State = f64, Signal = f64> + Unpin,
+    A: Feedback<State = Angle, Signal = f64> + Unpin,
+    T: TracksPosition + TracksForwardTravel + TracksHeading + TracksVelocity,
+    C: Clock,
+{
+    /// Estimates how close this motion is to reaching its target, from `0.0` to `1.0`.
+    ///
+    /// This is the fraction of the initial angle to the point that has been turned through as of
+    /// the motion's most recent update (see [`Tolerances::progress`]). Before the motion is first
+    /// polled, this returns `0.0`.
+    ///
+    /// Like [`DriveFuture::progress`](crate::basic::DriveFuture::progress), this isn't
+    /// monotonic and moves backwards whenever the error grows.
+    #[must_use]
+    pub fn progress(&self) -> f64 {
+        self.state.as_ref().map_or(0.0, |state| state.progress)
+    }
+
+    /// Returns how much of its tolerance duration this motion has spent in tolerance, from `0.0`
+    /// to `1.0`.
+    ///
+    /// This is taken from whichever of the linear and angular settling timers is further from
+    /// finishing as of the motion's most recent update (see [`Tolerances::settling_progress`]).
+    /// It drops back to `0.0` whenever the robot leaves tolerance.
+    #[must_use]
+    pub fn settling_progress(&self) -> f64 {
+        self.state.as_ref().map_or(0.0, |state| state.settling_progress)
+    }
+}
+
 // MARK: Generic Modifiers
 
-impl<M, L, A, T> TurnToPointFuture<'_, M, L, A, T>
+impl<'a, M, L, A, T, C> TurnToPointFuture<'a, M, L, A, T, C>
 where
     M: Arcade,
     L: Feedback<State = f64, Signal = f64> + Unpin,
     A: Feedback<State = Angle, Signal = f64> + Unpin,
     T: TracksPosition + TracksForwardTravel + TracksHeading + TracksVelocity,
+    C: Clock,
 {
     /// Modifies this motion's linear feedback controller.
-    pub fn with_linear_controller(&mut self, controller: L) -> &mut Self {
+    ///
+    /// The controller is [reset](Feedback::reset) before it is used.
+    pub fn with_linear_controller(&mut self, mut controller: L) -> &mut Self {
+        controller.reset();
         self.linear_controller = controller;
         self
     }
 
     /// Modifies this motion's angular feedback controller.
-    pub fn with_angular_controller(&mut self, controller: A) -> &mut Self {
+    ///
+    /// The controller is [reset](Feedback::reset) before it is used.
+    pub fn with_angular_controller(&mut self, mut controller: A) -> &mut Self {
+        controller.reset();
         self.angular_controller = controller;
         self
     }
@@ -160,6 +334,115 @@ where
         self
     }
 
+    /// Replaces the [`Clock`] used by this motion to keep time.
+    ///
+    /// See [`DriveFuture::with_clock`] for more information.
+    ///
+    /// [`DriveFuture::with_clock`]: crate::basic::DriveFuture::with_clock
+    pub fn with_clock<C2: Clock>(self, clock: C2) -> TurnToPointFuture<'a, M, L, A, T, C2> {
+        TurnToPointFuture {
+            point: self.point,
+            timeout: self.timeout,
+            linear_tolerances: self.linear_tolerances,
+            angular_tolerances: self.angular_tolerances,
+            linear_controller: self.linear_controller,
+            angular_controller: self.angular_controller,
+            drivetrain: self.drivetrain,
+            settle_condition: self.settle_condition,
+            active_brake: self.active_brake,
+            min_angular_output: self.min_angular_output,
+            pose_recorder: self.pose_recorder,
+            tick: self.tick,
+            error: ErrorSlot::new(),
+            outcome: None,
+            clock,
+            state: None,
+        }
+    }
+
+    /// Records the robot's pose into a [`PoseBuffer`] on every update of this motion.
+    pub fn record_into(&mut self, buffer: &'a mut PoseBuffer) -> &mut Self {
+        self.pose_recorder = Some(PoseRecorder::new(buffer));
+        self
+    }
+
+    /// Calls `callback` with the robot's pose on every update of this motion.
+    ///
+    /// The callback must return quickly and must not block. See [`DriveFuture::with_tick`] for more
+    /// information.
+    ///
+    /// [`DriveFuture::with_tick`]: crate::basic::DriveFuture::with_tick
+    pub fn with_tick(&mut self, callback: impl FnMut(&PoseSample) + 'a) -> &mut Self {
+        self.tick = Some(TickCallback::new(callback));
+        self
+    }
+
+    /// Removes this motion's tick callback.
+    pub fn without_tick(&mut self) -> &mut Self {
+        self.tick = None;
+        self
+    }
+
+    /// Adds a custom settling condition to this motion.
+    ///
+    /// See [`DriveFuture::with_settle_when`] for more information.
+    ///
+    /// [`DriveFuture::with_settle_when`]: crate::basic::DriveFuture::with_settle_when
+    pub fn with_settle_when(
+        &mut self,
+        predicate: impl FnMut(&MotionTelemetry) -> bool + 'a,
+    ) -> &mut Self {
+        self.settle_condition = Some(SettleCondition::new(predicate, false));
+        self
+    }
+
+    /// Replaces this motion's tolerances with a custom settling condition.
+    ///
+    /// See [`DriveFuture::with_settle_only_when`] for more information.
+    ///
+    /// [`DriveFuture::with_settle_only_when`]: crate::basic::DriveFuture::with_settle_only_when
+    pub fn with_settle_only_when(
+        &mut self,
+        predicate: impl FnMut(&MotionTelemetry) -> bool + 'a,
+    ) -> &mut Self {
+        self.settle_condition = Some(SettleCondition::new(predicate, true));
+        self
+    }
+
+    /// Removes this motion's custom settling condition.
+    pub fn without_settle_condition(&mut self) -> &mut Self {
+        self.settle_condition = None;
+        self
+    }
+
+    /// Enables or disables active braking once this motion settles.
+    ///
+    /// See [`DriveFuture::with_active_brake`] for more information.
+    ///
+    /// [`DriveFuture::with_active_brake`]: crate::basic::DriveFuture::with_active_brake
+    pub const fn with_active_brake(&mut self, enabled: bool) -> &mut Self {
+        self.active_brake = enabled;
+        self
+    }
+
+    /// Modifies this motion's minimum angular output.
+    ///
+    /// While the robot's heading is outside of this motion's angular error tolerance, the angular
+    /// output will never drop below this magnitude, allowing small heading errors to overcome
+    /// static friction. See [`DriveFuture::with_min_angular_output`] for more information.
+    ///
+    /// [`DriveFuture::with_min_angular_output`]: super::DriveFuture::with_min_angular_output
+    pub const fn with_min_angular_output(&mut self, output: f64) -> &mut Self {
+        self.min_angular_output = Some(output);
+        self
+    }
+
+    /// Removes this motion's minimum angular output.
+    pub const fn without_min_angular_output(&mut self) -> &mut Self {
+        self.min_angular_output = None;
+        self
+    }
+
     /// Modifies this motion's linear tolerances.
     pub const fn with_linear_tolerances(&mut self, tolerances: Tolerances) -> &mut Self {
         self.linear_tolerances = tolerances;
@@ -250,15 +533,26 @@ where
         self.angular_tolerances.duration = None;
         self
     }
+
+    /// Modifies how many consecutive out-of-tolerance checks this motion's linear and angular
+    /// tolerances allow before their settling timers reset.
+    ///
+    /// See [`Tolerances::debounce`] for more information.
+    pub const fn with_settle_debounce(&mut self, debounce: u32) -> &mut Self {
+        self.linear_tolerances.debounce = debounce;
+        self.angular_tolerances.debounce = debounce;
+        self
+    }
 }
 
 // MARK: Linear PID Modifiers
 
-impl<M, A, T> TurnToPointFuture<'_, M, Pid, A, T>
+impl<M, A, T, C> TurnToPointFuture<'_, M, Pid, A, T, C>
 where
     M: Arcade,
     A: Feedback<State = Angle, Signal = f64> + Unpin,
     T: TracksPosition + TracksForwardTravel + TracksHeading + TracksVelocity,
+    C: Clock,
 {
     /// Modifies this motion's linear PID gains.
     pub const fn with_linear_gains(&mut self, kp: f64, ki: f64, kd: f64) -> &mut Self {
@@ -312,11 +606,12 @@ where
 
 // MARK: Angular PID Modifiers
 
-impl<M, L, T> TurnToPointFuture<'_, M, L, AngularPid, T>
+impl<M, L, T, C> TurnToPointFuture<'_, M, L, AngularPid, T, C>
 where
     M: Arcade,
     L: Feedback<State = f64, Signal = f64> + Unpin,
     T: TracksPosition + TracksForwardTravel + TracksHeading + TracksVelocity,
+    C: Clock,
 {
     /// Modifies this motion's angular PID gains.
     pub const fn with_angular_gains(&mut self, kp: f64, ki: f64, kd: f64) -> &mut Self {
@@ -0,0 +1,58 @@
+//! Active braking at the end of motions.
+
+use std::time::{Duration, Instant};
+
+/// Arcade throttle applied per unit of residual linear velocity (in wheel units per second).
+const LINEAR_GAIN: f64 = 0.05;
+
+/// Arcade steer applied per unit of residual angular velocity (in radians per second).
+const ANGULAR_GAIN: f64 = 0.5;
+
+/// Linear velocity at which the robot is considered stopped if the motion has no linear
+/// velocity tolerance.
+const DEFAULT_LINEAR_STOP_VELOCITY: f64 = 1.0;
+
+/// Angular velocity at which the robot is considered stopped if the motion has no angular
+/// velocity tolerance.
+const DEFAULT_ANGULAR_STOP_VELOCITY: f64 = 0.1;
+
+/// Maximum time spent braking before giving up, in case the robot never fully stops.
+const MAX_DURATION: Duration = Duration::from_millis(250);
+
+/// State of a motion's active braking phase.
+pub(crate) struct ActiveBrake {
+    start: Instant,
+}
+
+impl ActiveBrake {
+    pub fn new(now: Instant) -> Self {
+        Self { start: now }
+    }
+
+    /// Computes the arcade outputs needed to counter the robot's residual velocity, returning
+    /// `None` once the robot has stopped (or braking has taken too long).
+    pub fn update(
+        &self,
+        now: Instant,
+        linear_velocity: f64,
+        angular_velocity: f64,
+        linear_stop_velocity: Option<f64>,
+        angular_stop_velocity: Option<f64>,
+    ) -> Option<(f64, f64)> {
+        let stopped = linear_velocity.abs()
+            < linear_stop_velocity.unwrap_or(DEFAULT_LINEAR_STOP_VELOCITY)
+            && angular_velocity.abs()
+                < angular_stop_velocity.unwrap_or(DEFAULT_ANGULAR_STOP_VELOCITY);
+
+        if stopped || now.saturating_duration_since(self.start) > MAX_DURATION {
+            return None;
+        }
+
+        // Counterclockwise-positive angular velocity is countered by steering clockwise, which
+        // is positive steer in arcade drive.
+        Some((
+            (-linear_velocity * LINEAR_GAIN).clamp(-1.0, 1.0),
+            (angular_velocity * ANGULAR_GAIN).clamp(-1.0, 1.0),
+        ))
+    }
+}
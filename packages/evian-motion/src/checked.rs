@@ -0,0 +1,148 @@
+//! Observing drivetrain errors from motions.
+//!
+//! Every drivetrain model reports failures (such as a disconnected motor) through its
+//! [`DrivetrainModel::Error`] type. Awaiting a motion directly ignores these errors, so that a
+//! single faulty motor doesn't abort an entire autonomous routine. Every motion that commands a
+//! drivetrain model keeps the first error it encounters, though, which can be observed by awaiting
+//! the motion through [`CheckedMotion::await_checked`] instead.
+//!
+//! Custom motions can support this by implementing [`CheckedMotion`] themselves.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::{
+//!     pin::pin,
+//!     task::{Context, Poll, Waker},
+//! };
+//! use evian_motion::CheckedMotion;
+//!
+//! /// A motion that completes immediately, after its drivetrain may have returned an error.
+//! struct Instant(Option<&'static str>);
+//!
+//! impl Future for Instant {
+//!     type Output = ();
+//!
+//!     fn poll(self: std::pin::Pin<&mut Self>, _: &mut Context<'_>) -> Poll<()> {
+//!         Poll::Ready(())
+//!     }
+//! }
+//!
+//! impl CheckedMotion for Instant {
+//!     type Error = &'static str;
+//!
+//!     fn take_error(&mut self) -> Option<Self::Error> {
+//!         self.0.take()
+//!     }
+//! }
+//!
+//! let mut cx = Context::from_waker(Waker::noop());
+//!
+//! let healthy = pin!(Instant(None).await_checked());
+//! assert_eq!(healthy.poll(&mut cx), Poll::Ready(Ok(())));
+//!
+//! let faulty = pin!(Instant(Some("motor disconnected")).await_checked());
+//! assert_eq!(faulty.poll(&mut cx), Poll::Ready(Err("motor disconnected")));
+//! ```
+//!
+//! [`DrivetrainModel::Error`]: evian_drivetrain::model::DrivetrainModel::Error
+
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// A motion that records errors returned by its drivetrain model.
+///
+/// See the [module-level documentation](self) for more information.
+pub trait CheckedMotion: Future + Sized {
+    /// The error type returned by the motion's drivetrain model.
+    type Error;
+
+    /// Takes the first error returned by the drivetrain model since this was last called, if
+    /// any.
+    fn take_error(&mut self) -> Option<Self::Error>;
+
+    /// Returns a future that runs this motion, resolving to `Err` with the first error returned
+    /// by the drivetrain model if one occurred.
+    ///
+    /// Errors don't end the motion early. Since the rest of the drivetrain may still work (such
+    /// as when only one of several motors on each side is disconnected), the motion keeps
+    /// commanding the drivetrain until it settles or times out as usual, and the error is
+    /// reported once it completes. Pair this with a timeout for motions that may not be able to
+    /// settle on a faulty drivetrain.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use evian::motion::checked::CheckedMotion;
+    ///
+    /// if let Err(error) = basic.drive_distance(&mut drivetrain, 24.0).await_checked().await {
+    ///     println!("Drivetrain error during drive: {error}");
+    /// }
+    /// ```
+    fn await_checked(self) -> CheckedFuture<Self> {
+        CheckedFuture { motion: self }
+    }
+}
+
+/// Storage for the first error returned by a motion's drivetrain model.
+pub(crate) struct ErrorSlot<E>(Option<E>);
+
+// The error is never pinned, so motions storing one can stay `Unpin` regardless of its type.
+impl<E> Unpin for ErrorSlot<E> {}
+
+impl<E> ErrorSlot<E> {
+    pub(crate) const fn new() -> Self {
+        Self(None)
+    }
+
+    /// Keeps the error from `result`, unless an earlier error is already being kept.
+    pub(crate) fn record(&mut self, result: Result<(), E>) {
+        if let Err(error) = result
+            && self.0.is_none()
+        {
+            self.0 = Some(error);
+        }
+    }
+
+    pub(crate) const fn take(&mut self) -> Option<E> {
+        self.0.take()
+    }
+}
+
+/// Runs a motion, resolving to the first error returned by its drivetrain model.
+///
+/// This is created by [`CheckedMotion::await_checked`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct CheckedFuture<F> {
+    motion: F,
+}
+
+impl<F> CheckedFuture<F> {
+    /// Returns a reference to the motion being run.
+    pub const fn motion(&self) -> &F {
+        &self.motion
+    }
+
+    /// Returns a mutable reference to the motion being run.
+    pub const fn motion_mut(&mut self) -> &mut F {
+        &mut self.motion
+    }
+}
+
+impl<F: CheckedMotion + Unpin> Future for CheckedFuture<F> {
+    type Output = Result<F::Output, F::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        Pin::new(&mut this.motion)
+            .poll(cx)
+            .map(|output| match this.motion.take_error() {
+                Some(error) => Err(error),
+                None => Ok(output),
+            })
+    }
+}
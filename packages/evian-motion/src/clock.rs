@@ -0,0 +1,256 @@
+//! Time sources for motion algorithms.
+//!
+//! Motions need to know the current time (for timeouts, settling durations, and computing
+//! `dt` for their controllers) and need to wait between updates. By default, motions use
+//! [`VexideClock`], which reads the brain's clock and sleeps using vexide's async runtime.
+//!
+//! Any motion's time source can be replaced using its `with_clock` modifier, allowing time to be
+//! driven deterministically (such as by a [`ManualClock`]) for simulation and testing.
+//!
+//! Clocks also report whether the robot is disabled by competition control, since motions pause
+//! while disabled. Like the brain's clock, the competition state is only available on a real
+//! brain, so this lets motions run on a host machine.
+
+extern crate alloc;
+
+use alloc::rc::Rc;
+use core::{
+    cell::Cell,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use std::time::{Duration, Instant};
+
+use vexide::{
+    competition::{self, CompetitionMode},
+    time::{Sleep, sleep},
+};
+
+/// A source of time for motion algorithms.
+pub trait Clock: Unpin {
+    /// The future returned by [`Clock::sleep`].
+    type Sleep: Future<Output = ()> + Unpin;
+
+    /// Returns the current time according to this clock.
+    fn now(&self) -> Instant;
+
+    /// Returns a future that completes once `duration` has passed according to this clock.
+    fn sleep(&self, duration: Duration) -> Self::Sleep;
+
+    /// Returns `true` if the robot is currently disabled by competition control.
+    fn is_disabled(&self) -> bool;
+
+    /// Returns the amount of time that has passed since `earlier` according to this clock.
+    ///
+    /// This returns [`Duration::ZERO`] if `earlier` is in the future.
+    fn elapsed(&self, earlier: Instant) -> Duration {
+        self.now().saturating_duration_since(earlier)
+    }
+}
+
+/// The brain's clock, using vexide's async runtime to sleep.
+///
+/// This is the default clock used by all motions.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VexideClock;
+
+impl Clock for VexideClock {
+    type Sleep = Sleep;
+
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) -> Self::Sleep {
+        sleep(duration)
+    }
+
+    fn is_disabled(&self) -> bool {
+        competition::mode() == CompetitionMode::Disabled
+    }
+}
+
+/// A clock that only moves forward when told to.
+///
+/// Cloning a `ManualClock` returns a handle to the same clock, so a clone can be given to a
+/// motion while the original is used to advance time.
+///
+/// Sleeps created by this clock complete once the clock has been advanced past their deadline.
+/// Until then, they will immediately ask to be polled again, so the clock should be advanced
+/// from the same task that is polling the motion (or from code interleaved with it).
+///
+/// The robot is reported as enabled until [`ManualClock::set_disabled`] is called.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use evian_motion::clock::{Clock, ManualClock};
+///
+/// let clock = ManualClock::new();
+/// let start = clock.now();
+///
+/// clock.advance(Duration::from_millis(50));
+///
+/// assert_eq!(clock.elapsed(start), Duration::from_millis(50));
+/// ```
+///
+/// Motions measure how long they have been within their tolerances using their clock, so a
+/// motion driven by a manual clock settles once enough simulated time has passed.
+///
+/// ```
+/// use std::{pin::pin, time::Duration};
+///
+/// use evian_control::{Tolerances, loops::{AngularPid, Pid}};
+/// use evian_drivetrain::Drivetrain;
+/// use evian_motion::{
+///     Basic,
+///     clock::ManualClock,
+///     sim::{self, SimRobot},
+/// };
+///
+/// let tolerances = Tolerances::new().error(0.5).duration(Duration::from_millis(100));
+/// let robot = SimRobot::new();
+/// let mut drivetrain = Drivetrain::new(robot.clone(), robot);
+/// let mut basic = Basic {
+///     linear_controller: Pid::new(1.0, 0.0, 0.0, None),
+///     angular_controller: AngularPid::new(1.0, 0.0, 0.0, None),
+///     linear_tolerances: tolerances,
+///     angular_tolerances: tolerances,
+///     timeout: None,
+/// };
+///
+/// let clock = ManualClock::new();
+/// let mut motion = pin!(basic.drive_distance(&mut drivetrain, 0.0).with_clock(clock.clone()));
+///
+/// // The robot is already within tolerance, so the motion settles once it has stayed there for
+/// // 100ms of simulated time, no matter how little real time has passed.
+/// let mut updates = 0;
+/// while sim::step(motion.as_mut(), &clock).is_pending() {
+///     assert!(updates < 25, "motion didn't settle in simulated time");
+///     updates += 1;
+/// }
+///
+/// assert!(updates > 20);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManualClock {
+    now: Rc<Cell<Instant>>,
+    disabled: Rc<Cell<bool>>,
+}
+
+impl ManualClock {
+    /// Creates a new manual clock starting at the current time.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            now: Rc::new(Cell::new(Instant::now())),
+            disabled: Rc::new(Cell::new(false)),
+        }
+    }
+
+    /// Moves the clock forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+
+    /// Sets whether the robot should be reported as disabled by competition control.
+    ///
+    /// # Examples
+    ///
+    /// Motions stop commanding the drivetrain while the robot is disabled.
+    ///
+    /// ```
+    /// use std::{pin::pin, time::Duration};
+    ///
+    /// use evian_control::{Tolerances, loops::{AngularPid, Pid}};
+    /// use evian_drivetrain::Drivetrain;
+    /// use evian_motion::{
+    ///     Basic,
+    ///     clock::ManualClock,
+    ///     sim::{self, SimRobot},
+    /// };
+    ///
+    /// let robot = SimRobot::new();
+    /// let mut drivetrain = Drivetrain::new(robot.clone(), robot.clone());
+    /// let mut basic = Basic {
+    ///     linear_controller: Pid::new(1.0, 0.0, 0.0, None),
+    ///     angular_controller: AngularPid::new(1.0, 0.0, 0.0, None),
+    ///     linear_tolerances: Tolerances::new(),
+    ///     angular_tolerances: Tolerances::new(),
+    ///     timeout: None,
+    /// };
+    ///
+    /// let clock = ManualClock::new();
+    /// let mut motion = pin!(
+    ///     basic
+    ///         .drive_for(&mut drivetrain, 0.5, 0.0, Duration::from_secs(1))
+    ///         .with_clock(clock.clone())
+    /// );
+    ///
+    /// // The first step starts the motion, which first updates once its clock advances.
+    /// _ = sim::step(motion.as_mut(), &clock);
+    /// let mut update = || {
+    ///     _ = sim::step(motion.as_mut(), &clock);
+    ///     robot.last().unwrap().throttle
+    /// };
+    ///
+    /// assert_eq!(update(), 0.5);
+    ///
+    /// clock.set_disabled(true);
+    /// assert_eq!(update(), 0.0);
+    ///
+    /// clock.set_disabled(false);
+    /// assert_eq!(update(), 0.5);
+    /// ```
+    pub fn set_disabled(&self, disabled: bool) {
+        self.disabled.set(disabled);
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    type Sleep = ManualSleep;
+
+    fn now(&self) -> Instant {
+        self.now.get()
+    }
+
+    fn sleep(&self, duration: Duration) -> Self::Sleep {
+        ManualSleep {
+            deadline: self.now.get() + duration,
+            now: self.now.clone(),
+        }
+    }
+
+    fn is_disabled(&self) -> bool {
+        self.disabled.get()
+    }
+}
+
+/// Future returned by [`ManualClock::sleep`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ManualSleep {
+    deadline: Instant,
+    now: Rc<Cell<Instant>>,
+}
+
+impl Future for ManualSleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.now.get() >= self.deadline {
+            Poll::Ready(())
+        } else {
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
@@ -1,12 +1,122 @@
 //! Motion control algorithms.
+//!
+//! All motions in this crate will stop commanding the drivetrain while the robot is disabled by
+//! competition control (as reported by their [`Clock`]), resuming where they left off once the
+//! robot is re-enabled. Motion timeouts will continue to elapse while disabled.
 
+mod brake;
 mod curvature;
+mod telemetry;
 
 pub mod basic;
+pub mod checked;
+pub mod clock;
+pub mod outcome;
+pub mod path;
 pub mod pursuit;
+pub mod replay;
 pub mod seeking;
+pub mod sequence;
+
+#[doc(hidden)]
+pub mod sim;
 
 pub use basic::Basic;
+pub use checked::CheckedMotion;
+pub use clock::Clock;
 pub use curvature::CurvatureDrive;
+pub use outcome::SettlingMotion;
+pub use path::Path;
 pub use pursuit::PurePursuit;
+pub use replay::InputRecorder;
 pub use seeking::Seeking;
+pub use sequence::Sequence;
+pub use telemetry::{MotionTelemetry, PoseBuffer, PoseSample};
+
+use core::task::Context;
+use std::time::Duration;
+
+use evian_control::loops::Feedback;
+use evian_drivetrain::model::{DesaturationPolicy, desaturate_holonomic};
+
+/// Returns a freshly reset copy of a stored controller, so that motions never inherit state left
+/// over from a previous motion.
+pub(crate) fn fresh_controller<C: Feedback + Clone>(controller: &C) -> C {
+    let mut controller = controller.clone();
+    controller.reset();
+    controller
+}
+
+/// Pauses a motion for one update if its clock reports that the robot is disabled, returning
+/// `true` if it did.
+///
+/// While disabled, the drivetrain is stopped with `stop` rather than being commanded by the
+/// motion, since any output we leave the motors at would be applied as soon as the robot is
+/// re-enabled. The motion's next update is still scheduled on `sleep`, so it picks up where it
+/// left off once re-enabled.
+pub(crate) fn pause_if_disabled<C: Clock>(
+    clock: &C,
+    sleep: &mut C::Sleep,
+    cx: &mut Context<'_>,
+    stop: impl FnOnce(),
+) -> bool {
+    if !clock.is_disabled() {
+        return false;
+    }
+
+    stop();
+    *sleep = clock.sleep(Duration::from_millis(5));
+    cx.waker().wake_by_ref();
+
+    true
+}
+
+/// Limits a turning output so that the robot's measured turn rate settles at no more than
+/// `max_rate`.
+///
+/// `rate` is the measured turn rate in the direction that a positive output turns the robot, and
+/// `prev_output` is the output applied during the previous update. While the robot is turning in
+/// the direction of `output`, its magnitude is capped at `prev_output * max_rate / rate`, which is
+/// the output that would hold the robot at `max_rate` if its turn rate is proportional to output.
+///
+/// Outputs are normalized powers, so any part of `prev_output` beyond full power was never applied
+/// to the drivetrain and isn't counted.
+pub(crate) fn limit_turn_rate(output: f64, prev_output: f64, rate: f64, max_rate: f64) -> f64 {
+    // Nothing to scale from, or the robot isn't turning the way we're pushing it.
+    if prev_output == 0.0 || rate * output <= 0.0 {
+        return output;
+    }
+
+    let cap = prev_output.abs().min(1.0) * max_rate / rate.abs();
+    output.clamp(-cap, cap)
+}
+
+/// Scales down a linear output to make room for an angular output, so that their arcade mix
+/// fits within full power without reducing the angular output.
+///
+/// The angular output is only scaled down if it alone exceeds full power.
+pub(crate) fn prioritize_angular(linear: f64, angular: f64) -> (f64, f64) {
+    let [left, right] = desaturate_holonomic(
+        [linear, linear],
+        [angular, -angular],
+        1.0,
+        DesaturationPolicy::PreserveRotation,
+    );
+
+    ((left + right) / 2.0, (left - right) / 2.0)
+}
+
+/// Raises the magnitude of a controller's output to at least `min_output` while the system is
+/// outside of its error tolerance.
+pub(crate) fn apply_min_output(
+    output: f64,
+    min_output: Option<f64>,
+    within_tolerance: bool,
+) -> f64 {
+    match min_output {
+        Some(min_output) if !within_tolerance && output != 0.0 && output.abs() < min_output => {
+            min_output.copysign(output)
+        }
+        _ => output,
+    }
+}
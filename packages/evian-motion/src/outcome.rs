@@ -0,0 +1,73 @@
+//! Observing how motions finish.
+//!
+//! A motion that drives towards a target finishes in one of two ways: either it settles within
+//! its tolerances, or it runs out of time. Awaiting the motion doesn't say which happened, so
+//! these motions record a [`MotionOutcome`] once they complete, which can be read through
+//! [`SettlingMotion::outcome`].
+//!
+//! Custom motions can support this by implementing [`SettlingMotion`] themselves.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::{pin::Pin, time::Duration};
+//!
+//! use evian_control::{Tolerances, loops::{AngularPid, Pid}};
+//! use evian_drivetrain::Drivetrain;
+//! use evian_motion::{
+//!     Basic,
+//!     clock::ManualClock,
+//!     outcome::{MotionOutcome, SettlingMotion},
+//!     sim::{self, SimRobot},
+//! };
+//!
+//! let robot = SimRobot::stuck();
+//! let mut drivetrain = Drivetrain::new(robot.clone(), robot);
+//! let mut basic = Basic {
+//!     linear_controller: Pid::new(1.0, 0.0, 0.0, None),
+//!     angular_controller: AngularPid::new(1.0, 0.0, 0.0, None),
+//!     linear_tolerances: Tolerances::new().error(0.5),
+//!     angular_tolerances: Tolerances::new().error(0.05),
+//!     timeout: Some(Duration::from_millis(100)),
+//! };
+//!
+//! let mut run = |distance| {
+//!     let clock = ManualClock::new();
+//!     let mut motion = basic.drive_distance(&mut drivetrain, distance).with_clock(clock.clone());
+//!
+//!     while sim::step(Pin::new(&mut motion), &clock).is_pending() {
+//!         assert_eq!(motion.outcome(), None);
+//!     }
+//!
+//!     motion.outcome()
+//! };
+//!
+//! // The robot is already at a target 0 inches away...
+//! assert_eq!(run(0.0), Some(MotionOutcome::Settled));
+//!
+//! // ...but will never reach one 24 inches away.
+//! assert_eq!(run(24.0), Some(MotionOutcome::TimedOut));
+//! ```
+
+use core::future::Future;
+
+/// How a motion finished running.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum MotionOutcome {
+    /// The motion settled at its target.
+    Settled,
+
+    /// The motion ran for longer than its timeout before it could settle.
+    TimedOut,
+}
+
+/// A motion that records how it finished.
+///
+/// See the [module-level documentation](self) for more information.
+pub trait SettlingMotion: Future {
+    /// Returns how the motion finished, or `None` if it hasn't finished yet.
+    ///
+    /// If a motion settles on the same update that its timeout elapses, it is considered to
+    /// have settled.
+    fn outcome(&self) -> Option<MotionOutcome>;
+}
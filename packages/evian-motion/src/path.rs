@@ -0,0 +1,211 @@
+//! Paths made of straight line segments.
+//!
+//! A [`Path`] is a sequence of points joined by straight lines, along with the distance along
+//! the path to each point. These distances are computed once when the path is created, so that
+//! questions like "how long is this path" or "where is the robot along this path" don't need to
+//! walk every segment from the start each time they're asked.
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use evian_math::Vec2;
+
+/// A point along a [`Path`].
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct PathPoint {
+    /// Position of the point.
+    pub position: Vec2<f64>,
+
+    /// Distance along the path from its first point to this point.
+    pub distance: f64,
+
+    /// Index of the segment containing this point, where segment `n` runs from point `n` to
+    /// point `n + 1`.
+    ///
+    /// This is always `0` for paths with fewer than two points.
+    pub segment: usize,
+}
+
+/// A sequence of points joined by straight line segments.
+///
+/// Paths can be created from any iterator of points (using [`collect`](Iterator::collect)), or
+/// from a slice or [`Vec`] of points.
+///
+/// # Examples
+///
+/// ```
+/// use evian_math::Vec2;
+/// use evian_motion::path::Path;
+///
+/// let path = Path::from([
+///     Vec2::new(0.0, 0.0),
+///     Vec2::new(24.0, 0.0),
+///     Vec2::new(24.0, 24.0),
+/// ]);
+///
+/// assert_eq!(path.length(), 48.0);
+/// assert_eq!(path.point_at_distance(36.0), Some(Vec2::new(24.0, 12.0)));
+///
+/// // The robot is 12 inches along the path, 3 inches to the side of it.
+/// let closest = path.closest_point(Vec2::new(12.0, 3.0)).unwrap();
+/// assert_eq!(closest.position, Vec2::new(12.0, 0.0));
+/// assert_eq!(closest.distance, 12.0);
+/// assert_eq!(closest.segment, 0);
+/// ```
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Path {
+    points: Vec<Vec2<f64>>,
+
+    /// Distance along the path to each point, starting at `0.0`.
+    distances: Vec<f64>,
+}
+
+impl Path {
+    /// Creates a new path passing through a sequence of points.
+    pub fn new(points: impl IntoIterator<Item = Vec2<f64>>) -> Self {
+        let points: Vec<Vec2<f64>> = points.into_iter().collect();
+
+        let mut distance = 0.0;
+        let distances = points
+            .iter()
+            .enumerate()
+            .map(|(i, point)| {
+                if i > 0 {
+                    distance += points[i - 1].distance(*point);
+                }
+                distance
+            })
+            .collect();
+
+        Self { points, distances }
+    }
+
+    /// Returns the points that this path passes through.
+    #[must_use]
+    pub fn points(&self) -> &[Vec2<f64>] {
+        &self.points
+    }
+
+    /// Returns the distance along the path from its first point to each of its points.
+    #[must_use]
+    pub fn distances(&self) -> &[f64] {
+        &self.distances
+    }
+
+    /// Returns the number of points in the path.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns `true` if the path has no points.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Returns the total length of the path.
+    #[must_use]
+    pub fn length(&self) -> f64 {
+        self.distances.last().copied().unwrap_or(0.0)
+    }
+
+    /// Returns the point `distance` along the path from its first point.
+    ///
+    /// `distance` is clamped to the length of the path, so negative distances return the first
+    /// point and distances past the end of the path return the last point. Returns `None` if the
+    /// path is empty.
+    #[must_use]
+    pub fn point_at_distance(&self, distance: f64) -> Option<Vec2<f64>> {
+        let last = self.points.len().checked_sub(1)?;
+        if last == 0 {
+            return Some(self.points[0]);
+        }
+
+        let distance = distance.clamp(0.0, self.length());
+
+        // The segment ending at the first point at or past `distance`.
+        let segment = self.distances.partition_point(|&d| d < distance).clamp(1, last) - 1;
+
+        Some(self.lerp_segment(segment, distance - self.distances[segment]))
+    }
+
+    /// Returns the point on the path closest to `point`.
+    ///
+    /// If multiple points on the path are equally close, the one closest to the start of the
+    /// path is returned. Returns `None` if the path is empty.
+    #[must_use]
+    pub fn closest_point(&self, point: Vec2<f64>) -> Option<PathPoint> {
+        let first = *self.points.first()?;
+
+        let mut closest = PathPoint {
+            position: first,
+            distance: 0.0,
+            segment: 0,
+        };
+        let mut closest_distance = point.distance_squared(first);
+
+        for (segment, window) in self.points.windows(2).enumerate() {
+            let (start, end) = (window[0], window[1]);
+            let delta = end - start;
+            let length_squared = delta.length_squared();
+
+            // Project onto the segment, clamping to its endpoints.
+            let t = if length_squared == 0.0 {
+                0.0
+            } else {
+                ((point - start).dot(delta) / length_squared).clamp(0.0, 1.0)
+            };
+            let position = start + delta * t;
+
+            let distance = point.distance_squared(position);
+            if distance < closest_distance {
+                closest_distance = distance;
+                closest = PathPoint {
+                    position,
+                    distance: self.distances[segment] + t * delta.length(),
+                    segment,
+                };
+            }
+        }
+
+        Some(closest)
+    }
+
+    /// Returns the point `distance` along a segment from its start.
+    fn lerp_segment(&self, segment: usize, distance: f64) -> Vec2<f64> {
+        let (start, end) = (self.points[segment], self.points[segment + 1]);
+        let length = self.distances[segment + 1] - self.distances[segment];
+
+        if length == 0.0 {
+            start
+        } else {
+            start.lerp(end, distance / length)
+        }
+    }
+}
+
+impl FromIterator<Vec2<f64>> for Path {
+    fn from_iter<I: IntoIterator<Item = Vec2<f64>>>(iter: I) -> Self {
+        Self::new(iter)
+    }
+}
+
+impl From<Vec<Vec2<f64>>> for Path {
+    fn from(points: Vec<Vec2<f64>>) -> Self {
+        Self::new(points)
+    }
+}
+
+impl From<&[Vec2<f64>]> for Path {
+    fn from(points: &[Vec2<f64>]) -> Self {
+        Self::new(points.iter().copied())
+    }
+}
+
+impl<const N: usize> From<[Vec2<f64>; N]> for Path {
+    fn from(points: [Vec2<f64>; N]) -> Self {
+        Self::new(points)
+    }
+}
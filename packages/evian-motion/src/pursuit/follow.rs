@@ -1,3 +1,6 @@
+extern crate alloc;
+
+use alloc::boxed::Box;
 use std::{
     pin::Pin,
     task::{Context, Poll},
@@ -6,48 +9,236 @@ use std::{
 
 use evian_drivetrain::{Drivetrain, model::Tank};
 use evian_math::{Angle, Vec2};
-use evian_tracking::{TracksHeading, TracksPosition};
-
-use vexide::{
-    time::{Sleep, sleep},
-};
+use evian_tracking::{TracksHeading, TracksPosition, TracksVelocity};
 
 use super::Waypoint;
+use crate::checked::{CheckedMotion, ErrorSlot};
+use crate::clock::{Clock, VexideClock};
+use crate::telemetry::{PoseBuffer, PoseRecorder, PoseSample, TickCallback};
 
-pub struct State {
+pub struct State<S> {
     current: Waypoint,
     next: Waypoint,
+    segment: usize,
     lookahead_point: Vec2<f64>,
+    lookahead_source: LookaheadSource,
     start_time: Instant,
-    sleep: Sleep,
+    sleep: S,
+}
+
+/// Describes how the lookahead point of a pure pursuit motion was chosen during an update.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum LookaheadSource {
+    /// The lookahead circle intersected the current path segment once.
+    SingleIntersection,
+
+    /// The lookahead circle intersected the current path segment twice, and the intersection
+    /// closest to the end of the segment was chosen.
+    NearestIntersection,
+
+    /// The lookahead circle didn't intersect the current path segment, so the lookahead point
+    /// from the previous update was reused.
+    ///
+    /// This typically happens when the robot has been pushed (or has drifted) more than the
+    /// lookahead distance away from the path. If it happens often, the lookahead distance is
+    /// likely too small.
+    PreviousPoint,
+}
+
+/// A lookahead distance that grows with the robot's speed.
+///
+/// The lookahead distance is `gain * speed`, clamped between a minimum and maximum distance. See
+/// [`PurePursuitFuture::with_adaptive_lookahead`] for more information.
+///
+/// # Examples
+///
+/// ```
+/// use evian_motion::pursuit::AdaptiveLookahead;
+///
+/// let lookahead = AdaptiveLookahead::new(0.25, 6.0, 18.0);
+///
+/// // The lookahead grows with speed...
+/// assert_eq!(lookahead.distance(0.0), 6.0);
+/// assert_eq!(lookahead.distance(40.0), 10.0);
+/// assert_eq!(lookahead.distance(60.0), 15.0);
+///
+/// // ...up to its maximum, in either direction.
+/// assert_eq!(lookahead.distance(100.0), 18.0);
+/// assert_eq!(lookahead.distance(-40.0), 10.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdaptiveLookahead {
+    gain: f64,
+    min: f64,
+    max: f64,
 }
 
+impl AdaptiveLookahead {
+    /// Creates a new adaptive lookahead, given the lookahead distance gained per unit of speed
+    /// (in seconds) and the minimum and maximum lookahead distances (in wheel units).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is greater than `max`.
+    #[must_use]
+    pub const fn new(gain: f64, min: f64, max: f64) -> Self {
+        assert!(
+            min <= max,
+            "minimum lookahead distance is greater than maximum"
+        );
+        Self { gain, min, max }
+    }
+
+    /// Returns the lookahead distance gained per unit of speed, in seconds.
+    #[must_use]
+    pub const fn gain(&self) -> f64 {
+        self.gain
+    }
+
+    /// Returns the minimum lookahead distance.
+    #[must_use]
+    pub const fn min(&self) -> f64 {
+        self.min
+    }
+
+    /// Returns the maximum lookahead distance.
+    #[must_use]
+    pub const fn max(&self) -> f64 {
+        self.max
+    }
+
+    /// Returns the lookahead distance for a robot moving at `speed` wheel units per second.
+    ///
+    /// Driving backwards is treated the same as driving forwards at the same speed.
+    #[must_use]
+    pub const fn distance(&self, speed: f64) -> f64 {
+        (self.gain * speed.abs()).clamp(self.min, self.max)
+    }
+}
+
+/// An [`AdaptiveLookahead`], paired with a way to read the robot's speed from its tracking
+/// system.
+pub(crate) struct SpeedLookahead<T> {
+    lookahead: AdaptiveLookahead,
+    speed: fn(&T) -> f64,
+}
+
+impl<T: TracksVelocity> SpeedLookahead<T> {
+    pub fn new(lookahead: AdaptiveLookahead) -> Self {
+        Self {
+            lookahead,
+            speed: T::linear_velocity,
+        }
+    }
+}
+
+impl<T> SpeedLookahead<T> {
+    pub fn distance(&self, tracking: &T) -> f64 {
+        self.lookahead.distance((self.speed)(tracking))
+    }
+}
+
+/// A snapshot of a pure pursuit motion's state during a single update.
+///
+/// This is provided to callbacks registered with [`PurePursuitFuture::with_telemetry`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PursuitTelemetry {
+    /// Time elapsed since the motion started.
+    pub elapsed: Duration,
+
+    /// The robot's position.
+    pub position: Vec2<f64>,
+
+    /// The robot's heading.
+    pub heading: Angle,
+
+    /// The point on the path that the robot is steering towards.
+    pub lookahead_point: Vec2<f64>,
+
+    /// How [`lookahead_point`](PursuitTelemetry::lookahead_point) was chosen.
+    pub lookahead_source: LookaheadSource,
+
+    /// Radius of the lookahead circle, which changes with the robot's speed if an
+    /// [adaptive lookahead](PurePursuitFuture::with_adaptive_lookahead) is used.
+    pub lookahead_distance: f64,
+
+    /// Index of the path segment currently being followed.
+    ///
+    /// Segment `0` is the segment from the robot's starting position to the first waypoint, and
+    /// segment `n` (for `n > 0`) is the segment from waypoint `n - 1` to waypoint `n`.
+    pub segment: usize,
+
+    /// Signed curvature (the reciprocal of the turning radius) of the arc from the robot to the
+    /// lookahead point.
+    ///
+    /// Negative values turn the robot counterclockwise.
+    pub curvature: f64,
+
+    /// Velocity of the path at the waypoint nearest to the robot.
+    pub velocity: f64,
+}
+
+/// Callback registered with [`PurePursuitFuture::with_telemetry`].
+type TelemetryCallback<'a> = Box<dyn FnMut(&PursuitTelemetry) + 'a>;
+
 /// Moves a drivetrain along a set of discrete waypoints using pure pursuit.
+///
+/// # Steering
+///
+/// On each update, the motion finds a *lookahead point* where a circle of radius
+/// [`lookahead_distance`](PurePursuitFuture::with_lookahead_distance) around the robot intersects
+/// the path, then computes the curvature `κ` of the arc from the robot to that point. Given the
+/// path velocity `v` and the drivetrain's track width `w`, the sides of the drivetrain are driven
+/// at:
+///
+/// - **Left:** `v * (2 + κw) / 2`
+/// - **Right:** `v * (2 - κw) / 2`
+///
+/// This is equivalent to arcade drive with a throttle of `v` and a steer of `vκw / 2`. The
+/// motion's [telemetry](PurePursuitFuture::with_telemetry) reports the lookahead point and
+/// curvature on each update, which is the most useful information to have when tuning.
+///
+/// # Lookahead Distance
+///
+/// A short lookahead follows the path closely, but steers so sharply at speed that the robot
+/// oscillates around the path. A long lookahead is smooth at speed, but cuts corners when
+/// driving slowly. By default, the lookahead distance is fixed, which is a compromise between
+/// the two. An [adaptive lookahead](PurePursuitFuture::with_adaptive_lookahead) instead scales
+/// the lookahead distance with the robot's speed.
 #[must_use = "futures do nothing unless you `.await` or poll them"]
-pub struct PurePursuitFuture<'a, M, T, I>
+pub struct PurePursuitFuture<'a, M, T, I, C = VexideClock>
 where
     M: Tank,
     T: TracksPosition + TracksHeading,
     I: Iterator<Item = Waypoint> + Unpin,
+    C: Clock,
 {
     pub(crate) drivetrain: &'a mut Drivetrain<M, T>,
+    pub(crate) error: ErrorSlot<M::Error>,
+
+    pub(crate) clock: C,
 
     /// Internal future state ("local variables").
-    pub(crate) state: Option<State>,
+    pub(crate) state: Option<State<C::Sleep>>,
 
     pub(crate) waypoints: I,
     pub(crate) lookahead_distance: f64,
+    pub(crate) adaptive_lookahead: Option<SpeedLookahead<T>>,
     pub(crate) track_width: f64,
     pub(crate) timeout: Option<Duration>,
+    pub(crate) pose_recorder: Option<PoseRecorder<'a, T>>,
+    pub(crate) tick: Option<TickCallback<'a, T>>,
+    pub(crate) telemetry: Option<TelemetryCallback<'a>>,
 }
 
 // MARK: Future Poll
 
-impl<M, T, I> Future for PurePursuitFuture<'_, M, T, I>
+impl<M, T, I, C> Future for PurePursuitFuture<'_, M, T, I, C>
 where
     M: Tank,
     T: TracksPosition + TracksHeading,
     I: Iterator<Item = Waypoint> + Unpin,
+    C: Clock,
 {
     type Output = ();
 
@@ -55,8 +246,14 @@ where
         let this = self.get_mut();
 
         if this.state.is_none() {
-            let now = Instant::now();
+            let now = this.clock.now();
             let position = this.drivetrain.tracking.position();
+            let lookahead_distance = this
+                .adaptive_lookahead
+                .as_ref()
+                .map_or(this.lookahead_distance, |lookahead| {
+                    lookahead.distance(&this.drivetrain.tracking)
+                });
 
             let Some(mut next) = this.waypoints.next() else {
                 return Poll::Ready(()); // path is empty
@@ -73,8 +270,11 @@ where
                 velocity: next.velocity,
             };
 
+            let mut segment = 0;
+
             // Keep iterating line segments until we find one we haven't intersected.
-            while position.distance(next.position) < this.lookahead_distance {
+            while position.distance(next.position) < lookahead_distance {
+                segment += 1;
                 current = next;
                 next = if let Some(next_waypoint) = this.waypoints.next() {
                     next_waypoint
@@ -84,9 +284,9 @@ where
             }
 
             // Compute initial lookahead point.
-            let lookahead_point = match line_segment_circle_intersections(
+            let (lookahead_point, lookahead_source) = match line_segment_circle_intersections(
                 position,
-                this.lookahead_distance,
+                lookahead_distance,
                 current.position,
                 next.position,
             ) {
@@ -95,24 +295,32 @@ where
                 (None, None) => unreachable!(),
 
                 // One intersection; use that.
-                (Some(solution), None) | (None, Some(solution)) => solution,
+                (Some(solution), None) | (None, Some(solution)) => {
+                    (solution, LookaheadSource::SingleIntersection)
+                }
 
                 // Two intersections, pick whichever one is closest to the next point on the path.
                 (Some(solution_1), Some(solution_2)) => {
-                    if solution_1.distance(next.position) < solution_2.distance(next.position) {
+                    let solution = if solution_1.distance_squared(next.position)
+                        < solution_2.distance_squared(next.position)
+                    {
                         solution_1
                     } else {
                         solution_2
-                    }
+                    };
+
+                    (solution, LookaheadSource::NearestIntersection)
                 }
             };
 
             this.state = Some(State {
-                sleep: sleep(Duration::from_millis(5)),
+                sleep: this.clock.sleep(Duration::from_millis(5)),
                 start_time: now,
                 lookahead_point,
+                lookahead_source,
                 current,
                 next,
+                segment,
             });
         }
 
@@ -122,31 +330,52 @@ where
             return Poll::Pending;
         }
 
+        if let Some(recorder) = &mut this.pose_recorder {
+            recorder.record(&this.drivetrain.tracking, this.clock.now());
+        }
+
+        if let Some(tick) = &mut this.tick {
+            tick.call(&this.drivetrain.tracking, this.clock.now());
+        }
+
+        if crate::pause_if_disabled(&this.clock, &mut state.sleep, cx, || {
+            this.error.record(this.drivetrain.model.drive_tank(0.0, 0.0));
+        }) {
+            return Poll::Pending;
+        }
+
         // Cancel movement if timeout has elapsed.
         if this
             .timeout
-            .is_some_and(|timeout| state.start_time.elapsed() > timeout)
+            .is_some_and(|timeout| this.clock.elapsed(state.start_time) > timeout)
         {
-            drop(this.drivetrain.model.drive_tank(0.0, 0.0));
+            this.error.record(this.drivetrain.model.drive_tank(0.0, 0.0));
             return Poll::Ready(());
         }
 
         // Tracking data (robot position and heading)
         let position = this.drivetrain.tracking.position();
         let heading = this.drivetrain.tracking.heading();
+        let lookahead_distance = this
+            .adaptive_lookahead
+            .as_ref()
+            .map_or(this.lookahead_distance, |lookahead| {
+                lookahead.distance(&this.drivetrain.tracking)
+            });
 
         // If the lookahead circle envelops the end of the current waypoint segment,
         // then switch to the next two waypoints.
         //
         // The ending point of the current segment becomes the starting point of the
         // next and so on until the path is complete.
-        while position.distance(state.next.position) < this.lookahead_distance {
+        while position.distance(state.next.position) < lookahead_distance {
+            state.segment += 1;
             state.current = state.next;
             state.next = if let Some(next_waypoint) = this.waypoints.next() {
                 next_waypoint
             } else {
                 // We're out of waypoints, meaning the end of path has been reached.
-                drop(this.drivetrain.model.drive_tank(0.0, 0.0));
+                this.error.record(this.drivetrain.model.drive_tank(0.0, 0.0));
                 return Poll::Ready(());
             };
         }
@@ -154,20 +383,25 @@ where
         // Compute lookahead point.
         match line_segment_circle_intersections(
             position,
-            this.lookahead_distance,
+            lookahead_distance,
             state.current.position,
             state.next.position,
         ) {
-            // No intersections; the lookahead circle isn't intersecting the path.
-            (None, None) => {}
+            // No intersections; the lookahead circle isn't intersecting the path, so keep
+            // steering towards the previous lookahead point.
+            (None, None) => state.lookahead_source = LookaheadSource::PreviousPoint,
 
             // One intersection; use that.
-            (Some(solution), None) | (None, Some(solution)) => state.lookahead_point = solution,
+            (Some(solution), None) | (None, Some(solution)) => {
+                state.lookahead_point = solution;
+                state.lookahead_source = LookaheadSource::SingleIntersection;
+            }
 
             // Two intersections; pick whichever one is closest to the next point on the path.
             (Some(solution_1), Some(solution_2)) => {
-                state.lookahead_point = if solution_1.distance(state.next.position)
-                    < solution_2.distance(state.next.position)
+                state.lookahead_source = LookaheadSource::NearestIntersection;
+                state.lookahead_point = if solution_1.distance_squared(state.next.position)
+                    < solution_2.distance_squared(state.next.position)
                 {
                     solution_1
                 } else {
@@ -177,16 +411,31 @@ where
         };
 
         // Take the profiled velocity of the closest point to the robot on the path.
-        let velocity =
-            if state.current.position.distance(position) < state.next.position.distance(position) {
-                state.current.velocity
-            } else {
-                state.next.velocity
-            };
+        let velocity = if state.current.position.distance_squared(position)
+            < state.next.position.distance_squared(position)
+        {
+            state.current.velocity
+        } else {
+            state.next.velocity
+        };
 
         let curvature = signed_arc_curvature(position, heading, state.lookahead_point);
 
-        drop(this.drivetrain.model.drive_tank(
+        if let Some(telemetry) = &mut this.telemetry {
+            telemetry(&PursuitTelemetry {
+                elapsed: this.clock.elapsed(state.start_time),
+                position,
+                heading,
+                lookahead_point: state.lookahead_point,
+                lookahead_source: state.lookahead_source,
+                lookahead_distance,
+                segment: state.segment,
+                curvature,
+                velocity,
+            });
+        }
+
+        this.error.record(this.drivetrain.model.drive_tank(
             velocity * (2.0 + curvature * this.track_width) / 2.0,
             velocity * (2.0 - curvature * this.track_width) / 2.0,
         ));
@@ -196,13 +445,28 @@ where
     }
 }
 
+impl<M, T, I, C> CheckedMotion for PurePursuitFuture<'_, M, T, I, C>
+where
+    M: Tank,
+    T: TracksPosition + TracksHeading,
+    I: Iterator<Item = Waypoint> + Unpin,
+    C: Clock,
+{
+    type Error = M::Error;
+
+    fn take_error(&mut self) -> Option<M::Error> {
+        self.error.take()
+    }
+}
+
 // MARK: Modifiers
 
-impl<M, T, I> PurePursuitFuture<'_, M, T, I>
+impl<'a, M, T, I, C> PurePursuitFuture<'a, M, T, I, C>
 where
     M: Tank,
     T: TracksPosition + TracksHeading,
     I: Iterator<Item = Waypoint> + Unpin,
+    C: Clock,
 {
     /// Modifies this motion's track width.
     pub const fn with_track_width(&mut self, track_width: f64) -> &mut Self {
@@ -221,11 +485,134 @@ where
         self
     }
 
+    /// Replaces the [`Clock`] used by this motion to keep time.
+    ///
+    /// See the [`clock`](crate::clock) module for more information. Any progress the motion has
+    /// made is discarded, so this should be called before the motion is first polled.
+    pub fn with_clock<C2: Clock>(self, clock: C2) -> PurePursuitFuture<'a, M, T, I, C2> {
+        PurePursuitFuture {
+            drivetrain: self.drivetrain,
+            waypoints: self.waypoints,
+            lookahead_distance: self.lookahead_distance,
+            adaptive_lookahead: self.adaptive_lookahead,
+            track_width: self.track_width,
+            timeout: self.timeout,
+            pose_recorder: self.pose_recorder,
+            tick: self.tick,
+            telemetry: self.telemetry,
+            error: ErrorSlot::new(),
+            clock,
+            state: None,
+        }
+    }
+
+    /// Records the robot's pose into a [`PoseBuffer`] on every update of this motion.
+    pub fn record_into(&mut self, buffer: &'a mut PoseBuffer) -> &mut Self {
+        self.pose_recorder = Some(PoseRecorder::new(buffer));
+        self
+    }
+
+    /// Calls `callback` with the robot's pose on every update of this motion.
+    ///
+    /// The callback must return quickly and must not block. See
+    /// [`DriveFuture::with_tick`](crate::basic::DriveFuture::with_tick) for more information.
+    pub fn with_tick(&mut self, callback: impl FnMut(&PoseSample) + 'a) -> &mut Self {
+        self.tick = Some(TickCallback::new(callback));
+        self
+    }
+
+    /// Removes this motion's tick callback.
+    pub fn without_tick(&mut self) -> &mut Self {
+        self.tick = None;
+        self
+    }
+
+    /// Calls `callback` with a snapshot of this motion's state on every update.
+    ///
+    /// This is useful for logging the motion's lookahead point and curvature while tuning.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// pure_pursuit
+    ///     .follow(&mut drivetrain, waypoints)
+    ///     .with_telemetry(|telemetry| {
+    ///         println!(
+    ///             "segment {}: curvature {:.3} ({:?})",
+    ///             telemetry.segment, telemetry.curvature, telemetry.lookahead_source,
+    ///         );
+    ///     })
+    ///     .await;
+    /// ```
+    pub fn with_telemetry(&mut self, callback: impl FnMut(&PursuitTelemetry) + 'a) -> &mut Self {
+        self.telemetry = Some(Box::new(callback));
+        self
+    }
+
+    /// Removes this motion's telemetry callback.
+    pub fn without_telemetry(&mut self) -> &mut Self {
+        self.telemetry = None;
+        self
+    }
+
     /// Modifies this motion's lookahead distance.
+    ///
+    /// If this motion has an [adaptive lookahead](PurePursuitFuture::with_adaptive_lookahead),
+    /// this distance is only used once it is removed.
     pub const fn with_lookahead_distance(&mut self, lookahead_distance: f64) -> &mut Self {
         self.lookahead_distance = lookahead_distance;
         self
     }
+
+    /// Removes this motion's adaptive lookahead, returning to a fixed lookahead distance.
+    pub fn without_adaptive_lookahead(&mut self) -> &mut Self {
+        self.adaptive_lookahead = None;
+        self
+    }
+}
+
+impl<M, T, I, C> PurePursuitFuture<'_, M, T, I, C>
+where
+    M: Tank,
+    T: TracksPosition + TracksHeading + TracksVelocity,
+    I: Iterator<Item = Waypoint> + Unpin,
+    C: Clock,
+{
+    /// Scales this motion's lookahead distance with the robot's speed.
+    ///
+    /// On each update, the lookahead distance is set to `gain * speed`, clamped between `min`
+    /// and `max`, where `speed` is the magnitude of the robot's linear velocity as measured by
+    /// its tracking system (see [`AdaptiveLookahead`]). This replaces the fixed
+    /// [lookahead distance](PurePursuitFuture::with_lookahead_distance) until
+    /// [removed](PurePursuitFuture::without_adaptive_lookahead).
+    ///
+    /// # Tuning
+    ///
+    /// `gain` is the time (in seconds) that the robot looks ahead along the path. Start by
+    /// finding the smallest lookahead distance that the robot follows the path without
+    /// oscillating at its top speed, and divide it by that speed to get `gain`. `min` should be
+    /// large enough that the robot still reaches the path smoothly while starting from rest, and
+    /// `max` limits how much of a corner can be cut at full speed. Logging the lookahead distance
+    /// reported by [telemetry](PurePursuitFuture::with_telemetry) is useful for checking that
+    /// the lookahead isn't pinned to either limit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is greater than `max`.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// pure_pursuit
+    ///     .follow(&mut drivetrain, waypoints)
+    ///     // Look 0.25 seconds ahead, between 6 and 18 inches.
+    ///     .with_adaptive_lookahead(0.25, 6.0, 18.0)
+    ///     .await;
+    /// ```
+    pub fn with_adaptive_lookahead(&mut self, gain: f64, min: f64, max: f64) -> &mut Self {
+        self.adaptive_lookahead = Some(SpeedLookahead::new(AdaptiveLookahead::new(gain, min, max)));
+        self
+    }
 }
 
 // MARK: Math Functions
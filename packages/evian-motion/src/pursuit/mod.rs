@@ -4,13 +4,15 @@ extern crate alloc;
 
 use std::time::Duration;
 
-use alloc::vec::Vec;
+use alloc::vec::{IntoIter, Vec};
 use evian_drivetrain::{Drivetrain, model::Tank};
 use evian_math::Vec2;
-use evian_tracking::{TracksHeading, TracksPosition};
+use evian_tracking::{PoseBuffer, TracksHeading, TracksPosition};
+
+use crate::{checked::ErrorSlot, clock::VexideClock, path::Path};
 
 mod follow;
-pub use follow::PurePursuitFuture;
+pub use follow::{AdaptiveLookahead, LookaheadSource, PurePursuitFuture, PursuitTelemetry};
 
 /// Parses a [LemLib 0.5 path] into a discrete list of [`Waypoint`]s.
 ///
@@ -91,11 +93,79 @@ impl PurePursuit {
     ) -> PurePursuitFuture<'a, M, T, I> {
         PurePursuitFuture {
             drivetrain,
+            error: ErrorSlot::new(),
+            clock: VexideClock,
             state: None,
             waypoints: waypoints.into_iter(),
             lookahead_distance: self.lookahead_distance,
+            adaptive_lookahead: None,
             track_width: self.track_width,
             timeout: self.timeout,
+            pose_recorder: None,
+            tick: None,
+            telemetry: None,
         }
     }
+
+    /// Moves a drivetrain along a [`Path`] at a constant `velocity` using pure pursuit.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let path: Path = [(0.0, 24.0), (24.0, 24.0), (24.0, 48.0)]
+    ///     .into_iter()
+    ///     .map(Vec2::from)
+    ///     .collect();
+    ///
+    /// pure_pursuit.follow_path(&mut drivetrain, &path, 0.5).await;
+    /// ```
+    pub fn follow_path<'a, M: Tank, T: TracksPosition + TracksHeading>(
+        &self,
+        drivetrain: &'a mut Drivetrain<M, T>,
+        path: &Path,
+        velocity: f64,
+    ) -> PurePursuitFuture<'a, M, T, IntoIter<Waypoint>> {
+        let waypoints: Vec<Waypoint> = path
+            .points()
+            .iter()
+            .map(|&position| Waypoint { position, velocity })
+            .collect();
+
+        self.follow(drivetrain, waypoints)
+    }
+
+    /// Retraces a recorded path backwards, following each recorded position from newest to
+    /// oldest using pure pursuit.
+    ///
+    /// This is useful for returning to where the robot started, such as to retry an action
+    /// after an intake jam. `history` may be recorded by a tracking system (such as
+    /// [`WheeledTracking::pose_history`]) or by any motion's `record_into` modifier. Each recorded
+    /// position is followed at a constant `velocity`.
+    ///
+    /// The robot drives forwards along the reversed path, meaning it will turn around to face
+    /// its first waypoint. Since pure pursuit only follows the line segments between waypoints,
+    /// a coarsely sampled history will still produce a smooth path, although any detail shorter
+    /// than the lookahead distance will be cut off.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// tracking.set_pose_history(256, 2.0);
+    ///
+    /// // ... later, after something has gone wrong ...
+    /// let history = drivetrain.tracking.pose_history();
+    /// pure_pursuit.retrace(&mut drivetrain, &history, 0.5).await;
+    /// ```
+    ///
+    /// [`WheeledTracking::pose_history`]: evian_tracking::wheeled::WheeledTracking::pose_history
+    pub fn retrace<'a, M: Tank, T: TracksPosition + TracksHeading>(
+        &self,
+        drivetrain: &'a mut Drivetrain<M, T>,
+        history: &PoseBuffer,
+        velocity: f64,
+    ) -> PurePursuitFuture<'a, M, T, IntoIter<Waypoint>> {
+        let path: Path = history.iter().rev().map(|sample| sample.position).collect();
+
+        self.follow_path(drivetrain, &path, velocity)
+    }
 }
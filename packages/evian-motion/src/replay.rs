@@ -0,0 +1,400 @@
+//! Driver input recording and replay.
+//!
+//! A quick way to create an autonomous routine is to record the driver's controller inputs
+//! during practice, then replay them later. An [`InputRecorder`] captures controller states
+//! during driver control, and a [`ReplayFuture`] feeds them back to the drivetrain at the same
+//! times they were recorded.
+//!
+//! Since no feedback is involved, replayed routines will drift from run to run with battery
+//! voltage, field conditions, and wheel slip. They're not a replacement for motions that use
+//! tracking, but can be a useful starting point.
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use evian_drivetrain::{
+    Drivetrain,
+    model::{Arcade, DrivetrainModel, Tank},
+};
+use evian_tracking::Tracking;
+use vexide::controller::ControllerState;
+
+use crate::checked::{CheckedMotion, ErrorSlot};
+use crate::clock::{Clock, VexideClock};
+
+/// A controller state recorded at a point in time.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputSample {
+    /// Time between the first recorded sample and this one.
+    pub elapsed: Duration,
+
+    /// The recorded state of the controller, including both joysticks and every button.
+    pub state: ControllerState,
+}
+
+/// Drives a model from a recorded controller state, as used by [`InputRecorder::replay_arcade`]
+/// and [`InputRecorder::replay_tank`].
+pub type ReplayDrive<M> = fn(&mut M, &ControllerState) -> Result<(), <M as DrivetrainModel>::Error>;
+
+/// Records controller inputs into a fixed-capacity buffer.
+///
+/// Once the buffer is full, recording stops and any further samples are discarded, so the
+/// recording always starts from the beginning of the driver's inputs.
+///
+/// Samples are timestamped using a [`Clock`], which is the brain's clock unless replaced using
+/// [`InputRecorder::with_clock`].
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut recorder = InputRecorder::new(3000);
+///
+/// loop {
+///     let state = controller.state().unwrap_or_default();
+///     recorder.record(state);
+///
+///     _ = drivetrain.model.drive_arcade(state.left_stick.y(), state.right_stick.x());
+///     sleep(Duration::from_millis(5)).await;
+/// }
+///
+/// // ... later, during autonomous ...
+/// recorder.replay_arcade(&mut drivetrain).await;
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputRecorder<C = VexideClock> {
+    samples: Vec<InputSample>,
+    capacity: usize,
+    start_time: Option<Instant>,
+    clock: C,
+}
+
+impl InputRecorder {
+    /// Creates a new empty recorder that can hold up to `capacity` samples.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            samples: Vec::with_capacity(capacity),
+            capacity,
+            start_time: None,
+            clock: VexideClock,
+        }
+    }
+}
+
+impl<C: Clock> InputRecorder<C> {
+    /// Replaces the [`Clock`] used to timestamp recorded samples.
+    ///
+    /// See the [`clock`](crate::clock) module for more information. Samples already recorded
+    /// were timestamped by the previous clock, so this should be called before recording starts.
+    #[must_use]
+    pub fn with_clock<C2: Clock>(self, clock: C2) -> InputRecorder<C2> {
+        InputRecorder {
+            samples: self.samples,
+            capacity: self.capacity,
+            start_time: self.start_time,
+            clock,
+        }
+    }
+
+    /// Records a controller state at the current time according to the recorder's clock.
+    ///
+    /// Returns `false` (discarding the state) if the recorder is full.
+    pub fn record(&mut self, state: ControllerState) -> bool {
+        self.record_at(state, self.clock.now())
+    }
+
+    /// Records a controller state at a given time.
+    ///
+    /// Returns `false` (discarding the state) if the recorder is full.
+    pub fn record_at(&mut self, state: ControllerState, timestamp: Instant) -> bool {
+        if self.is_full() {
+            return false;
+        }
+
+        let start_time = *self.start_time.get_or_insert(timestamp);
+        self.samples.push(InputSample {
+            elapsed: timestamp.saturating_duration_since(start_time),
+            state,
+        });
+
+        true
+    }
+
+    /// Returns the recorded samples, from oldest to newest.
+    #[must_use]
+    pub fn samples(&self) -> &[InputSample] {
+        &self.samples
+    }
+
+    /// Returns the number of recorded samples.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Returns `true` if no samples have been recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Returns `true` if the recorder can't hold any more samples.
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.samples.len() >= self.capacity
+    }
+
+    /// Returns the maximum number of samples the recorder can hold.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the time between the first and last recorded samples.
+    #[must_use]
+    pub fn duration(&self) -> Duration {
+        self.samples.last().map_or(Duration::ZERO, |sample| sample.elapsed)
+    }
+
+    /// Removes every recorded sample, allowing a new recording to be started.
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.start_time = None;
+    }
+
+    /// Replays the recorded inputs by passing each one to `drive` at the time it was recorded.
+    ///
+    /// `drive` should map a controller state to drivetrain outputs in the same way as it was
+    /// done during driver control, returning any error from the drivetrain model. Errors don't
+    /// stop the replay, but the first one is kept and can be observed through
+    /// [`CheckedMotion::await_checked`]. Once the recording ends, `drive` is given a default
+    /// controller state (with centered joysticks and no buttons pressed).
+    pub fn replay<'a, M, T, F>(
+        &'a self,
+        drivetrain: &'a mut Drivetrain<M, T>,
+        drive: F,
+    ) -> ReplayFuture<'a, M, T, F>
+    where
+        M: DrivetrainModel,
+        T: Tracking,
+        F: FnMut(&mut M, &ControllerState) -> Result<(), M::Error> + Unpin,
+    {
+        ReplayFuture {
+            samples: &self.samples,
+            drivetrain,
+            drive,
+            error: ErrorSlot::new(),
+            clock: VexideClock,
+            state: None,
+        }
+    }
+
+    /// Replays the recorded inputs as arcade drive, using the left joystick's y-axis for throttle
+    /// and the right joystick's x-axis for steering.
+    pub fn replay_arcade<'a, M: Arcade, T: Tracking>(
+        &'a self,
+        drivetrain: &'a mut Drivetrain<M, T>,
+    ) -> ReplayFuture<'a, M, T, ReplayDrive<M>> {
+        self.replay(drivetrain, |model: &mut M, state: &ControllerState| {
+            model.drive_arcade(state.left_stick.y(), state.right_stick.x())
+        })
+    }
+
+    /// Replays the recorded inputs as tank drive, using the y-axes of the left and right
+    /// joysticks for each side of the drivetrain.
+    pub fn replay_tank<'a, M: Tank, T: Tracking>(
+        &'a self,
+        drivetrain: &'a mut Drivetrain<M, T>,
+    ) -> ReplayFuture<'a, M, T, ReplayDrive<M>> {
+        self.replay(drivetrain, |model: &mut M, state: &ControllerState| {
+            model.drive_tank(state.left_stick.y(), state.right_stick.y())
+        })
+    }
+}
+
+pub(crate) struct State<S> {
+    sleep: S,
+    start_time: Instant,
+    disabled_since: Option<Instant>,
+    next_sample: usize,
+}
+
+/// Replays recorded controller inputs on a drivetrain.
+///
+/// This is created by [`InputRecorder::replay`] (or one of its variants). Playback is paused
+/// while the robot is disabled, and resumes from where it left off once re-enabled.
+///
+/// Like other motions, errors returned by the drivetrain model during playback are kept, and
+/// can be observed through [`CheckedMotion::await_checked`].
+///
+/// # Examples
+///
+/// ```
+/// use std::{
+///     pin::pin,
+///     task::{Context, Poll, Waker},
+///     time::Duration,
+/// };
+///
+/// use evian_drivetrain::{Drivetrain, model::{Arcade, DrivetrainModel}};
+/// use evian_motion::{CheckedMotion, InputRecorder, clock::{Clock, ManualClock}};
+/// use evian_tracking::Tracking;
+/// use vexide::controller::ControllerState;
+///
+/// /// A drivetrain with a disconnected motor.
+/// struct Disconnected;
+///
+/// impl DrivetrainModel for Disconnected {
+///     type Error = &'static str;
+/// }
+/// impl Arcade for Disconnected {
+///     fn drive_arcade(&mut self, _throttle: f64, _steer: f64) -> Result<(), Self::Error> {
+///         Err("motor disconnected")
+///     }
+/// }
+///
+/// struct NoTracking;
+/// impl Tracking for NoTracking {}
+///
+/// let clock = ManualClock::new();
+/// let mut recorder = InputRecorder::new(2);
+/// recorder.record_at(ControllerState::default(), clock.now());
+/// recorder.record_at(ControllerState::default(), clock.now() + Duration::from_millis(20));
+///
+/// let mut drivetrain = Drivetrain::new(Disconnected, NoTracking);
+/// let mut replay = pin!(
+///     recorder
+///         .replay_arcade(&mut drivetrain)
+///         .with_clock(clock.clone())
+///         .await_checked()
+/// );
+/// let mut cx = Context::from_waker(Waker::noop());
+///
+/// let result = loop {
+///     if let Poll::Ready(result) = replay.as_mut().poll(&mut cx) {
+///         break result;
+///     }
+///     clock.advance(Duration::from_millis(5));
+/// };
+///
+/// assert_eq!(result, Err("motor disconnected"));
+/// ```
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ReplayFuture<'a, M, T, F, C = VexideClock>
+where
+    M: DrivetrainModel,
+    T: Tracking,
+    F: FnMut(&mut M, &ControllerState) -> Result<(), M::Error> + Unpin,
+    C: Clock,
+{
+    samples: &'a [InputSample],
+    drivetrain: &'a mut Drivetrain<M, T>,
+    drive: F,
+    error: ErrorSlot<M::Error>,
+    clock: C,
+    state: Option<State<C::Sleep>>,
+}
+
+impl<M, T, F, C> Future for ReplayFuture<'_, M, T, F, C>
+where
+    M: DrivetrainModel,
+    T: Tracking,
+    F: FnMut(&mut M, &ControllerState) -> Result<(), M::Error> + Unpin,
+    C: Clock,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let state = this.state.get_or_insert_with(|| State {
+            sleep: this.clock.sleep(Duration::from_millis(5)),
+            start_time: this.clock.now(),
+            disabled_since: None,
+            next_sample: 0,
+        });
+
+        if Pin::new(&mut state.sleep).poll(cx).is_pending() {
+            return Poll::Pending;
+        }
+
+        if crate::pause_if_disabled(&this.clock, &mut state.sleep, cx, || {
+            this.error
+                .record((this.drive)(&mut this.drivetrain.model, &ControllerState::default()));
+        }) {
+            state.disabled_since.get_or_insert_with(|| this.clock.now());
+            return Poll::Pending;
+        }
+
+        // Pause the recording while disabled by shifting its start forward, so that being
+        // re-enabled picks up from the sample we left off at rather than skipping ahead.
+        if let Some(disabled_since) = state.disabled_since.take() {
+            state.start_time += this.clock.elapsed(disabled_since);
+        }
+
+        // Play back every sample we've reached since the last update in order, so that no button
+        // presses are skipped.
+        let elapsed = this.clock.elapsed(state.start_time);
+        while let Some(sample) = this.samples.get(state.next_sample)
+            && sample.elapsed <= elapsed
+        {
+            this.error
+                .record((this.drive)(&mut this.drivetrain.model, &sample.state));
+            state.next_sample += 1;
+        }
+
+        if state.next_sample >= this.samples.len() {
+            this.error
+                .record((this.drive)(&mut this.drivetrain.model, &ControllerState::default()));
+            return Poll::Ready(());
+        }
+
+        state.sleep = this.clock.sleep(Duration::from_millis(5));
+
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+impl<M, T, F, C> CheckedMotion for ReplayFuture<'_, M, T, F, C>
+where
+    M: DrivetrainModel,
+    T: Tracking,
+    F: FnMut(&mut M, &ControllerState) -> Result<(), M::Error> + Unpin,
+    C: Clock,
+{
+    type Error = M::Error;
+
+    fn take_error(&mut self) -> Option<M::Error> {
+        self.error.take()
+    }
+}
+
+impl<'a, M, T, F, C> ReplayFuture<'a, M, T, F, C>
+where
+    M: DrivetrainModel,
+    T: Tracking,
+    F: FnMut(&mut M, &ControllerState) -> Result<(), M::Error> + Unpin,
+    C: Clock,
+{
+    /// Replaces the [`Clock`] used by this motion to keep time.
+    ///
+    /// See the [`clock`](crate::clock) module for more information. Any progress the motion has
+    /// made is discarded, so this should be called before the motion is first polled.
+    pub fn with_clock<C2: Clock>(self, clock: C2) -> ReplayFuture<'a, M, T, F, C2> {
+        ReplayFuture {
+            samples: self.samples,
+            drivetrain: self.drivetrain,
+            drive: self.drive,
+            error: ErrorSlot::new(),
+            clock,
+            state: None,
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use core::f64::consts::FRAC_PI_2;
+
+use evian_math::Angle;
+
+/// Which end of the robot a seeking motion drives towards its target with.
+///
+/// A target more than 90° away from the robot's heading is behind the robot, so it is quicker
+/// to reverse towards it than to turn around. When this happens, the back of the robot is
+/// treated as its front, which flips the sign of both the distance and cross-track errors (see
+/// [`ApproachDirection::errors`]).
+///
+/// # Hysteresis
+///
+/// Exactly at 90°, both ends of the robot are equally far from facing the target, and flipping
+/// from one end to the other makes the cross-track error jump from one side of the robot to the
+/// other. If the direction were chosen fresh on every update, a target sitting almost
+/// perpendicular to the robot could flip the errors back and forth from sensor noise alone,
+/// kicking the controllers each time. [`ApproachDirection::update`] instead keeps driving with
+/// the current end until the target is a margin past 90°.
+///
+/// # Examples
+///
+/// ```
+/// use evian_math::IntoAngle;
+/// use evian_motion::seeking::ApproachDirection;
+///
+/// let hysteresis = 10.0.deg();
+///
+/// // Targets in front of the robot are approached forwards, and targets behind it backwards.
+/// assert_eq!(ApproachDirection::facing(89.0.deg()), ApproachDirection::Forwards);
+/// assert_eq!(ApproachDirection::facing(91.0.deg()), ApproachDirection::Backwards);
+/// assert_eq!(ApproachDirection::facing((-91.0).deg()), ApproachDirection::Backwards);
+///
+/// // Crossing 90° doesn't flip the direction until the hysteresis margin is passed...
+/// let direction = ApproachDirection::Forwards;
+/// assert_eq!(direction.update(95.0.deg(), hysteresis), ApproachDirection::Forwards);
+/// assert_eq!(direction.update((-99.0).deg(), hysteresis), ApproachDirection::Forwards);
+/// assert_eq!(direction.update(101.0.deg(), hysteresis), ApproachDirection::Backwards);
+///
+/// // ...in either direction.
+/// let direction = ApproachDirection::Backwards;
+/// assert_eq!(direction.update(85.0.deg(), hysteresis), ApproachDirection::Backwards);
+/// assert_eq!(direction.update((-79.0).deg(), hysteresis), ApproachDirection::Forwards);
+/// ```
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ApproachDirection {
+    /// The front of the robot is driven towards the target.
+    #[default]
+    Forwards,
+
+    /// The back of the robot is driven towards the target.
+    Backwards,
+}
+
+impl ApproachDirection {
+    /// Returns the end of the robot facing closest to the target, given the angle between the
+    /// robot's heading and the target.
+    ///
+    /// `angle_error` should be wrapped to `[-π, π]`.
+    #[must_use]
+    pub fn facing(angle_error: Angle) -> Self {
+        if angle_error.as_radians().abs() > FRAC_PI_2 {
+            Self::Backwards
+        } else {
+            Self::Forwards
+        }
+    }
+
+    /// Returns the end of the robot that should be driven towards the target, only switching
+    /// ends once the target is more than `hysteresis` past 90° from the current end.
+    ///
+    /// `angle_error` should be wrapped to `[-π, π]`. See the [type-level
+    /// documentation](ApproachDirection#hysteresis) for more information.
+    #[must_use]
+    pub fn update(self, angle_error: Angle, hysteresis: Angle) -> Self {
+        let angle = angle_error.as_radians().abs();
+        let margin = hysteresis.as_radians().abs();
+
+        match self {
+            Self::Forwards if angle > FRAC_PI_2 + margin => Self::Backwards,
+            Self::Backwards if angle < FRAC_PI_2 - margin => Self::Forwards,
+            direction => direction,
+        }
+    }
+
+    /// Returns the end of the robot that a motion should drive towards the target with, given the
+    /// end it drove with on its `previous` update (if any).
+    ///
+    /// A `reverse`d motion always drives backwards. Otherwise, the direction is chosen with
+    /// [`facing`](Self::facing) on the first update and [`update`](Self::update) after that.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::{pin::pin, time::Duration};
+    ///
+    /// use evian_control::{Tolerances, loops::Pid};
+    /// use evian_drivetrain::Drivetrain;
+    /// use evian_math::{Angle, IntoAngle};
+    /// use evian_motion::{
+    ///     Seeking,
+    ///     clock::ManualClock,
+    ///     seeking::ApproachDirection,
+    ///     sim::{self, SimRobot},
+    /// };
+    ///
+    /// let hysteresis = 10.0.deg();
+    ///
+    /// // A target straight ahead is normally approached forwards...
+    /// let forwards = ApproachDirection::select(None, false, Angle::ZERO, hysteresis);
+    /// assert_eq!(forwards, ApproachDirection::Forwards);
+    ///
+    /// // ...but a reversed motion backs away from it, and never switches ends.
+    /// let backwards = ApproachDirection::select(None, true, Angle::ZERO, hysteresis);
+    /// assert_eq!(backwards, ApproachDirection::Backwards);
+    /// assert_eq!(
+    ///     ApproachDirection::select(Some(backwards), true, Angle::ZERO, hysteresis),
+    ///     ApproachDirection::Backwards,
+    /// );
+    ///
+    /// // Returns the throttle from the first update of a move to a point 24 inches ahead.
+    /// let first_throttle = |reverse: bool| {
+    ///     let robot = SimRobot::new();
+    ///     let mut drivetrain = Drivetrain::new(robot.clone(), robot.clone());
+    ///     let mut seeking = Seeking {
+    ///         linear_controller: Pid::new(0.5, 0.0, 0.0, None),
+    ///         lateral_controller: Pid::new(1.0, 0.0, 0.0, None),
+    ///         tolerances: Tolerances::new().error(0.5),
+    ///         timeout: Some(Duration::from_secs(1)),
+    ///     };
+    ///
+    ///     let clock = ManualClock::new();
+    ///     let mut motion = seeking
+    ///         .move_to_point(&mut drivetrain, (24.0, 0.0))
+    ///         .with_clock(clock.clone());
+    ///     if reverse {
+    ///         motion.reverse();
+    ///     }
+    ///
+    ///     let mut motion = pin!(motion);
+    ///     _ = sim::step(motion.as_mut(), &clock);
+    ///     _ = sim::step(motion.as_mut(), &clock);
+    ///
+    ///     robot.last().unwrap().throttle
+    /// };
+    ///
+    /// // Seeking motions drive the distance error to zero, so the reversed motion drives the
+    /// // robot backwards while the normal one drives it forwards.
+    /// assert!(first_throttle(false) > 0.0);
+    /// assert!(first_throttle(true) < 0.0);
+    /// ```
+    #[must_use]
+    pub fn select(
+        previous: Option<Self>,
+        reverse: bool,
+        angle_error: Angle,
+        hysteresis: Angle,
+    ) -> Self {
+        if reverse {
+            return Self::Backwards;
+        }
+
+        previous.map_or_else(
+            || Self::facing(angle_error),
+            |direction| direction.update(angle_error, hysteresis),
+        )
+    }
+
+    /// Returns the signed distance and cross-track errors to a target `distance` away, at
+    /// `angle_error` from the robot's heading.
+    ///
+    /// Approaching forwards, the distance error is positive and the cross-track error is
+    /// `distance * sin(angle_error)`. Approaching backwards, both are negated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evian_math::IntoAngle;
+    /// use evian_motion::seeking::ApproachDirection;
+    ///
+    /// // Either side of 90°, the errors are continuous as long as the direction doesn't change.
+    /// let (_, before) = ApproachDirection::Forwards.errors(10.0, 89.0.deg());
+    /// let (_, after) = ApproachDirection::Forwards.errors(10.0, 91.0.deg());
+    /// assert!((before - after).abs() < 1e-9);
+    ///
+    /// // Switching ends flips the sign of both errors.
+    /// let (distance, cross_track) = ApproachDirection::Backwards.errors(10.0, 91.0.deg());
+    /// assert_eq!(distance, -10.0);
+    /// assert!((cross_track + after).abs() < 1e-9);
+    /// ```
+    #[must_use]
+    pub fn errors(self, distance: f64, angle_error: Angle) -> (f64, f64) {
+        let cross_track = distance * angle_error.sin();
+
+        match self {
+            Self::Forwards => (distance, cross_track),
+            Self::Backwards => (-distance, -cross_track),
+        }
+    }
+}
@@ -1,30 +1,40 @@
 use std::{future::Future, pin::Pin, task::Poll, time::{Duration, Instant}};
 
-use vexide::time::{Sleep, sleep};
-
 use evian_control::{
     Tolerances,
     loops::{AngularPid, Feedback, Pid},
 };
 use evian_drivetrain::{Drivetrain, model::Arcade};
-use evian_math::{Angle, IntoAngle, Vec2};
+use evian_math::{Angle, Vec2};
 use evian_tracking::{TracksHeading, TracksPosition, TracksVelocity};
 
-pub struct State {
-    sleep: Sleep,
+use crate::brake::ActiveBrake;
+use crate::checked::{CheckedMotion, ErrorSlot};
+use crate::clock::{Clock, VexideClock};
+use crate::telemetry::{
+    MotionTelemetry, PoseBuffer, PoseRecorder, PoseSample, SettleCondition, TickCallback,
+    is_settled,
+};
+
+pub struct State<S> {
+    sleep: S,
     prev_time: Instant,
     start_time: Instant,
+    brake: Option<ActiveBrake>,
     prev_position: Vec2<f64>,
+    progress: f64,
+    settling_progress: f64,
 }
 
 /// Boomerang move-to-pose algorithm.
 #[must_use = "futures do nothing unless you `.await` or poll them"]
-pub struct BoomerangFuture<'a, M, L, A, T>
+pub struct BoomerangFuture<'a, M, L, A, T, C = VexideClock>
 where
     M: Arcade,
     L: Feedback<State = f64, Signal = f64> + Unpin,
     A: Feedback<State = Angle, Signal = f64> + Unpin,
     T: TracksPosition + TracksHeading + TracksVelocity,
+    C: Clock,
 {
     pub(crate) target_point: Vec2<f64>,
     pub(crate) target_heading: Angle,
@@ -34,18 +44,25 @@ where
     pub(crate) linear_controller: L,
     pub(crate) angular_controller: A,
     pub(crate) drivetrain: &'a mut Drivetrain<M, T>,
-
-    pub(crate) state: Option<State>,
+    pub(crate) settle_condition: Option<SettleCondition<'a>>,
+    pub(crate) active_brake: bool,
+    pub(crate) pose_recorder: Option<PoseRecorder<'a, T>>,
+    pub(crate) tick: Option<TickCallback<'a, T>>,
+    pub(crate) error: ErrorSlot<M::Error>,
+
+    pub(crate) clock: C,
+    pub(crate) state: Option<State<C::Sleep>>,
 }
 
 // MARK: Future Poll
 
-impl<M, L, A, T> Future for BoomerangFuture<'_, M, L, A, T>
+impl<M, L, A, T, C> Future for BoomerangFuture<'_, M, L, A, T, C>
 where
     M: Arcade,
     L: Feedback<State = f64, Signal = f64> + Unpin,
     A: Feedback<State = Angle, Signal = f64> + Unpin,
     T: TracksPosition + TracksHeading + TracksVelocity,
+    C: Clock,
 {
     type Output = ();
 
@@ -55,12 +72,15 @@ where
     ) -> Poll<Self::Output> {
         let this = self.get_mut();
         let state = this.state.get_or_insert_with(|| {
-            let now = Instant::now();
+            let now = this.clock.now();
             State {
-                sleep: sleep(Duration::from_millis(5)),
+                sleep: this.clock.sleep(Duration::from_millis(5)),
                 prev_position: this.drivetrain.tracking.position(),
                 start_time: now,
                 prev_time: now,
+                brake: None,
+                progress: 0.0,
+                settling_progress: 0.0,
             }
         });
 
@@ -68,7 +88,22 @@ where
             return Poll::Pending;
         }
 
-        let dt = state.prev_time.elapsed();
+        if let Some(recorder) = &mut this.pose_recorder {
+            recorder.record(&this.drivetrain.tracking, this.clock.now());
+        }
+
+        if let Some(tick) = &mut this.tick {
+            tick.call(&this.drivetrain.tracking, this.clock.now());
+        }
+
+        if crate::pause_if_disabled(&this.clock, &mut state.sleep, cx, || {
+            this.error.record(this.drivetrain.model.drive_arcade(0.0, 0.0));
+        }) {
+            state.prev_time = this.clock.now();
+            return Poll::Pending;
+        }
+
+        let dt = this.clock.elapsed(state.prev_time);
 
         let position = this.drivetrain.tracking.position();
         let heading = this.drivetrain.tracking.heading();
@@ -81,19 +116,60 @@ where
 
         let local_target = carrot - position;
 
-        let angular_error = (heading - local_target.angle().rad()).wrapped_half();
+        let angular_error = (heading - local_target.angle()).wrapped_half();
         let linear_error = local_target.length();
 
         let close = linear_error < 7.5;
 
-        if this
-            .tolerances
-            .check(linear_error, this.drivetrain.tracking.linear_velocity())
+        let linear_velocity = this.drivetrain.tracking.linear_velocity();
+        let linear_check = this.tolerances.check(linear_error, linear_velocity, this.clock.now());
+        state.progress = this.tolerances.progress(linear_error);
+        state.settling_progress = this.tolerances.settling_progress(&linear_check);
+
+        // Once we've started braking, we've already settled.
+        let settled = state.brake.is_some()
+            || is_settled(
+                this.settle_condition.as_mut(),
+                linear_check.is_settled(),
+                || MotionTelemetry {
+                    elapsed: this.clock.elapsed(state.start_time),
+                    linear_error,
+                    angular_error: Some(angular_error),
+                    linear_velocity,
+                    angular_velocity: this.drivetrain.tracking.angular_velocity(),
+                    linear_tolerances: Some(linear_check),
+                    angular_tolerances: None,
+                },
+            );
+
+        if settled && this.active_brake {
+            let brake = state
+                .brake
+                .get_or_insert_with(|| ActiveBrake::new(this.clock.now()));
+
+            if let Some((throttle, steer)) = brake.update(
+                this.clock.now(),
+                linear_velocity,
+                this.drivetrain.tracking.angular_velocity(),
+                this.tolerances.velocity_tolerance,
+                None,
+            ) {
+                this.error.record(this.drivetrain.model.drive_arcade(throttle, steer));
+
+                state.sleep = this.clock.sleep(Duration::from_millis(5));
+                state.prev_time = this.clock.now();
+
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+        }
+
+        if settled
             || this
                 .timeout
-                .is_some_and(|timeout| state.start_time.elapsed() > timeout)
+                .is_some_and(|timeout| this.clock.elapsed(state.start_time) > timeout)
         {
-            drop(this.drivetrain.model.drive_arcade(0.0, 0.0));
+            this.error.record(this.drivetrain.model.drive_arcade(0.0, 0.0));
             return Poll::Ready(());
         }
 
@@ -106,13 +182,13 @@ where
         let linear_output =
             this.linear_controller.update(-linear_error, 0.0, dt) * angular_error.cos();
 
-        drop(this
+        this.error.record(this
             .drivetrain
             .model
             .drive_arcade(linear_output, angular_output));
 
-        state.sleep = sleep(Duration::from_millis(5));
-        state.prev_time = Instant::now();
+        state.sleep = this.clock.sleep(Duration::from_millis(5));
+        state.prev_time = this.clock.now();
         state.prev_position = position;
 
         cx.waker().wake_by_ref();
@@ -120,23 +196,80 @@ where
     }
 }
 
+impl<M, L, A, T, C> CheckedMotion for BoomerangFuture<'_, M, L, A, T, C>
+where
+    M: Arcade,
+    L: Feedback<State = f64, Signal = f64> + Unpin,
+    A: Feedback<State = Angle, Signal = f64> + Unpin,
+    T: TracksPosition + TracksHeading + TracksVelocity,
+    C: Clock,
+{
+    type Error = M::Error;
+
+    fn take_error(&mut self) -> Option<M::Error> {
+        self.error.take()
+    }
+}
+
+// MARK: Progress
+
+impl<M, L, A, T, C> BoomerangFuture<'_, M, L, A, T, C>
+where
+    M: Arcade,
+    L: Feedback<State = f64, Signal = f64> + Unpin,
+    A: Feedback<State = Angle, Signal = f64> + Unpin,
+    T: TracksPosition + TracksHeading + TracksVelocity,
+    C: Clock,
+{
+    /// Estimates how close this motion is to reaching its target, from `0.0` to `1.0`.
+    ///
+    /// This is the fraction of the initial distance to the target that has been covered as of the
+    /// motion's most recent update (see [`Tolerances::progress`]). Before the motion is first
+    /// polled, this returns `0.0`. The robot's heading isn't accounted for.
+    ///
+    /// Like [`DriveFuture::progress`](crate::basic::DriveFuture::progress), this isn't
+    /// monotonic and moves backwards whenever the error grows.
+    #[must_use]
+    pub fn progress(&self) -> f64 {
+        self.state.as_ref().map_or(0.0, |state| state.progress)
+    }
+
+    /// Returns how much of its tolerance duration this motion has spent in tolerance, from `0.0`
+    /// to `1.0`.
+    ///
+    /// This is taken from the motion's settling timer as of its most recent update (see
+    /// [`Tolerances::settling_progress`]), and drops back to `0.0` whenever the robot leaves
+    /// tolerance.
+    #[must_use]
+    pub fn settling_progress(&self) -> f64 {
+        self.state.as_ref().map_or(0.0, |state| state.settling_progress)
+    }
+}
+
 // MARK: Generic Modifiers
 
-impl<M, L, A, T> BoomerangFuture<'_, M, L, A, T>
+impl<'a, M, L, A, T, C> BoomerangFuture<'a, M, L, A, T, C>
 where
     M: Arcade,
     L: Feedback<State = f64, Signal = f64> + Unpin,
     A: Feedback<State = Angle, Signal = f64> + Unpin,
     T: TracksPosition + TracksHeading + TracksVelocity,
+    C: Clock,
 {
     /// Modifies this motion's linear feedback controller.
-    pub fn with_linear_controller(&mut self, controller: L) -> &mut Self {
+    ///
+    /// The controller is [reset](Feedback::reset) before it is used.
+    pub fn with_linear_controller(&mut self, mut controller: L) -> &mut Self {
+        controller.reset();
         self.linear_controller = controller;
         self
     }
 
     /// Modifies this motion's angular feedback controller.
-    pub fn with_angular_controller(&mut self, controller: A) -> &mut Self {
+    ///
+    /// The controller is [reset](Feedback::reset) before it is used.
+    pub fn with_angular_controller(&mut self, mut controller: A) -> &mut Self {
+        controller.reset();
         self.angular_controller = controller;
         self
     }
@@ -153,6 +286,96 @@ where
         self
     }
 
+    /// Replaces the [`Clock`] used by this motion to keep time.
+    ///
+    /// See [`DriveFuture::with_clock`] for more information.
+    ///
+    /// [`DriveFuture::with_clock`]: crate::basic::DriveFuture::with_clock
+    pub fn with_clock<C2: Clock>(self, clock: C2) -> BoomerangFuture<'a, M, L, A, T, C2> {
+        BoomerangFuture {
+            target_point: self.target_point,
+            target_heading: self.target_heading,
+            lead: self.lead,
+            timeout: self.timeout,
+            tolerances: self.tolerances,
+            linear_controller: self.linear_controller,
+            angular_controller: self.angular_controller,
+            drivetrain: self.drivetrain,
+            settle_condition: self.settle_condition,
+            active_brake: self.active_brake,
+            pose_recorder: self.pose_recorder,
+            tick: self.tick,
+            error: ErrorSlot::new(),
+            clock,
+            state: None,
+        }
+    }
+
+    /// Records the robot's pose into a [`PoseBuffer`] on every update of this motion.
+    pub fn record_into(&mut self, buffer: &'a mut PoseBuffer) -> &mut Self {
+        self.pose_recorder = Some(PoseRecorder::new(buffer));
+        self
+    }
+
+    /// Calls `callback` with the robot's pose on every update of this motion.
+    ///
+    /// The callback must return quickly and must not block. See [`DriveFuture::with_tick`] for more
+    /// information.
+    ///
+    /// [`DriveFuture::with_tick`]: crate::basic::DriveFuture::with_tick
+    pub fn with_tick(&mut self, callback: impl FnMut(&PoseSample) + 'a) -> &mut Self {
+        self.tick = Some(TickCallback::new(callback));
+        self
+    }
+
+    /// Removes this motion's tick callback.
+    pub fn without_tick(&mut self) -> &mut Self {
+        self.tick = None;
+        self
+    }
+
+    /// Adds a custom settling condition to this motion.
+    ///
+    /// See [`DriveFuture::with_settle_when`] for more information.
+    ///
+    /// [`DriveFuture::with_settle_when`]: crate::basic::DriveFuture::with_settle_when
+    pub fn with_settle_when(
+        &mut self,
+        predicate: impl FnMut(&MotionTelemetry) -> bool + 'a,
+    ) -> &mut Self {
+        self.settle_condition = Some(SettleCondition::new(predicate, false));
+        self
+    }
+
+    /// Replaces this motion's tolerances with a custom settling condition.
+    ///
+    /// See [`DriveFuture::with_settle_only_when`] for more information.
+    ///
+    /// [`DriveFuture::with_settle_only_when`]: crate::basic::DriveFuture::with_settle_only_when
+    pub fn with_settle_only_when(
+        &mut self,
+        predicate: impl FnMut(&MotionTelemetry) -> bool + 'a,
+    ) -> &mut Self {
+        self.settle_condition = Some(SettleCondition::new(predicate, true));
+        self
+    }
+
+    /// Removes this motion's custom settling condition.
+    pub fn without_settle_condition(&mut self) -> &mut Self {
+        self.settle_condition = None;
+        self
+    }
+
+    /// Enables or disables active braking once this motion settles.
+    ///
+    /// See [`DriveFuture::with_active_brake`] for more information.
+    ///
+    /// [`DriveFuture::with_active_brake`]: crate::basic::DriveFuture::with_active_brake
+    pub const fn with_active_brake(&mut self, enabled: bool) -> &mut Self {
+        self.active_brake = enabled;
+        self
+    }
+
     /// Modifies this motion's tolerances.
     pub const fn with_tolerances(&mut self, tolerances: Tolerances) -> &mut Self {
         self.tolerances = tolerances;
@@ -194,15 +417,25 @@ where
         self.tolerances.duration = None;
         self
     }
+
+    /// Modifies how many consecutive out-of-tolerance checks this motion allows before its
+    /// settling timer resets.
+    ///
+    /// See [`Tolerances::debounce`] for more information.
+    pub const fn with_settle_debounce(&mut self, debounce: u32) -> &mut Self {
+        self.tolerances.debounce = debounce;
+        self
+    }
 }
 
 // MARK: Linear PID Modifiers
 
-impl<M, A, T> BoomerangFuture<'_, M, Pid, A, T>
+impl<M, A, T, C> BoomerangFuture<'_, M, Pid, A, T, C>
 where
     M: Arcade,
     A: Feedback<State = Angle, Signal = f64> + Unpin,
     T: TracksPosition + TracksHeading + TracksVelocity,
+    C: Clock,
 {
     /// Modifies this motion's linear PID gains.
     pub const fn with_linear_gains(&mut self, kp: f64, ki: f64, kd: f64) -> &mut Self {
@@ -256,11 +489,12 @@ where
 
 // MARK: Angular PID Modifiers
 
-impl<M, L, T> BoomerangFuture<'_, M, L, AngularPid, T>
+impl<M, L, T, C> BoomerangFuture<'_, M, L, AngularPid, T, C>
 where
     M: Arcade,
     L: Feedback<State = f64, Signal = f64> + Unpin,
     T: TracksPosition + TracksHeading + TracksVelocity,
+    C: Clock,
 {
     /// Modifies this motion's angular PID gains.
     pub const fn with_angular_gains(&mut self, kp: f64, ki: f64, kd: f64) -> &mut Self {
@@ -3,13 +3,18 @@
 use std::time::Duration;
 
 use evian_control::{Tolerances, loops::Feedback};
-use evian_drivetrain::{Drivetrain, model::Arcade};
+use evian_drivetrain::{
+    Drivetrain,
+    model::{Arcade, Tank},
+};
 use evian_math::Vec2;
 use evian_tracking::{TracksHeading, TracksPosition, TracksVelocity};
 
+mod approach;
 mod boomerang;
 mod move_to_point;
 
+pub use approach::ApproachDirection;
 pub use boomerang::BoomerangFuture;
 pub use move_to_point::MoveToPointFuture;
 
@@ -21,7 +26,15 @@ pub use move_to_point::MoveToPointFuture;
 ///
 /// Seeking motions include:
 /// - [`move_to_point`](Seeking::move_to_point), which moves the drivetrain to a desired point.
+/// - [`move_to_point_tank`](Seeking::move_to_point_tank), which moves a tank drivetrain to a
+///   desired point using differential kinematics.
 /// - [`boomerang`](Seeking::move_to_point), which moves the drivetrain to a desired pose (including heading).
+///
+/// # Controller State
+///
+/// Each motion started from a `Seeking` runs on its own freshly [reset](Feedback::reset) copy of
+/// the stored controllers, so no state (such as a PID's integral) carries over between motions.
+/// Modifiers on the returned motion only change that motion's copy.
 #[derive(PartialEq)]
 pub struct Seeking<L, A>
 where
@@ -58,16 +71,38 @@ where
         drivetrain: &'a mut Drivetrain<M, T>,
         point: impl Into<Vec2<f64>>,
     ) -> MoveToPointFuture<'a, M, L, A, T> {
-        MoveToPointFuture {
+        let mut future = MoveToPointFuture::new(
             drivetrain,
-            reverse: false,
-            target_point: point.into(),
-            timeout: self.timeout,
-            tolerances: self.tolerances,
-            linear_controller: self.linear_controller.clone(),
-            lateral_controller: self.lateral_controller.clone(),
-            state: None,
-        }
+            point,
+            crate::fresh_controller(&self.linear_controller),
+            crate::fresh_controller(&self.lateral_controller),
+        );
+        future.timeout = self.timeout;
+        future.tolerances = self.tolerances;
+
+        future
+    }
+
+    /// Moves the robot to a 2D point, driving each side of the drivetrain using differential
+    /// kinematics.
+    ///
+    /// Rather than mixing the controllers' outputs as arcade throttle and steering, they're
+    /// treated as linear and angular velocities and converted into left and right wheel
+    /// velocities using the drivetrain's `track_width`. See
+    /// [`MoveToPointFuture::with_tank_kinematics`] for more information.
+    ///
+    /// The final heading of the robot after this motion executes is undefined.
+    pub fn move_to_point_tank<'a, M: Tank, T: TracksPosition + TracksHeading + TracksVelocity>(
+        &mut self,
+        drivetrain: &'a mut Drivetrain<M, T>,
+        point: impl Into<Vec2<f64>>,
+        track_width: f64,
+        max_wheel_velocity: f64,
+    ) -> MoveToPointFuture<'a, M, L, A, T> {
+        let mut future = self.move_to_point(drivetrain, point);
+        future.with_tank_kinematics(track_width, max_wheel_velocity);
+
+        future
     }
 
     // /// Moves the robot to a desired pose (position and heading).
@@ -93,6 +128,11 @@ where
     //         tolerances: self.tolerances,
     //         linear_controller: self.linear_controller.clone(),
     //         angular_controller: self.angular_controller.clone(),
+    //         settle_condition: None,
+    //         active_brake: false,
+    //         pose_recorder: None,
+    //         tick: None,
+    //         error: ErrorSlot::new(),
     //         state: None,
     //     }
     // }
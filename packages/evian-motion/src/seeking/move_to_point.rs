@@ -1,54 +1,273 @@
 use std::{
-    f64::consts::FRAC_PI_2,
     future::Future,
     pin::Pin,
     task::Poll,
     time::{Duration, Instant},
 };
 
-use vexide::time::{Sleep, sleep};
-
 use evian_control::{
     Tolerances,
     loops::{Feedback, Pid},
 };
-use evian_drivetrain::{Drivetrain, model::Arcade};
-use evian_math::{IntoAngle, Vec2};
+use evian_drivetrain::{
+    Drivetrain,
+    model::{Arcade, DrivetrainModel, Tank},
+};
+use evian_math::{Angle, Vec2, desaturate};
 use evian_tracking::{TracksHeading, TracksPosition, TracksVelocity};
 
-pub(crate) struct State {
-    sleep: Sleep,
+use super::ApproachDirection;
+use crate::brake::ActiveBrake;
+use crate::checked::{CheckedMotion, ErrorSlot};
+use crate::clock::{Clock, VexideClock};
+use crate::outcome::{MotionOutcome, SettlingMotion};
+use crate::telemetry::{
+    MotionTelemetry, PoseBuffer, PoseRecorder, PoseSample, SettleCondition, TickCallback,
+    is_settled,
+};
+
+pub(crate) struct State<S> {
+    sleep: S,
     prev_time: Instant,
     start_time: Instant,
+    brake: Option<ActiveBrake>,
+    direction: Option<ApproachDirection>,
+    progress: f64,
+    settling_progress: f64,
+}
+
+/// How far past 90° a target must be before the robot switches which end it approaches with.
+const APPROACH_HYSTERESIS: Angle = Angle::from_degrees(10.0);
+
+/// Differential kinematics used to drive a [`MoveToPointFuture`] through [`Tank`] rather than
+/// arcade mixing.
+pub(crate) struct TankKinematics<M: DrivetrainModel> {
+    track_width: f64,
+    max_wheel_velocity: f64,
+    drive_tank: fn(&mut M, f64, f64) -> Result<(), M::Error>,
+}
+
+impl<M: DrivetrainModel> TankKinematics<M> {
+    /// Drives the robot at a linear velocity (in wheel units per second) and a clockwise angular
+    /// velocity (in radians per second).
+    ///
+    /// Each side's velocity is converted to a normalized power using the drivetrain's maximum
+    /// wheel velocity, then desaturated so that the ratio between each side is preserved.
+    fn drive(&self, model: &mut M, linear: f64, angular: f64) -> Result<(), M::Error> {
+        let offset = angular * self.track_width / 2.0;
+        let [left, right] = desaturate(
+            [
+                (linear + offset) / self.max_wheel_velocity,
+                (linear - offset) / self.max_wheel_velocity,
+            ],
+            1.0,
+        );
+
+        (self.drive_tank)(model, left, right)
+    }
 }
 
 /// Moves the robot to a point using two seeking feedback controllers.
+///
+/// # Tuning the Lateral Controller
+///
+/// The lateral controller is driven by the robot's cross-track error (how far the target is to the
+/// side of the robot's direction of travel), and is updated with a setpoint of zero. Start by
+/// tuning its proportional and derivative gains until the robot tracks towards the target without
+/// oscillating.
+///
+/// Steady disturbances such as uneven floors or imbalanced drive motors will leave a standing
+/// cross-track error that proportional and derivative terms can't fully correct. A small integral
+/// gain can be added to remove this error, but should be paired with an integration range (see
+/// [`with_lateral_integration_range`](Self::with_lateral_integration_range)) so that the integral
+/// only accumulates once the robot is close to its line, rather than winding up while the robot
+/// first turns towards the target. The integral is also cleared whenever the cross-track error
+/// changes sign.
+///
+/// The example below simulates a robot whose drive motors are imbalanced, pulling it to one side
+/// as it drives towards a target 200 inches ahead:
+///
+/// ```
+/// use std::pin::pin;
+///
+/// use evian_control::{Tolerances, loops::Pid};
+/// use evian_drivetrain::Drivetrain;
+/// use evian_math::Vec2;
+/// use evian_motion::{
+///     Seeking,
+///     clock::ManualClock,
+///     sim::{self, SimRobot},
+/// };
+///
+/// let target = Vec2::new(200.0, 0.0);
+///
+/// // Returns the robot's cross-track error after each of its first 8 seconds of driving.
+/// let cross_track_errors = |lateral_controller: Pid| {
+///     let robot = SimRobot::new().with_max_speed(20.0).with_steer_bias(0.05);
+///     let mut drivetrain = Drivetrain::new(robot.clone(), robot.clone());
+///     let mut seeking = Seeking {
+///         linear_controller: Pid::new(0.5, 0.0, 0.0, None),
+///         lateral_controller,
+///         tolerances: Tolerances::new().error(0.5),
+///         timeout: None,
+///     };
+///
+///     let clock = ManualClock::new();
+///     let mut motion = pin!(
+///         seeking
+///             .move_to_point(&mut drivetrain, target)
+///             .with_clock(clock.clone())
+///     );
+///     for _ in 0..=1600 {
+///         _ = sim::step(motion.as_mut(), &clock);
+///     }
+///
+///     robot
+///         .history()
+///         .iter()
+///         .step_by(200)
+///         .map(|sample| {
+///             let (distance, bearing) = (target - sample.position).to_polar();
+///             distance * (bearing - sample.heading).sin()
+///         })
+///         .collect::<Vec<_>>()
+/// };
+///
+/// // Proportional control alone leaves the robot driving alongside its line...
+/// let errors = cross_track_errors(Pid::new(0.1, 0.0, 0.0, None));
+/// assert!(errors[4..].iter().all(|error| (error - 0.5).abs() < 0.05));
+///
+/// // ...while the integral steers it back onto the line.
+/// let errors = cross_track_errors(Pid::new(0.1, 0.05, 0.0, Some(2.0)));
+/// assert!(errors[2..].windows(2).all(|pair| pair[1].abs() < pair[0].abs()));
+/// assert!(errors.last().unwrap().abs() < 0.01);
+/// ```
+///
+/// # Tank Kinematics
+///
+/// By default, the controllers' outputs are passed to [`Arcade::drive_arcade`] as normalized
+/// throttle and steering powers. On a differential drivetrain whose maximum wheel velocity has
+/// been characterized, [`with_tank_kinematics`](Self::with_tank_kinematics) can be used to
+/// instead treat them as velocities, which are converted to left and right wheel velocities
+/// using the drivetrain's track width.
+///
+/// # Targets Behind the Robot
+///
+/// A target more than 90° from the robot's heading is approached in reverse rather than by
+/// turning around. To avoid flipping back and forth when the target is nearly perpendicular to
+/// the robot, the robot only switches ends once the target is 10° past perpendicular, and both
+/// controllers are reset when it does. See [`ApproachDirection`] for more information.
+///
+/// A [reversed](Self::reverse) motion always approaches its target backwards, even when the
+/// target is in front of the robot.
 #[must_use = "futures do nothing unless you `.await` or poll them"]
-pub struct MoveToPointFuture<'a, M, L, A, T>
+pub struct MoveToPointFuture<'a, M, L, A, T, C = VexideClock>
 where
     M: Arcade,
     L: Feedback<State = f64, Signal = f64> + Unpin,
     A: Feedback<State = f64, Signal = f64> + Unpin,
     T: TracksPosition + TracksHeading + TracksVelocity,
+    C: Clock,
 {
     pub(crate) target_point: Vec2<f64>,
     pub(crate) reverse: bool,
     pub(crate) timeout: Option<Duration>,
     pub(crate) tolerances: Tolerances,
+    pub(crate) min_linear_speed: Option<f64>,
+    pub(crate) slowdown_radius: Option<f64>,
+    pub(crate) max_curvature: Option<f64>,
+    pub(crate) heading_lock_radius: Option<f64>,
     pub(crate) linear_controller: L,
     pub(crate) lateral_controller: A,
     pub(crate) drivetrain: &'a mut Drivetrain<M, T>,
-    pub(crate) state: Option<State>,
+    pub(crate) settle_condition: Option<SettleCondition<'a>>,
+    pub(crate) active_brake: bool,
+    pub(crate) pose_recorder: Option<PoseRecorder<'a, T>>,
+    pub(crate) tick: Option<TickCallback<'a, T>>,
+    pub(crate) tank_kinematics: Option<TankKinematics<M>>,
+    pub(crate) error: ErrorSlot<M::Error>,
+    pub(crate) outcome: Option<MotionOutcome>,
+    pub(crate) clock: C,
+    pub(crate) state: Option<State<C::Sleep>>,
+}
+
+// MARK: Constructor
+
+impl<'a, M, L, A, T> MoveToPointFuture<'a, M, L, A, T>
+where
+    M: Arcade,
+    L: Feedback<State = f64, Signal = f64> + Unpin,
+    A: Feedback<State = f64, Signal = f64> + Unpin,
+    T: TracksPosition + TracksHeading + TracksVelocity,
+{
+    /// Default tolerances used by [`MoveToPointFuture::new`].
+    pub const DEFAULT_TOLERANCES: Tolerances = Tolerances::new()
+        .error(4.0)
+        .velocity(0.25)
+        .duration(Duration::from_millis(15));
+
+    /// Default timeout used by [`MoveToPointFuture::new`].
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// Creates a motion that moves the robot to a 2D point using the given feedback
+    /// controllers.
+    ///
+    /// This allows for one-off motions without storing a [`Seeking`](crate::Seeking)
+    /// configuration. The motion uses [`Self::DEFAULT_TOLERANCES`] and [`Self::DEFAULT_TIMEOUT`],
+    /// both of which can be changed using this future's modifiers.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// MoveToPointFuture::new(
+    ///     &mut drivetrain,
+    ///     (24.0, 24.0),
+    ///     Pid::new(0.5, 0.0, 0.1, None),
+    ///     Pid::new(1.0, 0.0, 0.0, None),
+    /// )
+    /// .with_timeout(Duration::from_secs(2))
+    /// .await;
+    /// ```
+    pub fn new(
+        drivetrain: &'a mut Drivetrain<M, T>,
+        point: impl Into<Vec2<f64>>,
+        linear_controller: L,
+        lateral_controller: A,
+    ) -> Self {
+        Self {
+            drivetrain,
+            reverse: false,
+            target_point: point.into(),
+            timeout: Some(Self::DEFAULT_TIMEOUT),
+            tolerances: Self::DEFAULT_TOLERANCES,
+            min_linear_speed: None,
+            slowdown_radius: None,
+            max_curvature: None,
+            heading_lock_radius: None,
+            linear_controller,
+            lateral_controller,
+            settle_condition: None,
+            active_brake: false,
+            pose_recorder: None,
+            tick: None,
+            tank_kinematics: None,
+            error: ErrorSlot::new(),
+            outcome: None,
+            clock: VexideClock,
+            state: None,
+        }
+    }
 }
 
 // MARK: Future Poll
 
-impl<M, L, A, T> Future for MoveToPointFuture<'_, M, L, A, T>
+impl<M, L, A, T, C> Future for MoveToPointFuture<'_, M, L, A, T, C>
 where
     M: Arcade,
     L: Feedback<State = f64, Signal = f64> + Unpin,
     A: Feedback<State = f64, Signal = f64> + Unpin,
     T: TracksPosition + TracksHeading + TracksVelocity,
+    C: Clock,
 {
     type Output = ();
 
@@ -58,12 +277,16 @@ where
     ) -> Poll<Self::Output> {
         let this = self.get_mut();
         let state = this.state.get_or_insert_with(|| {
-            let now = Instant::now();
+            let now = this.clock.now();
 
             State {
-                sleep: sleep(Duration::from_millis(5)),
+                sleep: this.clock.sleep(Duration::from_millis(5)),
                 start_time: now,
                 prev_time: now,
+                brake: None,
+                direction: None,
+                progress: 0.0,
+                settling_progress: 0.0,
             }
         });
 
@@ -71,73 +294,262 @@ where
             return Poll::Pending;
         }
 
-        let dt = state.prev_time.elapsed();
+        if let Some(recorder) = &mut this.pose_recorder {
+            recorder.record(&this.drivetrain.tracking, this.clock.now());
+        }
+
+        if let Some(tick) = &mut this.tick {
+            tick.call(&this.drivetrain.tracking, this.clock.now());
+        }
+
+        if crate::pause_if_disabled(&this.clock, &mut state.sleep, cx, || {
+            this.error.record(this.drivetrain.model.drive_arcade(0.0, 0.0));
+        }) {
+            state.prev_time = this.clock.now();
+            return Poll::Pending;
+        }
+
+        let dt = this.clock.elapsed(state.prev_time);
 
         let position = this.drivetrain.tracking.position();
         let heading = this.drivetrain.tracking.heading();
 
         let local_target = this.target_point - position;
-        let mut distance_error = local_target.length();
-
-        if this
-            .tolerances
-            .check(distance_error, this.drivetrain.tracking.linear_velocity())
-            || this
-                .timeout
-                .is_some_and(|timeout| state.start_time.elapsed() > timeout)
-        {
-            drop(this.drivetrain.model.drive_arcade(0.0, 0.0));
+        let distance = local_target.length();
+        let angle_error = (heading - local_target.angle()).wrapped_half();
+
+        let linear_velocity = this.drivetrain.tracking.linear_velocity();
+        let linear_check = this.tolerances.check(distance, linear_velocity, this.clock.now());
+        state.progress = this.tolerances.progress(distance);
+        state.settling_progress = this.tolerances.settling_progress(&linear_check);
+
+        // Once we've started braking, we've already settled.
+        let settled = state.brake.is_some()
+            || is_settled(
+                this.settle_condition.as_mut(),
+                linear_check.is_settled(),
+                || MotionTelemetry {
+                    elapsed: this.clock.elapsed(state.start_time),
+                    linear_error: distance,
+                    angular_error: Some(angle_error),
+                    linear_velocity,
+                    angular_velocity: this.drivetrain.tracking.angular_velocity(),
+                    linear_tolerances: Some(linear_check),
+                    angular_tolerances: None,
+                },
+            );
+
+        if settled && this.active_brake {
+            let brake = state
+                .brake
+                .get_or_insert_with(|| ActiveBrake::new(this.clock.now()));
+
+            if let Some((throttle, steer)) = brake.update(
+                this.clock.now(),
+                linear_velocity,
+                this.drivetrain.tracking.angular_velocity(),
+                this.tolerances.velocity_tolerance,
+                None,
+            ) {
+                this.error.record(this.drivetrain.model.drive_arcade(throttle, steer));
+
+                state.sleep = this.clock.sleep(Duration::from_millis(5));
+                state.prev_time = this.clock.now();
+
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+        }
+
+        let timed_out = this
+            .timeout
+            .is_some_and(|timeout| this.clock.elapsed(state.start_time) > timeout);
+
+        if settled || timed_out {
+            this.outcome = Some(if settled {
+                MotionOutcome::Settled
+            } else {
+                MotionOutcome::TimedOut
+            });
+            this.error.record(this.drivetrain.model.drive_arcade(0.0, 0.0));
             return Poll::Ready(());
         }
 
-        let angle_error = (heading - local_target.angle().rad()).wrapped_half();
-        let mut projected_cte = distance_error * angle_error.sin();
+        // Reverse towards targets behind the robot (or always, if this motion is reversed).
+        // Switching ends flips the sign of both errors, so the switch uses hysteresis to avoid
+        // flipping back and forth when the target is nearly perpendicular, and clears any
+        // controller state built up for the other end.
+        let direction = ApproachDirection::select(
+            state.direction,
+            this.reverse,
+            angle_error,
+            APPROACH_HYSTERESIS,
+        );
+        if state.direction.is_some_and(|prev| prev != direction) {
+            this.linear_controller.reset();
+            this.lateral_controller.reset();
+        }
+        state.direction = Some(direction);
 
-        if angle_error.as_radians().abs() > FRAC_PI_2 {
-            projected_cte *= -1.0;
-            distance_error *= -1.0;
+        let (distance_error, projected_cte) = direction.errors(distance, angle_error);
+
+        // Close to the target, the bearing to it swings wildly with small changes in position,
+        // so stop steering and drive straight in.
+        let mut angular_output = if this
+            .heading_lock_radius
+            .is_some_and(|radius| distance_error.abs() < radius)
+        {
+            0.0
+        } else {
+            this.lateral_controller.update(projected_cte, 0.0, dt)
+        };
+        let mut linear_output = this.linear_controller.update(-distance_error, 0.0, dt);
+
+        // Within the slowdown radius, cap our speed proportionally to how close we are to the
+        // target to prevent abrupt stops.
+        if let Some(radius) = this.slowdown_radius
+            && distance_error.abs() < radius
+        {
+            let full_speed = this
+                .tank_kinematics
+                .as_ref()
+                .map_or(1.0, |kinematics| kinematics.max_wheel_velocity);
+            let cap = full_speed * distance_error.abs() / radius;
+            linear_output = linear_output.clamp(-cap, cap);
         }
 
-        let angular_output = this.lateral_controller.update(projected_cte, 0.0, dt);
-        let linear_output = this.linear_controller.update(-distance_error, 0.0, dt) * angle_error.cos().abs();
+        if let Some(max_curvature) = this.max_curvature {
+            // Rather than slowing down to turn towards the target, keep driving and limit how
+            // sharply we're allowed to turn at our current speed, producing an arc.
+            let max_angular = max_curvature * linear_output.abs();
+            angular_output = angular_output.clamp(-max_angular, max_angular);
+        } else {
+            linear_output *= angle_error.cos().abs();
+        }
 
-        drop(
-            this.drivetrain
-                .model
-                .drive_arcade(linear_output, angular_output),
-        );
+        // Keep moving at a minimum speed while outside of the error tolerance to prevent the
+        // robot from creeping towards the target. This is applied last, so that slowing down to
+        // turn can't scale the output back below the floor.
+        if let Some(min_speed) = this.min_linear_speed
+            && this
+                .tolerances
+                .error_tolerance
+                .is_none_or(|tolerance| distance_error.abs() >= tolerance)
+            && linear_output.abs() < min_speed
+        {
+            linear_output = min_speed.copysign(distance_error);
+        }
+
+        let model = &mut this.drivetrain.model;
+        this.error.record(match &this.tank_kinematics {
+            Some(kinematics) => kinematics.drive(model, linear_output, angular_output),
+            None => model.drive_arcade(linear_output, angular_output),
+        });
 
-        state.sleep = sleep(Duration::from_millis(5));
-        state.prev_time = Instant::now();
+        state.sleep = this.clock.sleep(Duration::from_millis(5));
+        state.prev_time = this.clock.now();
 
         cx.waker().wake_by_ref();
         Poll::Pending
     }
 }
 
+impl<M, L, A, T, C> CheckedMotion for MoveToPointFuture<'_, M, L, A, T, C>
+where
+    M: Arcade,
+    L: Feedback<State = f64, Signal = f64> + Unpin,
+    A: Feedback<State = f64, Signal = f64> + Unpin,
+    T: TracksPosition + TracksHeading + TracksVelocity,
+    C: Clock,
+{
+    type Error = M::Error;
+
+    fn take_error(&mut self) -> Option<M::Error> {
+        self.error.take()
+    }
+}
+
+impl<M, L, A, T, C> SettlingMotion for MoveToPointFuture<'_, M, L, A, T, C>
+where
+    M: Arcade,
+    L: Feedback<State = f64, Signal = f64> + Unpin,
+    A: Feedback<State = f64, Signal = f64> + Unpin,
+    T: TracksPosition + TracksHeading + TracksVelocity,
+    C: Clock,
+{
+    fn outcome(&self) -> Option<MotionOutcome> {
+        self.outcome
+    }
+}
+
+// MARK: Progress
+
+impl<M, L, A, T, C> MoveToPointFuture<'_, M, L, A, T, C>
+where
+    M: Arcade,
+    L: Feedback<State = f64, Signal = f64> + Unpin,
+    A: Feedback<State = f64, Signal = f64> + Unpin,
+    T: TracksPosition + TracksHeading + TracksVelocity,
+    C: Clock,
+{
+    /// Estimates how close this motion is to reaching its target, from `0.0` to `1.0`.
+    ///
+    /// This is the fraction of the initial distance to the target that has been covered as of the
+    /// motion's most recent update (see [`Tolerances::progress`]). Before the motion is first
+    /// polled, this returns `0.0`.
+    ///
+    /// Like [`DriveFuture::progress`](crate::basic::DriveFuture::progress), this isn't
+    /// monotonic and moves backwards whenever the error grows.
+    #[must_use]
+    pub fn progress(&self) -> f64 {
+        self.state.as_ref().map_or(0.0, |state| state.progress)
+    }
+
+    /// Returns how much of its tolerance duration this motion has spent in tolerance, from `0.0`
+    /// to `1.0`.
+    ///
+    /// This is taken from the motion's settling timer as of its most recent update (see
+    /// [`Tolerances::settling_progress`]), and drops back to `0.0` whenever the robot leaves
+    /// tolerance.
+    #[must_use]
+    pub fn settling_progress(&self) -> f64 {
+        self.state.as_ref().map_or(0.0, |state| state.settling_progress)
+    }
+}
+
 // MARK: Generic Modifiers
 
-impl<M, L, A, T> MoveToPointFuture<'_, M, L, A, T>
+impl<'a, M, L, A, T, C> MoveToPointFuture<'a, M, L, A, T, C>
 where
     M: Arcade,
     L: Feedback<State = f64, Signal = f64> + Unpin,
     A: Feedback<State = f64, Signal = f64> + Unpin,
     T: TracksPosition + TracksHeading + TracksVelocity,
+    C: Clock,
 {
     /// Reverses this motion, moving to the point backwards rather than forwards.
-    pub fn reverse(&mut self) -> &mut Self {
+    ///
+    /// When reversed, the robot will turn its rear towards the target point and drive
+    /// backwards to reach it.
+    pub const fn reverse(&mut self) -> &mut Self {
         self.reverse = true;
         self
     }
 
     /// Modifies this motion's linear feedback controller.
-    pub fn with_linear_controller(&mut self, controller: L) -> &mut Self {
+    ///
+    /// The controller is [reset](Feedback::reset) before it is used.
+    pub fn with_linear_controller(&mut self, mut controller: L) -> &mut Self {
+        controller.reset();
         self.linear_controller = controller;
         self
     }
 
     /// Modifies this motion's lateral feedback controller.
-    pub fn with_lateral_controller(&mut self, controller: A) -> &mut Self {
+    ///
+    /// The controller is [reset](Feedback::reset) before it is used.
+    pub fn with_lateral_controller(&mut self, mut controller: A) -> &mut Self {
+        controller.reset();
         self.lateral_controller = controller;
         self
     }
@@ -154,6 +566,189 @@ where
         self
     }
 
+    /// Replaces the [`Clock`] used by this motion to keep time.
+    ///
+    /// See [`DriveFuture::with_clock`] for more information.
+    ///
+    /// [`DriveFuture::with_clock`]: crate::basic::DriveFuture::with_clock
+    pub fn with_clock<C2: Clock>(self, clock: C2) -> MoveToPointFuture<'a, M, L, A, T, C2> {
+        MoveToPointFuture {
+            target_point: self.target_point,
+            reverse: self.reverse,
+            timeout: self.timeout,
+            tolerances: self.tolerances,
+            min_linear_speed: self.min_linear_speed,
+            slowdown_radius: self.slowdown_radius,
+            max_curvature: self.max_curvature,
+            heading_lock_radius: self.heading_lock_radius,
+            linear_controller: self.linear_controller,
+            lateral_controller: self.lateral_controller,
+            drivetrain: self.drivetrain,
+            settle_condition: self.settle_condition,
+            active_brake: self.active_brake,
+            pose_recorder: self.pose_recorder,
+            tick: self.tick,
+            tank_kinematics: self.tank_kinematics,
+            error: ErrorSlot::new(),
+            outcome: None,
+            clock,
+            state: None,
+        }
+    }
+
+    /// Records the robot's pose into a [`PoseBuffer`] on every update of this motion.
+    pub fn record_into(&mut self, buffer: &'a mut PoseBuffer) -> &mut Self {
+        self.pose_recorder = Some(PoseRecorder::new(buffer));
+        self
+    }
+
+    /// Calls `callback` with the robot's pose on every update of this motion.
+    ///
+    /// The callback must return quickly and must not block. See [`DriveFuture::with_tick`] for more
+    /// information.
+    ///
+    /// [`DriveFuture::with_tick`]: crate::basic::DriveFuture::with_tick
+    pub fn with_tick(&mut self, callback: impl FnMut(&PoseSample) + 'a) -> &mut Self {
+        self.tick = Some(TickCallback::new(callback));
+        self
+    }
+
+    /// Removes this motion's tick callback.
+    pub fn without_tick(&mut self) -> &mut Self {
+        self.tick = None;
+        self
+    }
+
+    /// Adds a custom settling condition to this motion.
+    ///
+    /// See [`DriveFuture::with_settle_when`] for more information.
+    ///
+    /// [`DriveFuture::with_settle_when`]: crate::basic::DriveFuture::with_settle_when
+    pub fn with_settle_when(
+        &mut self,
+        predicate: impl FnMut(&MotionTelemetry) -> bool + 'a,
+    ) -> &mut Self {
+        self.settle_condition = Some(SettleCondition::new(predicate, false));
+        self
+    }
+
+    /// Replaces this motion's tolerances with a custom settling condition.
+    ///
+    /// See [`DriveFuture::with_settle_only_when`] for more information.
+    ///
+    /// [`DriveFuture::with_settle_only_when`]: crate::basic::DriveFuture::with_settle_only_when
+    pub fn with_settle_only_when(
+        &mut self,
+        predicate: impl FnMut(&MotionTelemetry) -> bool + 'a,
+    ) -> &mut Self {
+        self.settle_condition = Some(SettleCondition::new(predicate, true));
+        self
+    }
+
+    /// Removes this motion's custom settling condition.
+    pub fn without_settle_condition(&mut self) -> &mut Self {
+        self.settle_condition = None;
+        self
+    }
+
+    /// Enables or disables active braking once this motion settles.
+    ///
+    /// See [`DriveFuture::with_active_brake`] for more information.
+    ///
+    /// [`DriveFuture::with_active_brake`]: crate::basic::DriveFuture::with_active_brake
+    pub const fn with_active_brake(&mut self, enabled: bool) -> &mut Self {
+        self.active_brake = enabled;
+        self
+    }
+
+    /// Modifies this motion's minimum linear speed.
+    ///
+    /// While the robot is outside of this motion's error tolerance, the linear output will never
+    /// drop below this speed, preventing the robot from creeping slowly towards the target as the
+    /// linear controller's output shrinks. The floor applies to the final linear output, after it
+    /// has been slowed down for turning towards the target. Once within the error tolerance (or
+    /// with no error tolerance, once settled), the floor is no longer applied. Setting this too
+    /// high relative to the error tolerance may cause the robot to overshoot the target.
+    pub const fn with_min_linear_speed(&mut self, speed: f64) -> &mut Self {
+        self.min_linear_speed = Some(speed);
+        self
+    }
+
+    /// Removes this motion's minimum linear speed.
+    pub const fn without_min_linear_speed(&mut self) -> &mut Self {
+        self.min_linear_speed = None;
+        self
+    }
+
+    /// Modifies this motion's slowdown radius.
+    ///
+    /// Within this distance of the target, the linear output is capped proportionally to the
+    /// remaining distance (reaching full speed at the edge of the radius), allowing the robot to
+    /// gradually slow down rather than stopping abruptly. Outside of the radius, the linear
+    /// output is left as-is.
+    pub const fn with_slowdown_radius(&mut self, radius: f64) -> &mut Self {
+        self.slowdown_radius = Some(radius);
+        self
+    }
+
+    /// Removes this motion's slowdown radius.
+    pub const fn without_slowdown_radius(&mut self) -> &mut Self {
+        self.slowdown_radius = None;
+        self
+    }
+
+    /// Limits the curvature of the path this motion drives towards its target.
+    ///
+    /// By default, the robot's linear output is scaled down when it isn't facing the target,
+    /// so a robot facing far away from the target will mostly turn in place before driving
+    /// towards it. With a maximum curvature, the linear output is left as-is and the angular
+    /// output is instead capped to `curvature` times the magnitude of the linear output. The
+    /// robot will then drive a smooth arc towards the target rather than turning then driving.
+    ///
+    /// `curvature` is the maximum ratio of angular to linear output. For a differential
+    /// drivetrain, a curvature of `1.0` allows arcs as tight as pivoting around one side of the
+    /// drivetrain.
+    ///
+    /// # Tuning
+    ///
+    /// Lower curvatures give smoother and faster approaches, but take wider arcs to reach the
+    /// target, which may not be possible in tight spaces. A target too close to the side of the
+    /// robot may be unreachable with a low curvature, causing the robot to circle around it until
+    /// it is approached from a better angle. Higher curvatures are more direct, but approach the
+    /// default turn-then-drive behavior.
+    pub const fn with_max_curvature(&mut self, curvature: f64) -> &mut Self {
+        self.max_curvature = Some(curvature);
+        self
+    }
+
+    /// Removes this motion's maximum curvature, restoring the default approach of slowing down
+    /// when not facing the target.
+    pub const fn without_max_curvature(&mut self) -> &mut Self {
+        self.max_curvature = None;
+        self
+    }
+
+    /// Modifies this motion's heading lock radius.
+    ///
+    /// As the robot nears its target, the bearing to the target becomes very sensitive to small
+    /// changes in position, which can cause the robot to wiggle back and forth at the end of the
+    /// motion. Within this distance of the target, the lateral controller is no longer updated
+    /// and its output is zeroed, so the robot drives straight for the rest of its approach.
+    ///
+    /// This should be kept small (typically a few wheel units), since any cross-track error left
+    /// when the robot enters the radius will no longer be corrected. By default, no heading lock
+    /// radius is used.
+    pub const fn with_heading_lock_radius(&mut self, radius: f64) -> &mut Self {
+        self.heading_lock_radius = Some(radius);
+        self
+    }
+
+    /// Removes this motion's heading lock radius.
+    pub const fn without_heading_lock_radius(&mut self) -> &mut Self {
+        self.heading_lock_radius = None;
+        self
+    }
+
     /// Modifies this motion's tolerances.
     pub const fn with_tolerances(&mut self, tolerances: Tolerances) -> &mut Self {
         self.tolerances = tolerances;
@@ -195,15 +790,76 @@ where
         self.tolerances.duration = None;
         self
     }
+
+    /// Modifies how many consecutive out-of-tolerance checks this motion allows before its
+    /// settling timer resets.
+    ///
+    /// See [`Tolerances::debounce`] for more information.
+    pub const fn with_settle_debounce(&mut self, debounce: u32) -> &mut Self {
+        self.tolerances.debounce = debounce;
+        self
+    }
+}
+
+// MARK: Tank Modifiers
+
+impl<M, L, A, T, C> MoveToPointFuture<'_, M, L, A, T, C>
+where
+    M: Tank,
+    L: Feedback<State = f64, Signal = f64> + Unpin,
+    A: Feedback<State = f64, Signal = f64> + Unpin,
+    T: TracksPosition + TracksHeading + TracksVelocity,
+    C: Clock,
+{
+    /// Drives this motion using differential kinematics rather than arcade mixing.
+    ///
+    /// The linear controller's output is treated as the robot's linear velocity in wheel units
+    /// per second, and the lateral controller's output as its angular velocity in radians per
+    /// second, with positive outputs turning clockwise (matching the sign of arcade steering).
+    /// Each side of the drivetrain is then driven at
+    ///
+    /// ```text
+    /// left = linear + angular * track_width / 2
+    /// right = linear - angular * track_width / 2
+    /// ```
+    ///
+    /// divided by `max_wheel_velocity` (in wheel units per second) to give a normalized power,
+    /// and desaturated if either side exceeds full power.
+    ///
+    /// Since both controllers now output velocities, they will generally need larger gains than
+    /// when outputting normalized powers. The [minimum linear speed](Self::with_min_linear_speed)
+    /// is given in wheel units per second, the [maximum curvature](Self::with_max_curvature) in
+    /// radians per wheel unit (where `2.0 / track_width` allows pivoting around one side of the
+    /// drivetrain), and the [slowdown radius](Self::with_slowdown_radius) caps the linear output
+    /// relative to `max_wheel_velocity`.
+    ///
+    /// Stopping and [active braking](Self::with_active_brake) still drive the robot through
+    /// arcade mixing.
+    pub fn with_tank_kinematics(&mut self, track_width: f64, max_wheel_velocity: f64) -> &mut Self {
+        self.tank_kinematics = Some(TankKinematics {
+            track_width,
+            max_wheel_velocity,
+            drive_tank: M::drive_tank,
+        });
+        self
+    }
+
+    /// Removes this motion's tank kinematics, restoring arcade mixing of the controllers'
+    /// outputs.
+    pub fn without_tank_kinematics(&mut self) -> &mut Self {
+        self.tank_kinematics = None;
+        self
+    }
 }
 
 // MARK: Linear PID Modifiers
 
-impl<M, A, T> MoveToPointFuture<'_, M, Pid, A, T>
+impl<M, A, T, C> MoveToPointFuture<'_, M, Pid, A, T, C>
 where
     M: Arcade,
     A: Feedback<State = f64, Signal = f64> + Unpin,
     T: TracksPosition + TracksHeading + TracksVelocity,
+    C: Clock,
 {
     /// Modifies this motion's linear PID gains.
     pub const fn with_linear_gains(&mut self, kp: f64, ki: f64, kd: f64) -> &mut Self {
@@ -257,11 +913,12 @@ where
 
 // MARK: Angular PID Modifiers
 
-impl<M, L, T> MoveToPointFuture<'_, M, L, Pid, T>
+impl<M, L, T, C> MoveToPointFuture<'_, M, L, Pid, T, C>
 where
     M: Arcade,
     L: Feedback<State = f64, Signal = f64> + Unpin,
     T: TracksPosition + TracksHeading + TracksVelocity,
+    C: Clock,
 {
     /// Modifies this motion's lateral PID gains.
     pub const fn with_lateral_gains(&mut self, kp: f64, ki: f64, kd: f64) -> &mut Self {
@@ -0,0 +1,335 @@
+//! Sequential motion composition.
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use std::time::Duration;
+
+use evian_control::loops::Feedback;
+use evian_drivetrain::{Drivetrain, model::Arcade};
+use evian_math::{Angle, Vec2};
+use evian_tracking::{TracksForwardTravel, TracksHeading, TracksPosition, TracksVelocity};
+
+use crate::{
+    Basic, Seeking,
+    clock::{Clock, VexideClock},
+    outcome::{MotionOutcome, SettlingMotion},
+};
+
+/// A single queued motion in a [`Sequence`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Step {
+    Drive(f64),
+    DriveAtHeading(f64, Angle),
+    TurnToHeading(Angle),
+    TurnToPoint(Vec2<f64>),
+    MoveToPoint(Vec2<f64>),
+}
+
+/// A queue of motions that are run in order.
+///
+/// Sequences share a single set of [`Basic`] and [`Seeking`] controller configurations
+/// between all of their motions, removing the need to `.await` each motion individually.
+///
+/// # Timeouts
+///
+/// By default, a sequence will stop running motions once one of them times out, since
+/// later motions likely depend on earlier ones completing. This can be changed using
+/// [`Sequence::continue_on_timeout`].
+///
+/// Each motion reports whether it settled or timed out through its [`MotionOutcome`], so a
+/// motion that settles just as its timeout elapses isn't counted as having timed out.
+///
+/// # Timekeeping
+///
+/// Every motion in a sequence keeps time using the sequence's [`Clock`], which can be replaced
+/// using [`Sequence::with_clock`]. The sequence's [elapsed time](SequenceResult::elapsed) is
+/// measured with the same clock.
+///
+/// # Examples
+///
+/// ```ignore
+/// let result = Sequence::new(basic, seeking)
+///     .then_drive(24.0)
+///     .then_turn(90.0.deg())
+///     .then_move_to((24.0, 24.0))
+///     .run(&mut drivetrain)
+///     .await;
+///
+/// if !result.is_complete() {
+///     println!("Motions {:?} timed out", result.timed_out);
+/// }
+/// ```
+#[derive(PartialEq)]
+pub struct Sequence<BL, BA, SL, SA, C = VexideClock>
+where
+    BL: Feedback<State = f64, Signal = f64> + Unpin + Clone,
+    BA: Feedback<State = Angle, Signal = f64> + Unpin + Clone,
+    SL: Feedback<State = f64, Signal = f64> + Unpin + Clone,
+    SA: Feedback<State = f64, Signal = f64> + Unpin + Clone,
+    C: Clock + Clone,
+{
+    /// Configuration used for driving and turning motions.
+    pub basic: Basic<BL, BA>,
+
+    /// Configuration used for point-seeking motions.
+    pub seeking: Seeking<SL, SA>,
+
+    steps: Vec<Step>,
+    continue_on_timeout: bool,
+    clock: C,
+}
+
+/// The outcome of running a [`Sequence`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SequenceResult {
+    /// The number of motions that were run.
+    pub steps_run: usize,
+
+    /// The number of motions queued in the sequence.
+    pub steps_total: usize,
+
+    /// Indices of the motions that timed out.
+    pub timed_out: Vec<usize>,
+
+    /// Total time taken to run the sequence.
+    pub elapsed: Duration,
+}
+
+impl SequenceResult {
+    /// Returns `true` if every motion in the sequence was run without timing out.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.steps_run == self.steps_total && self.timed_out.is_empty()
+    }
+}
+
+impl<BL, BA, SL, SA> Sequence<BL, BA, SL, SA>
+where
+    BL: Feedback<State = f64, Signal = f64> + Unpin + Clone,
+    BA: Feedback<State = Angle, Signal = f64> + Unpin + Clone,
+    SL: Feedback<State = f64, Signal = f64> + Unpin + Clone,
+    SA: Feedback<State = f64, Signal = f64> + Unpin + Clone,
+{
+    /// Creates a new empty sequence using the given motion configurations.
+    pub const fn new(basic: Basic<BL, BA>, seeking: Seeking<SL, SA>) -> Self {
+        Self {
+            basic,
+            seeking,
+            steps: Vec::new(),
+            continue_on_timeout: false,
+            clock: VexideClock,
+        }
+    }
+}
+
+impl<BL, BA, SL, SA, C> Sequence<BL, BA, SL, SA, C>
+where
+    BL: Feedback<State = f64, Signal = f64> + Unpin + Clone,
+    BA: Feedback<State = Angle, Signal = f64> + Unpin + Clone,
+    SL: Feedback<State = f64, Signal = f64> + Unpin + Clone,
+    SA: Feedback<State = f64, Signal = f64> + Unpin + Clone,
+    C: Clock + Clone,
+{
+    /// Replaces the [`Clock`] used by every motion in this sequence to keep time.
+    ///
+    /// See the [`clock`](crate::clock) module for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use evian_control::{Tolerances, loops::{AngularPid, Pid}};
+    /// use evian_drivetrain::Drivetrain;
+    /// use evian_motion::{
+    ///     Basic, Seeking, Sequence,
+    ///     clock::ManualClock,
+    ///     sim::{self, SimRobot},
+    /// };
+    ///
+    /// let basic = Basic {
+    ///     linear_controller: Pid::new(1.0, 0.0, 0.0, None),
+    ///     angular_controller: AngularPid::new(1.0, 0.0, 0.0, None),
+    ///     linear_tolerances: Tolerances::new().error(0.5),
+    ///     angular_tolerances: Tolerances::new().error(0.05),
+    ///     timeout: Some(Duration::from_millis(100)),
+    /// };
+    /// let seeking = Seeking {
+    ///     linear_controller: Pid::new(1.0, 0.0, 0.0, None),
+    ///     lateral_controller: Pid::new(1.0, 0.0, 0.0, None),
+    ///     tolerances: Tolerances::new().error(0.5),
+    ///     timeout: Some(Duration::from_millis(100)),
+    /// };
+    ///
+    /// let clock = ManualClock::new();
+    /// let mut sequence = Sequence::new(basic, seeking)
+    ///     .then_drive(0.0)
+    ///     .then_drive(24.0)
+    ///     .then_drive(0.0)
+    ///     .with_clock(clock.clone());
+    ///
+    /// let robot = SimRobot::stuck();
+    /// let mut drivetrain = Drivetrain::new(robot.clone(), robot);
+    /// let result = sim::run(sequence.run(&mut drivetrain), &clock);
+    ///
+    /// // The first drive settles immediately, but the robot can't move to finish the second.
+    /// assert_eq!(result.steps_run, 2);
+    /// assert_eq!(result.timed_out, [1]);
+    /// assert!(result.elapsed >= Duration::from_millis(100));
+    /// ```
+    pub fn with_clock<C2: Clock + Clone>(self, clock: C2) -> Sequence<BL, BA, SL, SA, C2> {
+        Sequence {
+            basic: self.basic,
+            seeking: self.seeking,
+            steps: self.steps,
+            continue_on_timeout: self.continue_on_timeout,
+            clock,
+        }
+    }
+
+    /// Sets whether the sequence should keep running motions after one times out.
+    #[must_use]
+    pub const fn continue_on_timeout(mut self, continue_on_timeout: bool) -> Self {
+        self.continue_on_timeout = continue_on_timeout;
+        self
+    }
+
+    /// Queues a motion driving forwards by a given distance.
+    ///
+    /// See [`Basic::drive_distance`].
+    #[must_use]
+    pub fn then_drive(mut self, distance: f64) -> Self {
+        self.steps.push(Step::Drive(distance));
+        self
+    }
+
+    /// Queues a motion driving forwards by a given distance while turning to face a heading.
+    ///
+    /// See [`Basic::drive_distance_at_heading`].
+    #[must_use]
+    pub fn then_drive_at_heading(mut self, distance: f64, heading: Angle) -> Self {
+        self.steps.push(Step::DriveAtHeading(distance, heading));
+        self
+    }
+
+    /// Queues a motion turning in place to face a heading.
+    ///
+    /// See [`Basic::turn_to_heading`].
+    #[must_use]
+    pub fn then_turn(mut self, heading: Angle) -> Self {
+        self.steps.push(Step::TurnToHeading(heading));
+        self
+    }
+
+    /// Queues a motion turning in place to face a point.
+    ///
+    /// See [`Basic::turn_to_point`].
+    #[must_use]
+    pub fn then_turn_to_point(mut self, point: impl Into<Vec2<f64>>) -> Self {
+        self.steps.push(Step::TurnToPoint(point.into()));
+        self
+    }
+
+    /// Queues a motion moving to a point.
+    ///
+    /// See [`Seeking::move_to_point`].
+    #[must_use]
+    pub fn then_move_to(mut self, point: impl Into<Vec2<f64>>) -> Self {
+        self.steps.push(Step::MoveToPoint(point.into()));
+        self
+    }
+
+    /// Returns the number of motions queued in the sequence.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Returns `true` if no motions are queued in the sequence.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Runs each queued motion in order on a drivetrain.
+    pub async fn run<M, T>(&mut self, drivetrain: &mut Drivetrain<M, T>) -> SequenceResult
+    where
+        M: Arcade,
+        T: TracksForwardTravel + TracksPosition + TracksHeading + TracksVelocity,
+    {
+        let start = self.clock.now();
+        let mut result = SequenceResult {
+            steps_run: 0,
+            steps_total: self.steps.len(),
+            timed_out: Vec::new(),
+            elapsed: Duration::ZERO,
+        };
+
+        for (index, step) in self.steps.iter().enumerate() {
+            let clock = self.clock.clone();
+
+            let outcome = match *step {
+                Step::Drive(distance) => {
+                    run_motion(
+                        self.basic
+                            .drive_distance(drivetrain, distance)
+                            .with_clock(clock),
+                    )
+                    .await
+                }
+                Step::DriveAtHeading(distance, heading) => {
+                    run_motion(
+                        self.basic
+                            .drive_distance_at_heading(drivetrain, distance, heading)
+                            .with_clock(clock),
+                    )
+                    .await
+                }
+                Step::TurnToHeading(heading) => {
+                    run_motion(
+                        self.basic
+                            .turn_to_heading(drivetrain, heading)
+                            .with_clock(clock),
+                    )
+                    .await
+                }
+                Step::TurnToPoint(point) => {
+                    run_motion(
+                        self.basic
+                            .turn_to_point(drivetrain, point)
+                            .with_clock(clock),
+                    )
+                    .await
+                }
+                Step::MoveToPoint(point) => {
+                    run_motion(
+                        self.seeking
+                            .move_to_point(drivetrain, point)
+                            .with_clock(clock),
+                    )
+                    .await
+                }
+            };
+
+            result.steps_run += 1;
+
+            if outcome == Some(MotionOutcome::TimedOut) {
+                result.timed_out.push(index);
+
+                if !self.continue_on_timeout {
+                    break;
+                }
+            }
+        }
+
+        result.elapsed = self.clock.elapsed(start);
+        result
+    }
+}
+
+/// Runs a motion to completion, returning how it finished.
+async fn run_motion<F: SettlingMotion + Unpin>(mut motion: F) -> Option<MotionOutcome> {
+    (&mut motion).await;
+    motion.outcome()
+}
@@ -0,0 +1,293 @@
+//! A simulated robot for examples and tests.
+//!
+//! This module is shared by the examples in this crate's documentation, so that they can run
+//! motions against a robot without a brain. It isn't intended for use outside of them, and may
+//! change at any time.
+
+extern crate alloc;
+
+use alloc::{rc::Rc, vec::Vec};
+use core::{
+    cell::RefCell,
+    future::Future,
+    pin::{Pin, pin},
+    task::{Context, Poll, Waker},
+};
+use std::time::Duration;
+
+use evian_drivetrain::model::{Arcade, DrivetrainModel};
+use evian_math::{Angle, Vec2};
+use evian_tracking::{
+    Tracking, TracksForwardTravel, TracksHeading, TracksPosition, TracksVelocity,
+};
+
+use crate::clock::ManualClock;
+
+/// How often motions update, and how long each drive command is applied to a [`SimRobot`] for.
+pub const UPDATE_PERIOD: Duration = Duration::from_millis(5);
+
+/// How much simulated time [`run`] allows a motion before giving up.
+const RUN_LIMIT: Duration = Duration::from_secs(60);
+
+// MARK: Robot
+
+/// A simulated differential drive robot, acting as both its own drivetrain model and tracking
+/// system.
+///
+/// Cloning a `SimRobot` returns a handle to the same robot, so that one clone can be given to a
+/// [`Drivetrain`](evian_drivetrain::Drivetrain) as its model and another as its tracking, while
+/// the original is used to inspect the robot.
+///
+/// Each call to [`Arcade::drive_arcade`] drives the robot for one [`UPDATE_PERIOD`]. The throttle
+/// and steer are mixed into left and right powers, which are scaled down uniformly if either
+/// exceeds full power. Positive steering turns the robot clockwise, while its heading and angular
+/// velocity are reported counterclockwise-positive.
+#[derive(Debug, Clone)]
+pub struct SimRobot {
+    state: Rc<RefCell<State>>,
+}
+
+#[derive(Debug)]
+struct State {
+    max_speed: f64,
+    max_turn_rate: f64,
+    lag: Duration,
+    stiction: f64,
+    steer_bias: f64,
+
+    position: Vec2<f64>,
+    heading: f64,
+    forward_travel: f64,
+    linear_velocity: f64,
+    angular_velocity: f64,
+    history: Vec<SimSample>,
+}
+
+/// The state of a [`SimRobot`] after one of its drive commands.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimSample {
+    /// Throttle the robot was driven at.
+    pub throttle: f64,
+
+    /// Steering the robot was driven at.
+    pub steer: f64,
+
+    /// Robot's position afterwards.
+    pub position: Vec2<f64>,
+
+    /// Robot's unwrapped heading afterwards.
+    pub heading: Angle,
+
+    /// Robot's linear velocity afterwards.
+    pub linear_velocity: f64,
+
+    /// Robot's counterclockwise-positive angular velocity afterwards.
+    pub angular_velocity: f64,
+}
+
+impl SimRobot {
+    /// Creates a robot at the origin facing along the x-axis, which drives at up to 60 wheel
+    /// units per second and turns at up to 10 radians per second.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            state: Rc::new(RefCell::new(State {
+                max_speed: 60.0,
+                max_turn_rate: 10.0,
+                lag: Duration::ZERO,
+                stiction: 0.0,
+                steer_bias: 0.0,
+
+                position: Vec2::default(),
+                heading: 0.0,
+                forward_travel: 0.0,
+                linear_velocity: 0.0,
+                angular_velocity: 0.0,
+                history: Vec::new(),
+            })),
+        }
+    }
+
+    /// Creates a robot that never moves, no matter how it is driven.
+    #[must_use]
+    pub fn stuck() -> Self {
+        Self::new().with_max_speed(0.0).with_max_turn_rate(0.0)
+    }
+
+    /// Sets how fast the robot drives at full power, in wheel units per second.
+    #[must_use]
+    pub fn with_max_speed(self, speed: f64) -> Self {
+        self.state.borrow_mut().max_speed = speed;
+        self
+    }
+
+    /// Sets how fast the robot turns at full power, in radians per second.
+    #[must_use]
+    pub fn with_max_turn_rate(self, rate: f64) -> Self {
+        self.state.borrow_mut().max_turn_rate = rate;
+        self
+    }
+
+    /// Sets the time constant the robot takes to reach the speeds it is commanded to.
+    ///
+    /// By default, the robot reaches them immediately.
+    #[must_use]
+    pub fn with_lag(self, lag: Duration) -> Self {
+        self.state.borrow_mut().lag = lag;
+        self
+    }
+
+    /// Sets the smallest throttle or steering magnitude that can overcome static friction.
+    ///
+    /// Smaller throttles and steers are treated as zero.
+    #[must_use]
+    pub fn with_stiction(self, stiction: f64) -> Self {
+        self.state.borrow_mut().stiction = stiction;
+        self
+    }
+
+    /// Adds a constant steering disturbance to every drive command, such as from imbalanced
+    /// drive motors pulling the robot to one side.
+    #[must_use]
+    pub fn with_steer_bias(self, bias: f64) -> Self {
+        self.state.borrow_mut().steer_bias = bias;
+        self
+    }
+
+    /// Sets the robot's heading.
+    #[must_use]
+    pub fn with_heading(self, heading: Angle) -> Self {
+        self.state.borrow_mut().heading = heading.as_radians();
+        self
+    }
+
+    /// Returns the robot's heading without wrapping, which also measures how far it has turned.
+    #[must_use]
+    pub fn unwrapped_heading(&self) -> Angle {
+        Angle::from_radians(self.state.borrow().heading)
+    }
+
+    /// Returns the robot's state after each drive command so far.
+    #[must_use]
+    pub fn history(&self) -> Vec<SimSample> {
+        self.state.borrow().history.clone()
+    }
+
+    /// Returns the robot's state after its most recent drive command.
+    #[must_use]
+    pub fn last(&self) -> Option<SimSample> {
+        self.state.borrow().history.last().copied()
+    }
+}
+
+impl Default for SimRobot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DrivetrainModel for SimRobot {
+    type Error = ();
+}
+
+impl Arcade for SimRobot {
+    fn drive_arcade(&mut self, throttle: f64, steer: f64) -> Result<(), ()> {
+        let state = &mut *self.state.borrow_mut();
+        let overcome = |power: f64| if power.abs() < state.stiction { 0.0 } else { power };
+
+        let (linear, angular) = (overcome(throttle), overcome(steer + state.steer_bias));
+        let scale = f64::max(1.0, linear.abs() + angular.abs());
+        let target_linear = linear / scale * state.max_speed;
+        let target_angular = -angular / scale * state.max_turn_rate;
+
+        let dt = UPDATE_PERIOD.as_secs_f64();
+        let response = if state.lag.is_zero() {
+            1.0
+        } else {
+            f64::min(1.0, dt / state.lag.as_secs_f64())
+        };
+        state.linear_velocity += (target_linear - state.linear_velocity) * response;
+        state.angular_velocity += (target_angular - state.angular_velocity) * response;
+
+        state.heading += state.angular_velocity * dt;
+        let distance = state.linear_velocity * dt;
+        state.forward_travel += distance;
+        state.position += Vec2::from_polar(distance, state.heading);
+
+        let sample = SimSample {
+            throttle,
+            steer,
+            position: state.position,
+            heading: Angle::from_radians(state.heading),
+            linear_velocity: state.linear_velocity,
+            angular_velocity: state.angular_velocity,
+        };
+        state.history.push(sample);
+
+        Ok(())
+    }
+}
+
+impl Tracking for SimRobot {}
+
+impl TracksPosition for SimRobot {
+    fn position(&self) -> Vec2<f64> {
+        self.state.borrow().position
+    }
+}
+
+impl TracksHeading for SimRobot {
+    fn heading(&self) -> Angle {
+        self.unwrapped_heading().wrapped_full()
+    }
+}
+
+impl TracksVelocity for SimRobot {
+    fn linear_velocity(&self) -> f64 {
+        self.state.borrow().linear_velocity
+    }
+
+    fn angular_velocity(&self) -> f64 {
+        self.state.borrow().angular_velocity
+    }
+}
+
+impl TracksForwardTravel for SimRobot {
+    fn forward_travel(&self) -> f64 {
+        self.state.borrow().forward_travel
+    }
+}
+
+// MARK: Polling
+
+/// Polls `motion` once, then advances `clock` by one [`UPDATE_PERIOD`] if it is still running.
+///
+/// Motions start on their first poll and first update once their clock has advanced, so the
+/// `n`th step runs the motion's `n - 1`th update.
+pub fn step<F: Future + ?Sized>(motion: Pin<&mut F>, clock: &ManualClock) -> Poll<F::Output> {
+    let poll = motion.poll(&mut Context::from_waker(Waker::noop()));
+    if poll.is_pending() {
+        clock.advance(UPDATE_PERIOD);
+    }
+
+    poll
+}
+
+/// Polls `motion` until it completes, advancing `clock` between polls.
+///
+/// # Panics
+///
+/// Panics if the motion doesn't complete within a minute of simulated time.
+pub fn run<F: Future>(motion: F, clock: &ManualClock) -> F::Output {
+    let mut motion = pin!(motion);
+    let mut elapsed = Duration::ZERO;
+
+    loop {
+        if let Poll::Ready(output) = step(motion.as_mut(), clock) {
+            return output;
+        }
+
+        elapsed += UPDATE_PERIOD;
+        assert!(elapsed <= RUN_LIMIT, "motion didn't complete in simulated time");
+    }
+}
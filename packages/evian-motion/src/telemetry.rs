@@ -0,0 +1,141 @@
+//! Motion telemetry, pose recording, and custom settling conditions.
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use std::time::{Duration, Instant};
+
+use evian_control::ToleranceCheck;
+use evian_math::{Angle, Vec2};
+use evian_tracking::{TracksHeading, TracksPosition};
+
+pub use evian_tracking::{PoseBuffer, PoseSample};
+
+/// A snapshot of a motion's state during a single update.
+///
+/// This is provided to custom settling conditions, such as those passed to
+/// [`DriveFuture::with_settle_when`](crate::basic::DriveFuture::with_settle_when).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MotionTelemetry {
+    /// Time elapsed since the motion started.
+    pub elapsed: Duration,
+
+    /// Linear error of the motion in wheel units.
+    ///
+    /// For motions targeting a point, this is the distance to the target. For motions
+    /// driving a distance, this is the remaining distance to drive.
+    pub linear_error: f64,
+
+    /// Angular error of the motion, if the motion has an angular target.
+    ///
+    /// For motions targeting a point, this is the difference between the robot's heading and the
+    /// angle to the target.
+    pub angular_error: Option<Angle>,
+
+    /// The robot's linear velocity in wheel units per second.
+    pub linear_velocity: f64,
+
+    /// The robot's angular velocity in radians per second.
+    pub angular_velocity: f64,
+
+    /// The result of checking the motion's linear tolerances during this update, if the motion
+    /// has linear tolerances.
+    pub linear_tolerances: Option<ToleranceCheck>,
+
+    /// The result of checking the motion's angular tolerances during this update, if the motion
+    /// has angular tolerances.
+    pub angular_tolerances: Option<ToleranceCheck>,
+}
+
+/// A user-provided settling condition for a motion.
+pub(crate) struct SettleCondition<'a> {
+    pub predicate: Box<dyn FnMut(&MotionTelemetry) -> bool + 'a>,
+
+    /// Whether the condition replaces the motion's tolerances entirely, rather than being
+    /// checked alongside them.
+    pub replaces_tolerances: bool,
+}
+
+impl<'a> SettleCondition<'a> {
+    pub fn new(
+        predicate: impl FnMut(&MotionTelemetry) -> bool + 'a,
+        replaces_tolerances: bool,
+    ) -> Self {
+        Self {
+            predicate: Box::new(predicate),
+            replaces_tolerances,
+        }
+    }
+}
+
+/// Determines if a motion has settled given an optional custom settling condition and whether the
+/// motion's tolerances have been met.
+pub(crate) fn is_settled(
+    condition: Option<&mut SettleCondition<'_>>,
+    tolerances_settled: bool,
+    telemetry: impl FnOnce() -> MotionTelemetry,
+) -> bool {
+    match condition {
+        None => tolerances_settled,
+        Some(condition) if condition.replaces_tolerances => (condition.predicate)(&telemetry()),
+        Some(condition) => tolerances_settled || (condition.predicate)(&telemetry()),
+    }
+}
+
+// MARK: Pose Recording
+
+/// Records poses from a tracking system into a [`PoseBuffer`] as a motion runs.
+pub(crate) struct PoseRecorder<'a, T> {
+    buffer: &'a mut PoseBuffer,
+    pose: fn(&T) -> (Vec2<f64>, Angle),
+}
+
+impl<'a, T: TracksPosition + TracksHeading> PoseRecorder<'a, T> {
+    pub fn new(buffer: &'a mut PoseBuffer) -> Self {
+        Self {
+            buffer,
+            pose: |tracking| (tracking.position(), tracking.heading()),
+        }
+    }
+}
+
+impl<T> PoseRecorder<'_, T> {
+    pub fn record(&mut self, tracking: &T, timestamp: Instant) {
+        let (position, heading) = (self.pose)(tracking);
+
+        self.buffer.push(PoseSample {
+            timestamp,
+            position,
+            heading,
+        });
+    }
+}
+
+// MARK: Tick Callbacks
+
+/// A user-provided callback run with the robot's pose on every update of a motion.
+pub(crate) struct TickCallback<'a, T> {
+    callback: Box<dyn FnMut(&PoseSample) + 'a>,
+    pose: fn(&T) -> (Vec2<f64>, Angle),
+}
+
+impl<'a, T: TracksPosition + TracksHeading> TickCallback<'a, T> {
+    pub fn new(callback: impl FnMut(&PoseSample) + 'a) -> Self {
+        Self {
+            callback: Box::new(callback),
+            pose: |tracking| (tracking.position(), tracking.heading()),
+        }
+    }
+}
+
+impl<T> TickCallback<'_, T> {
+    pub fn call(&mut self, tracking: &T, timestamp: Instant) {
+        let (position, heading) = (self.pose)(tracking);
+
+        (self.callback)(&PoseSample {
+            timestamp,
+            position,
+            heading,
+        });
+    }
+}
@@ -0,0 +1,48 @@
+//! Filtered acceleration estimation.
+
+use std::time::Duration;
+
+/// Time constant of the low-pass filters applied by [`AccelerationEstimator`].
+///
+/// Both the velocity and the resulting acceleration are filtered using this time constant, so
+/// acceleration estimates lag behind the robot's true acceleration by roughly twice this amount.
+pub(crate) const FILTER_TIME_CONSTANT: Duration = Duration::from_millis(50);
+
+/// Estimates acceleration by differentiating a low-pass filtered velocity signal.
+///
+/// Differentiating velocity (which is itself differentiated from position) amplifies sensor
+/// noise considerably, so both the velocity fed into the derivative and the derivative itself are
+/// passed through an exponential moving average.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub(crate) struct AccelerationEstimator {
+    filtered_velocity: Option<f64>,
+    acceleration: f64,
+}
+
+impl AccelerationEstimator {
+    /// Updates the estimator with a new velocity sample taken `dt` after the previous one,
+    /// returning the new acceleration estimate.
+    pub fn update(&mut self, velocity: f64, dt: Duration) -> f64 {
+        let dt = dt.as_secs_f64();
+
+        if dt <= 0.0 || !velocity.is_finite() {
+            return self.acceleration;
+        }
+
+        let alpha = dt / (FILTER_TIME_CONSTANT.as_secs_f64() + dt);
+
+        // The first sample has nothing to differentiate against.
+        let Some(prev_velocity) = self.filtered_velocity else {
+            self.filtered_velocity = Some(velocity);
+            return self.acceleration;
+        };
+
+        let filtered_velocity = prev_velocity + alpha * (velocity - prev_velocity);
+        self.filtered_velocity = Some(filtered_velocity);
+
+        let raw_acceleration = (filtered_velocity - prev_velocity) / dt;
+        self.acceleration += alpha * (raw_acceleration - self.acceleration);
+
+        self.acceleration
+    }
+}
@@ -0,0 +1,240 @@
+use evian_math::{Angle, Vec2};
+
+use crate::{
+    Tracking, TracksAcceleration, TracksForwardTravel, TracksHeading, TracksPosition,
+    TracksVelocity,
+};
+
+/// One of the two tracking systems in a [`DualTracking`].
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum TrackingSource {
+    /// The primary tracking system.
+    #[default]
+    Primary,
+
+    /// The secondary tracking system.
+    Secondary,
+}
+
+/// Two tracking systems running side by side, with one reported as active.
+///
+/// `DualTracking` implements every tracking trait implemented by both of its sources by
+/// delegating to whichever source is [active](DualTracking::active) (the primary by default).
+/// The other source keeps running in the background, and can be compared against the active one
+/// to detect problems such as wheel slip. For example, a mecanum drivetrain might pair tracking
+/// wheel odometry with odometry from its drive encoders, whose wheels slip far more easily.
+///
+/// Both sources should be created at the same starting pose, using the same units.
+///
+/// # Switching Sources
+///
+/// If the primary source is known to be unreliable (such as after its wheels are flagged as
+/// slipping), [`switch_to`](DualTracking::switch_to) changes which source is reported. Since
+/// the two sources will have drifted apart by then, the position and heading reported at the
+/// moment of the switch are carried over to the new source, so a motion running during the
+/// switch doesn't see its pose jump. After the switch, the pose is updated from the new source's
+/// movement, rotated by the difference between the two sources' headings at the switch so that
+/// driving forwards still moves the reported position forwards. Forward travel, velocity, and
+/// acceleration are reported directly from the active source.
+///
+/// Sources are never switched automatically, since a disagreement between them doesn't say
+/// which one is wrong.
+///
+/// # Examples
+///
+/// ```
+/// use evian_math::{Angle, IntoAngle, Vec2};
+/// use evian_tracking::{DualTracking, Tracking, TracksHeading, TracksPosition, TrackingSource};
+///
+/// struct Fixed(Vec2<f64>, Angle);
+///
+/// impl Tracking for Fixed {}
+/// impl TracksPosition for Fixed {
+///     fn position(&self) -> Vec2<f64> {
+///         self.0
+///     }
+/// }
+/// impl TracksHeading for Fixed {
+///     fn heading(&self) -> Angle {
+///         self.1
+///     }
+/// }
+///
+/// // The secondary source has drifted 3 inches back, 4 inches left, and 90° clockwise.
+/// let mut tracking = DualTracking::new(
+///     Fixed(Vec2::new(24.0, 0.0), 90.0.deg()),
+///     Fixed(Vec2::new(21.0, 4.0), Angle::ZERO),
+/// );
+///
+/// assert_eq!(tracking.position(), Vec2::new(24.0, 0.0));
+/// assert_eq!(tracking.position_discrepancy(), 5.0);
+///
+/// // Switching sources doesn't move the reported pose.
+/// tracking.switch_to(TrackingSource::Secondary);
+/// assert_eq!(tracking.position(), Vec2::new(24.0, 0.0));
+/// assert_eq!(tracking.heading(), 90.0.deg());
+///
+/// // Movement is now tracked by the secondary source, measured from the reported heading. The
+/// // secondary source thinks the robot drove 2 inches along +x, but the robot is facing +y.
+/// tracking.secondary_mut().0.x += 2.0;
+/// let position = tracking.position();
+/// assert!((position - Vec2::new(24.0, 2.0)).length() < 1e-9);
+///
+/// tracking.secondary_mut().1 = 15.0.deg();
+/// assert!((tracking.heading() - 105.0.deg()).as_radians().abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DualTracking<A, B> {
+    primary: A,
+    secondary: B,
+    active: TrackingSource,
+    switch_position: Vec2<f64>,
+    raw_switch_position: Vec2<f64>,
+    heading_offset: Angle,
+}
+
+impl<A, B> DualTracking<A, B> {
+    /// Creates a new dual tracking system from a primary and secondary source, with the primary
+    /// source active.
+    pub fn new(primary: A, secondary: B) -> Self {
+        Self {
+            primary,
+            secondary,
+            active: TrackingSource::Primary,
+            switch_position: Vec2::default(),
+            raw_switch_position: Vec2::default(),
+            heading_offset: Angle::ZERO,
+        }
+    }
+
+    /// Returns the source that is currently being reported.
+    #[must_use]
+    pub const fn active(&self) -> TrackingSource {
+        self.active
+    }
+
+    /// Returns a reference to the primary tracking system.
+    #[must_use]
+    pub const fn primary(&self) -> &A {
+        &self.primary
+    }
+
+    /// Returns a mutable reference to the primary tracking system.
+    pub const fn primary_mut(&mut self) -> &mut A {
+        &mut self.primary
+    }
+
+    /// Returns a reference to the secondary tracking system.
+    #[must_use]
+    pub const fn secondary(&self) -> &B {
+        &self.secondary
+    }
+
+    /// Returns a mutable reference to the secondary tracking system.
+    pub const fn secondary_mut(&mut self) -> &mut B {
+        &mut self.secondary
+    }
+}
+
+impl<A: TracksPosition, B: TracksPosition> DualTracking<A, B> {
+    /// Returns the distance between the positions reported by each source, in wheel units.
+    ///
+    /// This compares the sources directly (ignoring any offset applied by
+    /// [`switch_to`](DualTracking::switch_to)), so it measures how far the two have drifted
+    /// apart since they were created. A discrepancy that grows quickly is a good sign that one
+    /// source's wheels are slipping.
+    #[must_use]
+    pub fn position_discrepancy(&self) -> f64 {
+        self.primary.position().distance(self.secondary.position())
+    }
+}
+
+impl<A, B> DualTracking<A, B>
+where
+    A: TracksPosition + TracksHeading,
+    B: TracksPosition + TracksHeading,
+{
+    /// Changes which source is reported.
+    ///
+    /// The pose reported at the time of the switch is preserved, and is updated from the new
+    /// source's movement from then on. See the [type-level
+    /// documentation](DualTracking#switching-sources) for more information. Switching to the
+    /// source that is already active does nothing.
+    pub fn switch_to(&mut self, source: TrackingSource) {
+        if source == self.active {
+            return;
+        }
+
+        let (position, heading) = (self.position(), self.heading());
+        self.active = source;
+
+        let (raw_position, raw_heading) = match source {
+            TrackingSource::Primary => (self.primary.position(), self.primary.heading()),
+            TrackingSource::Secondary => (self.secondary.position(), self.secondary.heading()),
+        };
+        self.switch_position = position;
+        self.raw_switch_position = raw_position;
+        self.heading_offset = heading - raw_heading;
+    }
+}
+
+impl<A: Tracking, B: Tracking> Tracking for DualTracking<A, B> {}
+
+impl<A: TracksPosition, B: TracksPosition> TracksPosition for DualTracking<A, B> {
+    fn position(&self) -> Vec2<f64> {
+        let position = match self.active {
+            TrackingSource::Primary => self.primary.position(),
+            TrackingSource::Secondary => self.secondary.position(),
+        };
+
+        // The active source's movement since the switch is measured in its own frame, which
+        // is rotated from the reported frame by the heading offset.
+        self.switch_position
+            + (position - self.raw_switch_position).rotated(self.heading_offset.as_radians())
+    }
+}
+
+impl<A: TracksHeading, B: TracksHeading> TracksHeading for DualTracking<A, B> {
+    fn heading(&self) -> Angle {
+        let heading = match self.active {
+            TrackingSource::Primary => self.primary.heading(),
+            TrackingSource::Secondary => self.secondary.heading(),
+        };
+
+        (heading + self.heading_offset).wrapped_full()
+    }
+}
+
+impl<A: TracksVelocity, B: TracksVelocity> TracksVelocity for DualTracking<A, B> {
+    fn linear_velocity(&self) -> f64 {
+        match self.active {
+            TrackingSource::Primary => self.primary.linear_velocity(),
+            TrackingSource::Secondary => self.secondary.linear_velocity(),
+        }
+    }
+
+    fn angular_velocity(&self) -> f64 {
+        match self.active {
+            TrackingSource::Primary => self.primary.angular_velocity(),
+            TrackingSource::Secondary => self.secondary.angular_velocity(),
+        }
+    }
+}
+
+impl<A: TracksAcceleration, B: TracksAcceleration> TracksAcceleration for DualTracking<A, B> {
+    fn linear_acceleration(&self) -> f64 {
+        match self.active {
+            TrackingSource::Primary => self.primary.linear_acceleration(),
+            TrackingSource::Secondary => self.secondary.linear_acceleration(),
+        }
+    }
+}
+
+impl<A: TracksForwardTravel, B: TracksForwardTravel> TracksForwardTravel for DualTracking<A, B> {
+    fn forward_travel(&self) -> f64 {
+        match self.active {
+            TrackingSource::Primary => self.primary.forward_travel(),
+            TrackingSource::Secondary => self.secondary.forward_travel(),
+        }
+    }
+}
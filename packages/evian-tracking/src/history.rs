@@ -0,0 +1,106 @@
+//! Pose recording.
+
+extern crate alloc;
+
+use alloc::collections::VecDeque;
+use std::time::Instant;
+
+use evian_math::{Angle, Vec2};
+
+/// A pose of the robot recorded at a point in time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoseSample {
+    /// The time at which the pose was recorded.
+    pub timestamp: Instant,
+
+    /// The robot's position.
+    pub position: Vec2<f64>,
+
+    /// The robot's heading.
+    pub heading: Angle,
+}
+
+/// A fixed-capacity buffer of recorded poses.
+///
+/// Motions can push poses into this buffer on every update using their `record_into` modifier,
+/// allowing the path the robot took to be logged or visualized after the fact. Tracking systems
+/// may also keep a history of recent poses using this buffer, such as
+/// [`WheeledTracking::pose_history`](crate::wheeled::WheeledTracking::pose_history). Once the
+/// buffer is full, the oldest recorded pose is overwritten.
+///
+/// # Examples
+///
+/// ```
+/// use evian_tracking::PoseBuffer;
+///
+/// let mut poses = PoseBuffer::new(512);
+///
+/// // basic.drive_distance(&mut drivetrain, 24.0).record_into(&mut poses).await;
+///
+/// for pose in poses.iter() {
+///     println!("{:?}", pose.position);
+/// }
+/// ```
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct PoseBuffer {
+    samples: VecDeque<PoseSample>,
+    capacity: usize,
+}
+
+impl PoseBuffer {
+    /// Creates a new empty buffer that can hold up to `capacity` poses.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Pushes a pose into the buffer, overwriting the oldest pose if the buffer is full.
+    pub fn push(&mut self, sample: PoseSample) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+
+        self.samples.push_back(sample);
+    }
+
+    /// Returns an iterator over the poses in the buffer, from oldest to newest.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &PoseSample> + ExactSizeIterator {
+        self.samples.iter()
+    }
+
+    /// Returns the most recently recorded pose, if any.
+    #[must_use]
+    pub fn latest(&self) -> Option<&PoseSample> {
+        self.samples.back()
+    }
+
+    /// Returns the number of poses in the buffer.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Returns `true` if the buffer contains no poses.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Returns the maximum number of poses the buffer can hold.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Removes all poses from the buffer.
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+}
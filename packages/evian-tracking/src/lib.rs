@@ -12,6 +12,7 @@
 //! - [`TracksPosition`], for tracking the robot's 2D position (odometry).
 //! - [`TracksHeading`], for tracking the robot's absolute orientation (heading).
 //! - [`TracksVelocity`], for tracking the robot's linear and angular velocity.
+//! - [`TracksAcceleration`], for tracking the robot's linear acceleration.
 //! - [`TracksForwardTravel`], for tracking the robot's signed forward wheel travel.
 //!
 //! Below is an example of a motion algorithm function that is generic across all differential
@@ -24,7 +25,12 @@
 //! ```
 //!
 //! Additionally, a reference implementation of a tracking system that performs wheeled odometry is
-//! provided by the [`wheeled`] module.
+//! provided by the [`wheeled`] module, and a tracking system for mecanum drivetrains using only
+//! their drive encoders is provided by the [`mecanum`] module. Two tracking systems can be run
+//! side by side and cross-checked using [`DualTracking`].
+//!
+//! For testing on a host machine, the `sim` feature provides `NoisyTracking`, which adds
+//! reproducible random noise to the measurements of another tracking system.
 //!
 //! # A quick note about units!
 //!
@@ -41,15 +47,63 @@
 //! of what units to use and keeps everything generally stable while the Rust ecosystem tries to
 //! figure out what typed units library to go with.
 
+mod acceleration;
+mod dual;
+mod history;
+#[cfg(feature = "sim")]
+mod noisy;
 mod sensor;
+mod wait;
+pub mod mecanum;
 pub mod wheeled;
 
-pub use sensor::{Gyro, RotarySensor};
+pub use dual::{DualTracking, TrackingSource};
+pub use history::{PoseBuffer, PoseSample};
+#[cfg(feature = "sim")]
+pub use noisy::{Noise, NoisyTracking};
+pub use sensor::{Gyro, GyroGroup, GyroGroupError, RotarySensor};
+pub use wait::WaitUntilFuture;
+
+use std::{cell::RefCell, rc::Rc};
 
 use evian_math::{Angle, Vec2};
 
 /// Marker trait for a tracking system.
-pub trait Tracking {}
+pub trait Tracking {
+    /// Returns a future that resolves once `predicate` returns `true` for this tracking system.
+    ///
+    /// The predicate is checked on the same interval that tracking systems update on. This is
+    /// useful for triggering mechanisms partway through a motion, such as by running the
+    /// returned future concurrently with the motion. Since motions borrow the drivetrain (and
+    /// therefore its tracking system) mutably, the tracking system must be shared to do this,
+    /// which can be done by wrapping it in an `Rc<RefCell<T>>`.
+    ///
+    /// An optional timeout can be set using [`WaitUntilFuture::with_timeout`]. The future
+    /// resolves to `true` if the predicate was met, or `false` if the timeout elapsed first.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let tracking = Rc::new(RefCell::new(tracking));
+    /// let mut drivetrain = Drivetrain::new(model, tracking.clone());
+    ///
+    /// let drive = basic.drive_distance(&mut drivetrain, 48.0);
+    /// let extend = async {
+    ///     // Extend the wing once we've driven past x = 30.
+    ///     tracking.wait_until(|tracking| tracking.position().x > 30.0).await;
+    ///     wing.set_high().ok();
+    /// };
+    ///
+    /// join(drive, extend).await;
+    /// ```
+    fn wait_until<F>(&self, predicate: F) -> WaitUntilFuture<'_, Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&Self) -> bool + Unpin,
+    {
+        WaitUntilFuture::new(self, predicate)
+    }
+}
 
 impl Tracking for () {}
 
@@ -91,9 +145,17 @@ pub trait TracksHeading: Tracking {
 /// # Units
 ///
 /// - Linear velocity is measured in *wheel units per second*. In this instance, *wheel units*
-///   refer to whatever units the user measured their wheel diameter with.
+///   refer to whatever units the user measured their wheel diameter with. A robot whose wheel
+///   diameter was given in inches reports inches per second, and one whose wheel diameter was
+///   given in meters reports meters per second.
 ///
-/// - Angular velocity is measured in *radians per second*.
+/// - Angular velocity is measured in *radians per second*, with anticlockwise rotation being
+///   positive (matching [`TracksHeading`]).
+///
+/// Velocities are never reported as a percentage of the drivetrain's maximum speed or in motor
+/// RPM. Since `vexide` doesn't provide unit-typed velocities, these are returned as plain `f64`s,
+/// so any velocity tolerances or limits compared against them (such as those in motion
+/// settling conditions) must be given in the same units.
 pub trait TracksVelocity: Tracking {
     /// Returns the robot's estimated linear velocity in wheel units per second.
     fn linear_velocity(&self) -> f64;
@@ -102,6 +164,21 @@ pub trait TracksVelocity: Tracking {
     fn angular_velocity(&self) -> f64;
 }
 
+/// A tracking system that tracks a robot's linear acceleration.
+///
+/// Acceleration is typically estimated by differentiating velocity, which is very noisy. As such,
+/// implementors are expected to filter their estimates, which will cause them to lag slightly
+/// behind the robot's true acceleration.
+///
+/// # Units
+///
+/// Linear acceleration is measured in *wheel units per second squared*, using the same wheel
+/// units as [`TracksVelocity`].
+pub trait TracksAcceleration: Tracking {
+    /// Returns the robot's estimated linear acceleration in wheel units per second squared.
+    fn linear_acceleration(&self) -> f64;
+}
+
 /// A tracking system that tracks a robot's signed forward wheel travel.
 ///
 /// # Units
@@ -116,6 +193,54 @@ pub trait TracksForwardTravel: Tracking {
     fn forward_travel(&self) -> f64;
 }
 
+/// Blanket implementation for all `Rc<RefCell<T>>` wrappers of already implemented tracking
+/// systems.
+impl<T: Tracking> Tracking for Rc<RefCell<T>> {}
+
+/// Blanket implementation for all `Rc<RefCell<T>>` wrappers of already implemented tracking
+/// systems.
+impl<T: TracksPosition> TracksPosition for Rc<RefCell<T>> {
+    fn position(&self) -> Vec2<f64> {
+        self.borrow().position()
+    }
+}
+
+/// Blanket implementation for all `Rc<RefCell<T>>` wrappers of already implemented tracking
+/// systems.
+impl<T: TracksHeading> TracksHeading for Rc<RefCell<T>> {
+    fn heading(&self) -> Angle {
+        self.borrow().heading()
+    }
+}
+
+/// Blanket implementation for all `Rc<RefCell<T>>` wrappers of already implemented tracking
+/// systems.
+impl<T: TracksVelocity> TracksVelocity for Rc<RefCell<T>> {
+    fn linear_velocity(&self) -> f64 {
+        self.borrow().linear_velocity()
+    }
+
+    fn angular_velocity(&self) -> f64 {
+        self.borrow().angular_velocity()
+    }
+}
+
+/// Blanket implementation for all `Rc<RefCell<T>>` wrappers of already implemented tracking
+/// systems.
+impl<T: TracksAcceleration> TracksAcceleration for Rc<RefCell<T>> {
+    fn linear_acceleration(&self) -> f64 {
+        self.borrow().linear_acceleration()
+    }
+}
+
+/// Blanket implementation for all `Rc<RefCell<T>>` wrappers of already implemented tracking
+/// systems.
+impl<T: TracksForwardTravel> TracksForwardTravel for Rc<RefCell<T>> {
+    fn forward_travel(&self) -> f64 {
+        self.borrow().forward_travel()
+    }
+}
+
 /// Creates a shared motor array.
 ///
 /// This macro simplifies the creation of an `Rc<RefCell<[Motor; N]>>` array, which is a shareable
@@ -0,0 +1,272 @@
+//! Mecanum drive encoder odometry.
+
+use evian_math::{Angle, Vec2, signed_delta};
+
+use std::{cell::RefCell, rc::Rc, time::Instant};
+use vexide::{
+    smart::motor::Motor,
+    task::{Task, spawn},
+    time::sleep,
+};
+
+use crate::{
+    Tracking, TracksAcceleration, TracksForwardTravel, TracksHeading, TracksPosition,
+    TracksVelocity,
+    acceleration::AccelerationEstimator,
+    sensor::{Gyro, RotarySensor},
+    wheeled::{GearRatio, TrackingWheel},
+};
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+struct TrackingData {
+    position: Vec2<f64>,
+    raw_heading: Angle,
+    heading_offset: Angle,
+    forward_travel: f64,
+    linear_velocity: f64,
+    strafe_velocity: f64,
+    linear_acceleration: f64,
+    angular_velocity: f64,
+    strafe_efficiency: f64,
+}
+
+// MARK: Tracking Implementation
+
+/// Tracking system that uses the drive wheels of a mecanum drivetrain to track position.
+///
+/// This tracking system estimates the robot's position using only the four drive wheel encoders
+/// of a mecanum drivetrain (typically the motors' integrated encoders) and a [`Gyro`] for
+/// orientation, allowing holonomic motion to be tracked without dedicated tracking wheels.
+///
+/// Forward and sideways displacement are computed each update using the forward kinematics
+/// of a mecanum drivetrain:
+///
+/// - `forward = (fl + fr + bl + br) / 4`
+/// - `strafe = (fl - fr - bl + br) / 4`
+///
+/// # Strafe Efficiency
+///
+/// Mecanum rollers tend to slip when strafing, meaning the drive wheels will report more travel
+/// than the robot actually moved sideways. This can be corrected for by measuring the actual
+/// distance the robot strafes against the distance reported by tracking, and passing their ratio
+/// to [`MecanumTracking::set_strafe_efficiency`].
+#[derive(Debug)]
+pub struct MecanumTracking {
+    data: Rc<RefCell<TrackingData>>,
+    _task: Task<()>,
+}
+
+impl MecanumTracking {
+    /// Creates a new mecanum tracking system.
+    ///
+    /// # Parameters
+    ///
+    /// * `origin` - The starting position of the robot.
+    /// * `heading` - The starting heading of the robot.
+    /// * `wheels` - Rotary sensors measuring the front-left, front-right, back-left, and
+    ///   back-right drive wheels (in that order).
+    /// * `wheel_diameter` - The diameter of the drive wheels in linear units. When driving a
+    ///   `Mecanum` drivetrain model, prefer creating tracking through its `tracking` method,
+    ///   which measures the model's own motors using its wheel diameter.
+    /// * `gearing` - Gear ratio between the sensors and wheels. See [`TrackingWheel::new`] for
+    ///   more information.
+    /// * `gyro` - The gyro used to determine robot orientation.
+    pub fn new<T: RotarySensor + 'static, G: Gyro + 'static>(
+        origin: impl Into<Vec2<f64>>,
+        heading: Angle,
+        wheels: [T; 4],
+        wheel_diameter: f64,
+        gearing: impl Into<GearRatio>,
+        gyro: G,
+    ) -> Self {
+        let gearing = gearing.into();
+        let wheels = wheels.map(|sensor| TrackingWheel::new(sensor, wheel_diameter, 0.0, gearing));
+
+        let initial_raw_heading = gyro.heading().unwrap_or_default();
+        let initial_travel = wheels.each_ref().map(|wheel| wheel.travel().ok());
+
+        let data = Rc::new(RefCell::new(TrackingData {
+            position: origin.into(),
+            heading_offset: heading,
+            raw_heading: initial_raw_heading,
+            strafe_efficiency: 1.0,
+            ..Default::default()
+        }));
+
+        Self {
+            data: data.clone(),
+            _task: spawn(Self::task(
+                wheels,
+                gyro,
+                data,
+                initial_travel,
+                initial_raw_heading,
+            )),
+        }
+    }
+
+    // MARK: Task
+
+    async fn task<T: RotarySensor, G: Gyro>(
+        wheels: [TrackingWheel<T>; 4],
+        gyro: G,
+        data: Rc<RefCell<TrackingData>>,
+        mut prev_travel: [Option<f64>; 4],
+        mut prev_raw_heading: Angle,
+    ) {
+        let mut prev_time = Instant::now();
+        let mut acceleration = AccelerationEstimator::default();
+
+        loop {
+            sleep(Motor::WRITE_INTERVAL).await;
+
+            let mut data = data.borrow_mut();
+
+            // Once a gyro disconnects it will recalibrate upon regaining power, which will
+            // mess up tracking badly. We have no other way of determining heading, so there's
+            // no point in continuing this task.
+            let Ok(raw_heading) = gyro.heading() else {
+                return;
+            };
+            data.raw_heading = raw_heading;
+
+            // If any of our wheels fail, we can't compute the robot's displacement, so just
+            // wait for them to reconnect.
+            let travel = wheels.each_ref().map(|wheel| wheel.travel().ok());
+            let [Some(fl), Some(fr), Some(bl), Some(br)] = travel else {
+                continue;
+            };
+
+            // Wheels that failed last iteration have nothing to compute a change in travel
+            // against, so we'll have to start over from this iteration instead.
+            let [Some(prev_fl), Some(prev_fr), Some(prev_bl), Some(prev_br)] = prev_travel else {
+                prev_travel = travel;
+                prev_raw_heading = data.raw_heading;
+                continue;
+            };
+            prev_travel = travel;
+
+            let delta_heading = signed_delta(prev_raw_heading, data.raw_heading);
+            prev_raw_heading = data.raw_heading;
+
+            // Average between the current and previous heading reading used for conversion
+            // between global and local coordinate displacements.
+            let avg_heading =
+                (data.raw_heading - (delta_heading / 2.0) + data.heading_offset).wrapped_full();
+
+            let (delta_fl, delta_fr, delta_bl, delta_br) =
+                (fl - prev_fl, fr - prev_fr, bl - prev_bl, br - prev_br);
+
+            // Mecanum forward kinematics. Rotation cancels out in both of these sums, since the
+            // left and right wheels spin in opposite directions when turning in place.
+            let local_displacement = Vec2::new(
+                (delta_fl + delta_fr + delta_bl + delta_br) / 4.0,
+                // Positive strafe is to the right, but our local y-axis points left.
+                -(delta_fl - delta_fr - delta_bl + delta_br) / 4.0 * data.strafe_efficiency,
+            );
+
+            data.forward_travel = (fl + fr + bl + br) / 4.0;
+
+            let dt = prev_time.elapsed();
+            prev_time = Instant::now();
+
+            // Linear velocity is signed (negative while reversing), so only the forward
+            // component of our displacement is used. Strafing is reported separately.
+            data.linear_velocity = local_displacement.x / dt.as_secs_f64();
+            data.strafe_velocity = -local_displacement.y / dt.as_secs_f64();
+            data.linear_acceleration = acceleration.update(data.linear_velocity, dt);
+            data.angular_velocity = gyro
+                .angular_velocity()
+                .unwrap_or_else(|_| delta_heading.as_radians() / dt.as_secs_f64());
+
+            data.position += local_displacement.rotated(avg_heading.as_radians());
+        }
+    }
+
+    // MARK: Getters
+
+    /// Returns the robot's estimated sideways velocity in wheel units per second.
+    ///
+    /// Positive values are to the right of the robot. Unlike
+    /// [`linear_velocity`](TracksVelocity::linear_velocity), which only measures the robot's
+    /// forward velocity, this measures how fast the robot is strafing.
+    #[must_use]
+    pub fn strafe_velocity(&self) -> f64 {
+        self.data.borrow().strafe_velocity
+    }
+
+    /// Returns the magnitude of the robot's velocity in wheel units per second, including both
+    /// its forward and sideways components.
+    #[must_use]
+    pub fn speed(&self) -> f64 {
+        let data = self.data.borrow();
+        Vec2::new(data.linear_velocity, data.strafe_velocity).length()
+    }
+
+    // MARK: Setters
+
+    /// Offsets the currently tracked heading to a given [`Angle`].
+    pub fn set_heading(&mut self, heading: Angle) {
+        let mut data = self.data.borrow_mut();
+        data.heading_offset = heading - data.raw_heading;
+    }
+
+    /// Sets the currently tracked position to a new point.
+    pub fn set_position(&mut self, position: impl Into<Vec2<f64>>) {
+        self.data.borrow_mut().position = position.into();
+    }
+
+    /// Sets the strafe efficiency calibration factor.
+    ///
+    /// This is the ratio of the distance the robot actually strafed to the distance that was
+    /// reported by tracking, and is used as a multiplier on all sideways displacement. Defaults to
+    /// `1.0` (no correction).
+    pub fn set_strafe_efficiency(&mut self, efficiency: f64) {
+        self.data.borrow_mut().strafe_efficiency = efficiency;
+    }
+}
+
+// MARK: Tracking Traits
+
+impl Tracking for MecanumTracking {}
+
+impl TracksPosition for MecanumTracking {
+    fn position(&self) -> Vec2<f64> {
+        self.data.borrow().position
+    }
+}
+
+impl TracksHeading for MecanumTracking {
+    fn heading(&self) -> Angle {
+        let data = self.data.borrow();
+
+        // Apply heading offset and wrap from [0, 2π]
+        (data.raw_heading + data.heading_offset).wrapped_full()
+    }
+}
+
+impl TracksForwardTravel for MecanumTracking {
+    fn forward_travel(&self) -> f64 {
+        self.data.borrow().forward_travel
+    }
+}
+
+impl TracksVelocity for MecanumTracking {
+    fn angular_velocity(&self) -> f64 {
+        self.data.borrow().angular_velocity
+    }
+
+    fn linear_velocity(&self) -> f64 {
+        self.data.borrow().linear_velocity
+    }
+}
+
+impl TracksAcceleration for MecanumTracking {
+    /// Returns the robot's estimated linear acceleration in wheel units per second squared.
+    ///
+    /// This is estimated by differentiating low-pass filtered velocity, and lags behind the
+    /// robot's true acceleration by roughly 100 milliseconds.
+    fn linear_acceleration(&self) -> f64 {
+        self.data.borrow().linear_acceleration
+    }
+}
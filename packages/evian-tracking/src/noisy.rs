@@ -0,0 +1,218 @@
+use core::{cell::Cell, f64::consts::TAU};
+
+use evian_math::{Angle, Vec2};
+
+use crate::{
+    Tracking, TracksAcceleration, TracksForwardTravel, TracksHeading, TracksPosition,
+    TracksVelocity,
+};
+
+/// Distribution of the random noise added to a measurement by [`NoisyTracking`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Noise {
+    /// Normally distributed noise with a mean of zero.
+    Gaussian {
+        /// Standard deviation of the noise.
+        std_dev: f64,
+    },
+
+    /// Noise distributed evenly between `-amplitude` and `amplitude`.
+    Uniform {
+        /// Largest magnitude of the noise.
+        amplitude: f64,
+    },
+}
+
+/// A tracking system that adds reproducible random noise to the measurements of another.
+///
+/// `NoisyTracking` is intended for testing motions and filters on a host machine, where the
+/// inner tracking system is typically simulated and would otherwise report perfectly clean
+/// measurements. Noise can be added to the reported position (on each axis), heading (in radians),
+/// linear velocity, and angular velocity, and each is configured independently. Forward travel
+/// and acceleration are reported from the inner tracking system unchanged.
+///
+/// Noise is generated from a fixed seed, so two noisy tracking systems created with the same seed
+/// and read in the same order report the same noise. A fresh sample is drawn every time a
+/// measurement is read.
+///
+/// This is only available with the `sim` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// use evian_math::Vec2;
+/// use evian_tracking::{Noise, NoisyTracking, Tracking, TracksPosition};
+///
+/// struct Fixed(Vec2<f64>);
+///
+/// impl Tracking for Fixed {}
+/// impl TracksPosition for Fixed {
+///     fn position(&self) -> Vec2<f64> {
+///         self.0
+///     }
+/// }
+///
+/// let noisy = |seed| {
+///     NoisyTracking::new(Fixed(Vec2::new(24.0, 0.0)), seed)
+///         .with_position_noise(Noise::Uniform { amplitude: 0.5 })
+/// };
+///
+/// let (a, b) = (noisy(7), noisy(7));
+/// for _ in 0..100 {
+///     let position = a.position();
+///
+///     // The same seed always produces the same noise...
+///     assert_eq!(position, b.position());
+///
+///     // ...within the configured amplitude.
+///     assert!((position.x - 24.0).abs() <= 0.5 && position.y.abs() <= 0.5);
+/// }
+///
+/// // Different seeds produce different noise.
+/// assert_ne!(noisy(7).position(), noisy(8).position());
+/// ```
+#[derive(Debug, Clone)]
+pub struct NoisyTracking<T> {
+    inner: T,
+    position_noise: Option<Noise>,
+    heading_noise: Option<Noise>,
+    linear_velocity_noise: Option<Noise>,
+    angular_velocity_noise: Option<Noise>,
+    state: Cell<u64>,
+}
+
+impl<T> NoisyTracking<T> {
+    /// Creates a new noisy tracking system wrapping `inner`, seeding its noise with `seed`.
+    ///
+    /// No noise is added to any measurement until configured.
+    pub const fn new(inner: T, seed: u64) -> Self {
+        Self {
+            inner,
+            position_noise: None,
+            heading_noise: None,
+            linear_velocity_noise: None,
+            angular_velocity_noise: None,
+            state: Cell::new(seed),
+        }
+    }
+
+    /// Adds noise to each axis of the reported position, in wheel units.
+    #[must_use]
+    pub const fn with_position_noise(mut self, noise: Noise) -> Self {
+        self.position_noise = Some(noise);
+        self
+    }
+
+    /// Adds noise to the reported heading, in radians.
+    #[must_use]
+    pub const fn with_heading_noise(mut self, noise: Noise) -> Self {
+        self.heading_noise = Some(noise);
+        self
+    }
+
+    /// Adds noise to the reported linear velocity, in wheel units per second.
+    #[must_use]
+    pub const fn with_linear_velocity_noise(mut self, noise: Noise) -> Self {
+        self.linear_velocity_noise = Some(noise);
+        self
+    }
+
+    /// Adds noise to the reported angular velocity, in radians per second.
+    #[must_use]
+    pub const fn with_angular_velocity_noise(mut self, noise: Noise) -> Self {
+        self.angular_velocity_noise = Some(noise);
+        self
+    }
+
+    /// Returns a reference to the inner tracking system.
+    #[must_use]
+    pub const fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner tracking system.
+    pub const fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes the noisy tracking system, returning the inner tracking system.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Restarts the noise sequence from a new seed.
+    pub fn reseed(&self, seed: u64) {
+        self.state.set(seed);
+    }
+
+    /// Returns a uniformly distributed random number in `[0, 1)`.
+    fn next_uniform(&self) -> f64 {
+        // SplitMix64, which is small, fast, and produces well-distributed output from any seed.
+        let state = self.state.get().wrapping_add(0x9E37_79B9_7F4A_7C15);
+        self.state.set(state);
+
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+
+        // The top 53 bits fill an f64's mantissa exactly.
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Draws a sample of `noise`, or zero if there is no noise.
+    fn sample(&self, noise: Option<Noise>) -> f64 {
+        match noise {
+            Some(Noise::Gaussian { std_dev }) => {
+                // Box-Muller transform. `1.0 - u` keeps the logarithm's argument in `(0, 1]`.
+                let radius = (-2.0 * (1.0 - self.next_uniform()).ln()).sqrt();
+                radius * (TAU * self.next_uniform()).cos() * std_dev
+            }
+            Some(Noise::Uniform { amplitude }) => (self.next_uniform() * 2.0 - 1.0) * amplitude,
+            None => 0.0,
+        }
+    }
+}
+
+impl<T: Tracking> Tracking for NoisyTracking<T> {}
+
+impl<T: TracksPosition> TracksPosition for NoisyTracking<T> {
+    fn position(&self) -> Vec2<f64> {
+        let noise = Vec2::new(
+            self.sample(self.position_noise),
+            self.sample(self.position_noise),
+        );
+
+        self.inner.position() + noise
+    }
+}
+
+impl<T: TracksHeading> TracksHeading for NoisyTracking<T> {
+    fn heading(&self) -> Angle {
+        let noise = Angle::from_radians(self.sample(self.heading_noise));
+
+        (self.inner.heading() + noise).wrapped_full()
+    }
+}
+
+impl<T: TracksVelocity> TracksVelocity for NoisyTracking<T> {
+    fn linear_velocity(&self) -> f64 {
+        self.inner.linear_velocity() + self.sample(self.linear_velocity_noise)
+    }
+
+    fn angular_velocity(&self) -> f64 {
+        self.inner.angular_velocity() + self.sample(self.angular_velocity_noise)
+    }
+}
+
+impl<T: TracksAcceleration> TracksAcceleration for NoisyTracking<T> {
+    fn linear_acceleration(&self) -> f64 {
+        self.inner.linear_acceleration()
+    }
+}
+
+impl<T: TracksForwardTravel> TracksForwardTravel for NoisyTracking<T> {
+    fn forward_travel(&self) -> f64 {
+        self.inner.forward_travel()
+    }
+}
@@ -1,6 +1,6 @@
 use std::{cell::RefCell, rc::Rc, vec::Vec};
 
-use evian_math::Angle;
+use evian_math::{Angle, signed_delta};
 use vexide::{
     adi::encoder::AdiEncoder,
     smart::{
@@ -48,7 +48,7 @@ impl<const TPR: u32> RotarySensor for AdiEncoder<TPR> {
     }
 }
 
-impl<T: RotarySensor> RotarySensor for Vec<T> {
+impl<T: RotarySensor> RotarySensor for [T] {
     type Error = T::Error;
 
     fn position(&self) -> Result<Angle, Self::Error> {
@@ -87,43 +87,19 @@ impl<T: RotarySensor> RotarySensor for Vec<T> {
     }
 }
 
-// Duplicated because rust is stupid and I can't blanket-impl this for AsMut<[T]>.
-impl<const N: usize, T: RotarySensor> RotarySensor for [T; N] {
+impl<T: RotarySensor> RotarySensor for Vec<T> {
     type Error = T::Error;
 
     fn position(&self) -> Result<Angle, Self::Error> {
-        // The total motors to be used in the average later
-        let mut total_motors = 0;
-        let mut degree_sum = 0.0;
-        let mut last_error = None;
-
-        for motor in self {
-            degree_sum += match motor.position() {
-                Ok(position) => {
-                    total_motors += 1;
-                    position.as_degrees()
-                }
-                Err(error) => {
-                    // Since this motor isn't being counted in the average, decrement the count
-                    last_error = Some(error);
-                    continue;
-                }
-            };
-        }
+        self.as_slice().position()
+    }
+}
 
-        // Handle a case where no motors were added to the total.
-        if total_motors == 0 {
-            return if let Some(error) = last_error {
-                // Return the error from the last motor that failed.
-                Err(error)
-            } else {
-                // This means there were no motors in the group. We don't want to divide by zero here.
-                Ok(Angle::ZERO)
-            };
-        }
+impl<const N: usize, T: RotarySensor> RotarySensor for [T; N] {
+    type Error = T::Error;
 
-        #[allow(clippy::cast_precision_loss)]
-        Ok(Angle::from_degrees(degree_sum / f64::from(total_motors)))
+    fn position(&self) -> Result<Angle, Self::Error> {
+        self.as_slice().position()
     }
 }
 
@@ -162,3 +138,344 @@ impl<T: RotarySensor> RotarySensor for Rc<RefCell<T>> {
         self.borrow().position()
     }
 }
+
+/// Blanket implementation for all `Rc<RefCell<T>>` wrappers of already implemented gyros.
+impl<T: Gyro> Gyro for Rc<RefCell<T>> {
+    type Error = <T as Gyro>::Error;
+
+    fn heading(&self) -> Result<Angle, Self::Error> {
+        self.borrow().heading()
+    }
+
+    fn angular_velocity(&self) -> Result<f64, Self::Error> {
+        self.borrow().angular_velocity()
+    }
+}
+
+// MARK: Gyro Fusion
+
+/// The error returned by a [`GyroGroup`] when it has no gyros left to read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GyroGroupError<E> {
+    /// Every remaining gyro in the group failed during this reading. Contains the error returned
+    /// by the last gyro that failed.
+    Sensor(E),
+
+    /// Every gyro in the group has already failed in a previous reading.
+    Exhausted,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct GyroGroupState<const N: usize> {
+    prev_headings: [Option<Angle>; N],
+    strikes: [u32; N],
+    failed: [bool; N],
+    trusted: [bool; N],
+    heading: Option<Angle>,
+}
+
+/// A group of gyros whose headings are fused together into a single [`Gyro`].
+///
+/// Rather than averaging absolute headings (which would differ between gyros calibrated at
+/// slightly different times), the group averages the *change* in heading each gyro reports
+/// between readings. If a gyro's change in heading differs from the median change by more than
+/// the group's disagreement threshold, that gyro is ignored for the reading. When no gyro is
+/// within the threshold (such as when two gyros disagree), the gyro reporting the smallest
+/// change is trusted.
+///
+/// A gyro that returns an error is skipped until it reports a heading again. Since gyros
+/// recalibrate after losing power, a recovered gyro's first heading is only used as the starting
+/// point for its next change in heading, so the outage doesn't appear as a jump in the group's
+/// heading. Gyros that return an error or are ignored for disagreeing with the group on too many
+/// consecutive readings (see [`GyroGroup::with_failure_limit`]) are permanently removed from the
+/// group. The group only returns an error once no gyro is able to report a heading, allowing
+/// tracking systems like [`WheeledTracking`](crate::wheeled::WheeledTracking) to fall back to
+/// wheel-derived heading.
+///
+/// # Examples
+///
+/// The group can be wrapped in an `Rc<RefCell<T>>` to check which gyros are trusted after it has
+/// been given to a tracking system.
+///
+/// ```ignore
+/// let imus = Rc::new(RefCell::new(GyroGroup::new(
+///     [
+///         InertialSensor::new(peripherals.port_1),
+///         InertialSensor::new(peripherals.port_2),
+///     ],
+///     Angle::from_degrees(2.0),
+/// )));
+///
+/// let tracking = WheeledTracking::new(
+///     Vec2::default(),
+///     90.0.deg(),
+///     [left_wheel, right_wheel],
+///     [sideways_wheel],
+///     Some(imus.clone()),
+/// );
+///
+/// println!("Trusted IMUs: {:?}", imus.borrow().trusted());
+/// ```
+///
+/// A gyro that drops out and recovers is re-seeded rather than removed from the group.
+///
+/// ```
+/// use core::cell::Cell;
+/// use evian_math::Angle;
+/// use evian_tracking::{Gyro, GyroGroup};
+///
+/// struct FlakyGyro(Cell<Option<f64>>);
+///
+/// impl Gyro for &FlakyGyro {
+///     type Error = ();
+///
+///     fn heading(&self) -> Result<Angle, ()> {
+///         self.0.get().map(Angle::from_degrees).ok_or(())
+///     }
+///
+///     fn angular_velocity(&self) -> Result<f64, ()> {
+///         Ok(0.0)
+///     }
+/// }
+///
+/// let (a, b) = (FlakyGyro(Cell::new(Some(0.0))), FlakyGyro(Cell::new(Some(0.0))));
+/// let group = GyroGroup::new([&a, &b], Angle::from_degrees(2.0));
+/// group.heading().unwrap();
+///
+/// // The second gyro drops out, then comes back having recalibrated to a different heading.
+/// b.0.set(None);
+/// a.0.set(Some(10.0));
+/// group.heading().unwrap();
+/// b.0.set(Some(-90.0));
+/// group.heading().unwrap();
+/// assert_eq!(group.failed(), [false, false]);
+///
+/// // Once re-seeded, it contributes to the group again without its heading jumping.
+/// a.0.set(Some(20.0));
+/// b.0.set(Some(-80.0));
+/// let heading = group.heading().unwrap();
+/// assert_eq!(group.trusted(), [true, true]);
+/// assert!((heading.as_degrees() - 20.0).abs() < 1e-9);
+/// ```
+#[derive(Debug)]
+pub struct GyroGroup<G: Gyro, const N: usize> {
+    gyros: [G; N],
+    disagreement_threshold: Angle,
+    failure_limit: u32,
+    state: RefCell<GyroGroupState<N>>,
+}
+
+impl<G: Gyro, const N: usize> GyroGroup<G, N> {
+    /// Creates a new group of gyros.
+    ///
+    /// `disagreement_threshold` is the maximum amount a gyro's change in heading between two
+    /// readings may differ from the rest of the group before it is ignored.
+    pub const fn new(gyros: [G; N], disagreement_threshold: Angle) -> Self {
+        Self {
+            gyros,
+            disagreement_threshold,
+            failure_limit: Self::DEFAULT_FAILURE_LIMIT,
+            state: RefCell::new(GyroGroupState {
+                prev_headings: [None; N],
+                strikes: [0; N],
+                failed: [false; N],
+                trusted: [false; N],
+                heading: None,
+            }),
+        }
+    }
+
+    /// Default number of consecutive readings a gyro may fail or be ignored for before it is
+    /// permanently removed from the group.
+    pub const DEFAULT_FAILURE_LIMIT: u32 = 50;
+
+    /// Sets the number of consecutive readings a gyro may return an error or be ignored for
+    /// disagreeing with the group before it is permanently removed from the group.
+    ///
+    /// Defaults to [`GyroGroup::DEFAULT_FAILURE_LIMIT`].
+    #[must_use]
+    pub const fn with_failure_limit(mut self, limit: u32) -> Self {
+        self.failure_limit = limit;
+        self
+    }
+
+    /// Returns which gyros were trusted during the most recent heading reading.
+    pub fn trusted(&self) -> [bool; N] {
+        self.state.borrow().trusted
+    }
+
+    /// Returns which gyros have failed and been permanently removed from the group.
+    pub fn failed(&self) -> [bool; N] {
+        self.state.borrow().failed
+    }
+
+    /// Returns a reference to the gyros in this group.
+    pub const fn gyros(&self) -> &[G; N] {
+        &self.gyros
+    }
+
+    /// Permanently removes gyros that have reached the failure limit from the group.
+    fn retire_failing(&self, state: &mut GyroGroupState<N>) {
+        for i in 0..N {
+            if state.strikes[i] >= self.failure_limit {
+                state.failed[i] = true;
+                state.prev_headings[i] = None;
+            }
+        }
+    }
+}
+
+impl<G: Gyro, const N: usize> Gyro for GyroGroup<G, N> {
+    type Error = GyroGroupError<G::Error>;
+
+    fn heading(&self) -> Result<Angle, Self::Error> {
+        let mut state = self.state.borrow_mut();
+        let mut headings = [None; N];
+        let mut last_error = None;
+
+        for (i, gyro) in self.gyros.iter().enumerate() {
+            if state.failed[i] {
+                continue;
+            }
+
+            match gyro.heading() {
+                Ok(heading) => headings[i] = Some(heading),
+                Err(error) => {
+                    // Forget the gyro's previous heading, so its first reading after recovering
+                    // re-seeds it rather than being compared against a stale heading.
+                    state.prev_headings[i] = None;
+                    state.strikes[i] += 1;
+                    last_error = Some(error);
+                }
+            }
+        }
+        self.retire_failing(&mut state);
+
+        state.trusted = [false; N];
+
+        // The first gyro to report a heading determines the group's starting heading.
+        let Some(prev_heading) = state.heading else {
+            let Some((i, heading)) = headings
+                .iter()
+                .enumerate()
+                .find_map(|(i, heading)| heading.map(|heading| (i, heading)))
+            else {
+                return Err(last_error.map_or(GyroGroupError::Exhausted, GyroGroupError::Sensor));
+            };
+
+            state.prev_headings = headings;
+            state.trusted[i] = true;
+            state.heading = Some(heading);
+            return Ok(heading);
+        };
+
+        // Change in heading reported by each gyro since its previous reading.
+        let mut deltas = [None; N];
+        for i in 0..N {
+            if let (Some(heading), Some(prev)) = (headings[i], state.prev_headings[i]) {
+                deltas[i] = Some(signed_delta(prev, heading).as_radians());
+            }
+        }
+        state.prev_headings = headings;
+
+        // Gather the deltas into the front of a stack array to take their median, since this runs
+        // on every tracking update.
+        let mut buffer = [0.0; N];
+        let mut count = 0;
+        for delta in deltas.iter().flatten() {
+            buffer[count] = *delta;
+            count += 1;
+        }
+        let sorted = &mut buffer[..count];
+
+        if sorted.is_empty() {
+            // Gyros that just started reporting have nothing to compare against yet, so we'll
+            // hold the current heading until the next reading.
+            if headings.iter().all(Option::is_none) {
+                return Err(last_error.map_or(GyroGroupError::Exhausted, GyroGroupError::Sensor));
+            }
+            return Ok(prev_heading);
+        }
+        sorted.sort_by(f64::total_cmp);
+
+        let median = if sorted.len().is_multiple_of(2) {
+            f64::midpoint(sorted[sorted.len() / 2 - 1], sorted[sorted.len() / 2])
+        } else {
+            sorted[sorted.len() / 2]
+        };
+
+        let threshold = self.disagreement_threshold.as_radians().abs();
+        let mut delta_sum = 0.0;
+        let mut trusted_count = 0;
+
+        for (i, delta) in deltas.iter().enumerate() {
+            if let Some(delta) = *delta
+                && (delta - median).abs() <= threshold
+            {
+                state.trusted[i] = true;
+                delta_sum += delta;
+                trusted_count += 1;
+            }
+        }
+
+        // No gyros agree with each other, so trust whichever one moved the least. Disagreements
+        // are usually caused by a single gyro jumping, not by one failing to move.
+        if trusted_count == 0 {
+            let (i, delta) = deltas
+                .iter()
+                .enumerate()
+                .filter_map(|(i, delta)| delta.map(|delta| (i, delta)))
+                .min_by(|(_, a), (_, b)| a.abs().total_cmp(&b.abs()))
+                .unwrap();
+
+            state.trusted[i] = true;
+            delta_sum = delta;
+            trusted_count = 1;
+        }
+
+        // Gyros are only forgiven for earlier errors or disagreements once they're trusted again.
+        for (i, delta) in deltas.iter().enumerate() {
+            if state.trusted[i] {
+                state.strikes[i] = 0;
+            } else if delta.is_some() {
+                state.strikes[i] += 1;
+            }
+        }
+        self.retire_failing(&mut state);
+
+        let heading = (prev_heading + Angle::from_radians(delta_sum / f64::from(trusted_count)))
+            .wrapped_full();
+        state.heading = Some(heading);
+
+        Ok(heading)
+    }
+
+    fn angular_velocity(&self) -> Result<f64, Self::Error> {
+        let state = self.state.borrow();
+        let mut velocity_sum = 0.0;
+        let mut count = 0;
+        let mut last_error = None;
+
+        for (i, gyro) in self.gyros.iter().enumerate() {
+            // Prefer gyros trusted in the latest heading reading, but fall back to any healthy
+            // gyro if none have been trusted yet.
+            if state.failed[i] || (state.trusted.contains(&true) && !state.trusted[i]) {
+                continue;
+            }
+
+            match gyro.angular_velocity() {
+                Ok(velocity) => {
+                    velocity_sum += velocity;
+                    count += 1;
+                }
+                Err(error) => last_error = Some(error),
+            }
+        }
+
+        if count == 0 {
+            return Err(last_error.map_or(GyroGroupError::Exhausted, GyroGroupError::Sensor));
+        }
+
+        Ok(velocity_sum / f64::from(count))
+    }
+}
@@ -0,0 +1,91 @@
+//! Awaitable tracking conditions.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use vexide::{
+    smart::motor::Motor,
+    time::{Sleep, sleep},
+};
+
+/// Waits until a condition on a tracking system is met.
+///
+/// This future is created by [`Tracking::wait_until`](crate::Tracking::wait_until). It checks
+/// its predicate against the tracking system on the same interval that tracking systems update
+/// on, resolving to `true` once the predicate returns `true`, or to `false` if its timeout
+/// elapses first.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WaitUntilFuture<'a, T, F>
+where
+    F: FnMut(&T) -> bool + Unpin,
+{
+    tracking: &'a T,
+    predicate: F,
+    timeout: Option<Duration>,
+    start_time: Option<Instant>,
+    sleep: Option<Sleep>,
+}
+
+impl<'a, T, F> WaitUntilFuture<'a, T, F>
+where
+    F: FnMut(&T) -> bool + Unpin,
+{
+    pub(crate) const fn new(tracking: &'a T, predicate: F) -> Self {
+        Self {
+            tracking,
+            predicate,
+            timeout: None,
+            start_time: None,
+            sleep: None,
+        }
+    }
+
+    /// Gives up waiting once `timeout` has elapsed, resolving to `false`.
+    pub const fn with_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Removes this future's timeout, waiting for the condition indefinitely.
+    pub const fn without_timeout(&mut self) -> &mut Self {
+        self.timeout = None;
+        self
+    }
+}
+
+impl<T, F> Future for WaitUntilFuture<'_, T, F>
+where
+    F: FnMut(&T) -> bool + Unpin,
+{
+    type Output = bool;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let start_time = *this.start_time.get_or_insert_with(Instant::now);
+
+        loop {
+            if let Some(sleep) = &mut this.sleep
+                && Pin::new(sleep).poll(cx).is_pending()
+            {
+                return Poll::Pending;
+            }
+
+            if (this.predicate)(this.tracking) {
+                return Poll::Ready(true);
+            }
+
+            if this
+                .timeout
+                .is_some_and(|timeout| start_time.elapsed() > timeout)
+            {
+                return Poll::Ready(false);
+            }
+
+            this.sleep = Some(sleep(Motor::WRITE_INTERVAL));
+        }
+    }
+}
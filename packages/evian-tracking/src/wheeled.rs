@@ -2,7 +2,13 @@
 
 use evian_math::{Angle, IntoAngle, Vec2};
 
-use std::{cell::RefCell, f64::consts::PI, rc::Rc, time::Instant};
+use std::{
+    cell::RefCell,
+    f64::consts::PI,
+    rc::Rc,
+    time::{Duration, Instant},
+    vec::Vec,
+};
 use vexide::{
     smart::motor::Motor,
     task::{Task, spawn},
@@ -10,7 +16,9 @@ use vexide::{
 };
 
 use crate::{
-    Tracking, TracksForwardTravel, TracksHeading, TracksPosition,
+    PoseBuffer, PoseSample, Tracking, TracksAcceleration, TracksForwardTravel, TracksHeading,
+    TracksPosition,
+    acceleration::AccelerationEstimator,
     sensor::{Gyro, RotarySensor},
 };
 
@@ -33,10 +41,15 @@ pub struct TrackingWheel<T: RotarySensor> {
     /// the center of rotation.
     pub offset: f64,
 
-    /// External gearing of the wheel.
+    /// External gearing between the wheel and its sensor.
+    pub gearing: GearRatio,
+
+    /// Calibration multiplier applied to the distance traveled by the wheel.
     ///
-    /// Used as a multiplier when determining wheel travel.
-    pub gearing: Option<f64>,
+    /// This corrects for the wheel's true diameter differing from its nominal diameter (such as
+    /// from tread wear or compression), and defaults to `1.0`. See
+    /// [`TrackingWheel::with_scale`] for how to measure it.
+    pub scale: f64,
 }
 
 impl<T: RotarySensor> TrackingWheel<T> {
@@ -47,20 +60,103 @@ impl<T: RotarySensor> TrackingWheel<T> {
     /// * `sensor` - The rotary sensor to read wheel rotation from.
     /// * `wheel_diameter` - The diameter of the wheel in linear units.
     /// * `offset` - Distance from wheel to robot's center of rotation.
-    /// * `gearing` - Optional gear ratio between sensor and wheel (use None for 1:1 if ungeared).
-    pub const fn new(sensor: T, wheel_diameter: f64, offset: f64, gearing: Option<f64>) -> Self {
+    /// * `gearing` - Gear ratio between the sensor and wheel. This may either be a [`GearRatio`],
+    ///   or an `Option<f64>` multiplier applied to the sensor's rotation (with `None` meaning the
+    ///   sensor is directly mounted to the wheel).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evian_math::Angle;
+    /// use evian_tracking::{
+    ///     RotarySensor,
+    ///     wheeled::{GearRatio, TrackingWheel},
+    /// };
+    ///
+    /// struct Encoder(Angle);
+    ///
+    /// impl RotarySensor for Encoder {
+    ///     type Error = ();
+    ///
+    ///     fn position(&self) -> Result<Angle, ()> {
+    ///         Ok(self.0)
+    ///     }
+    /// }
+    ///
+    /// let direct = TrackingWheel::new(Encoder(Angle::FULL_TURN), 2.75, 0.0, None);
+    /// let geared = TrackingWheel::new(
+    ///     Encoder(Angle::FULL_TURN),
+    ///     2.75,
+    ///     0.0,
+    ///     // Encoder turns twice for every turn of the wheel.
+    ///     GearRatio::new(2.0, 1.0),
+    /// );
+    ///
+    /// assert_eq!(geared.travel(), direct.travel().map(|travel| travel / 2.0));
+    /// ```
+    pub fn new(
+        sensor: T,
+        wheel_diameter: f64,
+        offset: f64,
+        gearing: impl Into<GearRatio>,
+    ) -> Self {
         Self {
             sensor,
             wheel_diameter,
             offset,
-            gearing,
+            gearing: gearing.into(),
+            scale: 1.0,
         }
     }
 
+    /// Sets a calibration multiplier applied to the distance traveled by this wheel.
+    ///
+    /// Nominal wheel diameters are rarely exact, so odometry using them will usually be off by a
+    /// small but consistent percentage. To calibrate a wheel:
+    ///
+    /// 1. Push or drive the robot in a straight line over a known distance (a longer
+    ///    distance gives a more precise measurement).
+    /// 2. Read the distance traveled by the wheel, as reported by [`TrackingWheel::travel`].
+    /// 3. Set the scale to the known distance divided by the reported distance.
+    ///
+    /// This is applied in addition to the wheel's [`gearing`](TrackingWheel::gearing), and
+    /// defaults to `1.0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evian_math::Angle;
+    /// use evian_tracking::{RotarySensor, wheeled::TrackingWheel};
+    ///
+    /// struct Encoder(Angle);
+    ///
+    /// impl RotarySensor for Encoder {
+    ///     type Error = ();
+    ///
+    ///     fn position(&self) -> Result<Angle, ()> {
+    ///         Ok(self.0)
+    ///     }
+    /// }
+    ///
+    /// // The robot was pushed 48 inches, but the wheel thinks it traveled 48.6 inches.
+    /// let turns = 48.6 / (2.75 * core::f64::consts::PI);
+    /// let wheel = TrackingWheel::new(Encoder(Angle::from_turns(turns)), 2.75, 0.0, None);
+    /// let reported = wheel.travel().unwrap();
+    ///
+    /// let wheel = wheel.with_scale(48.0 / reported);
+    /// assert!((wheel.travel().unwrap() - 48.0).abs() < 1e-9);
+    /// ```
+    #[must_use]
+    pub const fn with_scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+        self
+    }
+
     /// Calculates the total linear distance traveled by this wheel.
     ///
-    /// This method uses the wheel's diameter and gear ratio to convert
-    /// sensor rotations into linear distance traveled.
+    /// This method uses the wheel's diameter, gear ratio, and calibration
+    /// [scale](TrackingWheel::scale) to convert sensor rotations into linear
+    /// distance traveled.
     ///
     /// # Errors
     ///
@@ -68,37 +164,329 @@ impl<T: RotarySensor> TrackingWheel<T> {
     pub fn travel(&self) -> Result<f64, T::Error> {
         let wheel_circumference = self.wheel_diameter * PI;
 
-        Ok(self.sensor.position()?.as_turns() * self.gearing.unwrap_or(1.0) * wheel_circumference)
+        Ok(self.sensor.position()?.as_turns()
+            * self.gearing.multiplier()
+            * wheel_circumference
+            * self.scale)
     }
 }
 
-enum HeadingError<T: RotarySensor> {
-    /// Gyro failed for whatever reason (we don't care exactly what happened,
-    /// since all gyro failures are potentially fatal to tracking).
+/// Gear ratio between a tracking wheel and the sensor measuring it.
+///
+/// Ratios are expressed as `sensor:wheel`, meaning a ratio of `2:1` describes a sensor that makes
+/// two full rotations for every rotation of its wheel.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GearRatio {
+    multiplier: f64,
+}
+
+impl GearRatio {
+    /// A sensor mounted directly to its wheel (1:1).
+    pub const DIRECT: Self = Self { multiplier: 1.0 };
+
+    /// Creates a gear ratio of `sensor:wheel`.
     ///
-    /// A backup angle computed from two parallel forward tracking is provided,
-    /// if such wheels are available.
-    Imu(Option<Angle>),
+    /// `sensor` is the number of rotations the sensor makes for every `wheel` rotations of the
+    /// wheel (or equivalently, the number of teeth on the wheel's gear and the sensor's gear
+    /// respectively).
+    #[must_use]
+    pub const fn new(sensor: f64, wheel: f64) -> Self {
+        Self {
+            multiplier: wheel / sensor,
+        }
+    }
+
+    /// Creates a gear ratio from a multiplier that converts sensor rotations into wheel
+    /// rotations.
+    #[must_use]
+    pub const fn from_multiplier(multiplier: f64) -> Self {
+        Self { multiplier }
+    }
+
+    /// Returns the number of wheel rotations per rotation of the sensor.
+    #[must_use]
+    pub const fn multiplier(&self) -> f64 {
+        self.multiplier
+    }
+}
 
-    /// [`RotarySensor`] failed to return position data required to compute heading
-    /// from a pair of parallel forward tracking wheels.
-    RotarySensor(T::Error),
+impl Default for GearRatio {
+    fn default() -> Self {
+        Self::DIRECT
+    }
+}
+
+impl From<Option<f64>> for GearRatio {
+    /// Converts an optional multiplier into a gear ratio, where `None` is [`GearRatio::DIRECT`].
+    fn from(multiplier: Option<f64>) -> Self {
+        multiplier.map_or(Self::DIRECT, Self::from_multiplier)
+    }
+}
+
+// MARK: Units
+
+/// A unit of length.
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum LinearUnit {
+    /// Inches.
+    #[default]
+    Inches,
+
+    /// Feet.
+    Feet,
+
+    /// Millimeters.
+    Millimeters,
+
+    /// Centimeters.
+    Centimeters,
+
+    /// Meters.
+    Meters,
+}
+
+impl LinearUnit {
+    /// Returns the number of inches in one of this unit.
+    #[must_use]
+    pub const fn inches_per_unit(self) -> f64 {
+        match self {
+            Self::Inches => 1.0,
+            Self::Feet => 12.0,
+            Self::Millimeters => 1.0 / 25.4,
+            Self::Centimeters => 1.0 / 2.54,
+            Self::Meters => 1.0 / 0.0254,
+        }
+    }
+}
+
+/// The direction in which headings increase.
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum HeadingConvention {
+    /// Headings increase as the robot turns counterclockwise, and the y-axis points 90 degrees
+    /// counterclockwise from the x-axis (the standard cartesian coordinate system).
+    #[default]
+    CounterClockwise,
+
+    /// Headings increase as the robot turns clockwise, and the y-axis points 90 degrees clockwise
+    /// from the x-axis.
+    ///
+    /// The y-axis is mirrored alongside the heading so that the angle between two points is still
+    /// measured in the same direction as the robot's heading, which is what motion algorithms
+    /// expect.
+    Clockwise,
+}
+
+/// Units and conventions used by the public API of a [`WheeledTracking`] system.
+///
+/// Wheel diameters, wheel offsets, positions, distances, and velocities are all measured in
+/// [`linear`](TrackingUnits::linear) units, while headings and angular velocities follow the
+/// [`heading`](TrackingUnits::heading) convention.
+///
+/// Internally, tracking is always performed in inches with counterclockwise-positive headings,
+/// and values are converted to and from these units at the API boundary. By default, tracking
+/// uses inches and counterclockwise-positive headings, meaning no conversion occurs.
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct TrackingUnits {
+    /// Unit of length.
+    pub linear: LinearUnit,
+
+    /// Direction in which headings increase.
+    pub heading: HeadingConvention,
+}
+
+impl TrackingUnits {
+    /// Creates a new set of tracking units.
+    #[must_use]
+    pub const fn new(linear: LinearUnit, heading: HeadingConvention) -> Self {
+        Self { linear, heading }
+    }
+
+    /// Converts a length from these units to inches.
+    const fn length_to_inches(&self, length: f64) -> f64 {
+        length * self.linear.inches_per_unit()
+    }
+
+    /// Converts a length from inches to these units.
+    const fn length_from_inches(&self, length: f64) -> f64 {
+        length / self.linear.inches_per_unit()
+    }
+
+    /// Converts the geometry of a tracking wheel from these units to inches.
+    const fn wheel_to_inches<T: RotarySensor>(
+        &self,
+        mut wheel: TrackingWheel<T>,
+    ) -> TrackingWheel<T> {
+        wheel.wheel_diameter = self.length_to_inches(wheel.wheel_diameter);
+        wheel.offset = self.length_to_inches(wheel.offset);
+        wheel
+    }
+
+    /// Flips the sign of a rotational quantity if headings are clockwise-positive.
+    ///
+    /// Since this is a reflection, the same conversion is used in both directions.
+    fn convert_rotation(&self, value: f64) -> f64 {
+        match self.heading {
+            HeadingConvention::CounterClockwise => value,
+            HeadingConvention::Clockwise => -value,
+        }
+    }
+
+    /// Converts an angle between these units and counterclockwise-positive headings.
+    fn convert_heading(&self, heading: Angle) -> Angle {
+        match self.heading {
+            HeadingConvention::CounterClockwise => heading,
+            HeadingConvention::Clockwise => -heading,
+        }
+    }
+
+    /// Converts a point from these units to inches in a standard cartesian coordinate system.
+    fn position_to_inches(&self, position: Vec2<f64>) -> Vec2<f64> {
+        Vec2::new(
+            self.length_to_inches(position.x),
+            self.convert_rotation(self.length_to_inches(position.y)),
+        )
+    }
+
+    /// Converts a point from inches in a standard cartesian coordinate system to these units.
+    fn position_from_inches(&self, position: Vec2<f64>) -> Vec2<f64> {
+        Vec2::new(
+            self.length_from_inches(position.x),
+            self.convert_rotation(self.length_from_inches(position.y)),
+        )
+    }
+}
+
+// MARK: Slip Detection
+
+/// The direction of travel that a tracking wheel measures.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum WheelAxis {
+    /// A wheel measuring travel along the robot's forward axis.
+    Forward,
+
+    /// A wheel measuring travel along the robot's sideways axis.
+    Sideways,
+}
+
+/// Slip detection status of a tracking wheel.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WheelStatus {
+    /// The axis of the wheel.
+    pub axis: WheelAxis,
+
+    /// Index of the wheel in the array of wheels passed to [`WheeledTracking::new`] for its axis.
+    pub index: usize,
+
+    /// Difference between this wheel's velocity and the velocity it was expected to measure,
+    /// measured in wheel units per second.
+    ///
+    /// The expected velocity is measured by the axis's slip reference if one is set (see
+    /// [`WheeledTracking::set_slip_reference`]), and is otherwise the average velocity of the
+    /// other wheels on the same axis. Velocities are compensated for the robot's rotation, so
+    /// wheels at different offsets are still directly comparable while turning.
+    pub velocity_error: f64,
+}
+
+/// An independent measurement of the robot's travel along one axis, which tracking wheels are
+/// checked against for slip.
+struct SlipReference {
+    travel: Box<dyn Fn() -> Option<f64>>,
+    offset: f64,
+    prev_travel: Option<f64>,
+}
+
+impl SlipReference {
+    fn new<T: RotarySensor + 'static>(wheel: TrackingWheel<T>) -> Self {
+        Self {
+            offset: wheel.offset,
+            travel: Box::new(move || wheel.travel().ok()),
+            prev_travel: None,
+        }
+    }
+
+    /// Reads the reference's travel, returning its change since the last update compensated for
+    /// the robot's rotation.
+    fn update(&mut self, delta_heading: Angle) -> Option<f64> {
+        let travel = (self.travel)();
+        let delta = travel
+            .zip(self.prev_travel)
+            .map(|(travel, prev_travel)| {
+                (travel - prev_travel) - self.offset * delta_heading.as_radians()
+            });
+        self.prev_travel = travel;
+
+        delta
+    }
+}
+
+impl core::fmt::Debug for SlipReference {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SlipReference")
+            .field("offset", &self.offset)
+            .field("prev_travel", &self.prev_travel)
+            .finish_non_exhaustive()
+    }
+}
+
+// MARK: Heading Sources
+
+/// A sensor that [`WheeledTracking`] can determine the robot's heading from.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum HeadingSource {
+    /// Heading is read from a [`Gyro`].
+    Gyro,
+
+    /// Heading is computed from the difference in travel between two parallel forward tracking
+    /// wheels.
+    Wheels,
 }
 
 /// Generic tracking data returned by [`ParallelWheelTracking`] and [`PerpendicularWheelTracking`].
-#[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[derive(Default, Debug)]
 pub(crate) struct TrackingData {
     position: Vec2<f64>,
     raw_heading: Angle,
     heading_offset: Angle,
     forward_travel: f64,
     linear_velocity: f64,
+    linear_acceleration: f64,
     angular_velocity: f64,
+    slip_threshold: Option<f64>,
+    forward_slip_reference: Option<SlipReference>,
+    sideways_slip_reference: Option<SlipReference>,
+    exclude_slipping_wheels: bool,
+    slipping_wheels: Vec<WheelStatus>,
+    max_velocity: Option<f64>,
+    last_rejected_delta: Option<Vec2<f64>>,
+    pose_history: PoseBuffer,
+    pose_history_spacing: f64,
+    heading_source: Option<HeadingSource>,
+    scrub_factor: f64,
+    forward_wheel_travel: Vec<Option<f64>>,
+    sideways_wheel_travel: Vec<Option<f64>>,
+    forward_wheel_zeros: Vec<f64>,
+    sideways_wheel_zeros: Vec<f64>,
+    units: TrackingUnits,
 }
 
 // MARK: Tracking Implementation
 
 /// Tracking system that uses wheels to track position and orientation.
+///
+/// # Heading Sources
+///
+/// The robot's heading can be determined either from a [`Gyro`] or from two parallel forward
+/// tracking wheels with roughly the same offset from the center of the robot. When both are
+/// available, the gyro is preferred since it isn't affected by wheel slip, and the tracking
+/// wheels are used as a fallback whenever the gyro fails to return a reading (such as while it
+/// is calibrating, or if it is disconnected). The tracking system switches back to the gyro once
+/// it recovers.
+///
+/// Each time the tracking system switches sources, the new source is offset to continue from
+/// the last heading given by the previous source, so switching doesn't cause the heading to
+/// jump. This also means that a gyro which recalibrates after losing power doesn't reset the
+/// robot's heading. The source currently in use is returned by
+/// [`WheeledTracking::heading_source`].
 #[derive(Debug)]
 pub struct WheeledTracking {
     data: Rc<RefCell<TrackingData>>,
@@ -107,6 +495,10 @@ pub struct WheeledTracking {
 
 impl WheeledTracking {
     /// Creates a new wheeled tracking system.
+    ///
+    /// All lengths are measured in the same units as the given tracking wheels, and headings are
+    /// counterclockwise-positive. See [`WheeledTracking::with_units`] to track using a specific
+    /// set of units.
     pub fn new<
         T: RotarySensor + 'static,
         U: RotarySensor + 'static,
@@ -118,8 +510,53 @@ impl WheeledTracking {
         heading: Angle,
         forward_wheels: [TrackingWheel<T>; NUM_FORWARD],
         sideways_wheels: [TrackingWheel<U>; NUM_SIDEWAYS],
-        mut gyro: Option<G>,
+        gyro: Option<G>,
+    ) -> Self {
+        Self::with_units(
+            origin,
+            heading,
+            forward_wheels,
+            sideways_wheels,
+            gyro,
+            TrackingUnits::default(),
+        )
+    }
+
+    /// Creates a new wheeled tracking system using a given set of units.
+    ///
+    /// `origin`, `heading`, and the diameters and offsets of each tracking wheel are interpreted
+    /// in `units`, and all values returned by the tracking system will be reported in `units`.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let tracking = WheeledTracking::with_units(
+    ///     Vec2::default(),
+    ///     90.0.deg(),
+    ///     [
+    ///         TrackingWheel::new(left_motors.clone(), 8.255, -18.0, Some(36.0 / 48.0)),
+    ///         TrackingWheel::new(right_motors.clone(), 8.255, 18.0, Some(36.0 / 48.0)),
+    ///     ],
+    ///     [TrackingWheel::new(sideways_rotation, 5.08, -5.0, None)],
+    ///     Some(imu),
+    ///     TrackingUnits::new(LinearUnit::Centimeters, HeadingConvention::Clockwise),
+    /// );
+    /// ```
+    pub fn with_units<
+        T: RotarySensor + 'static,
+        U: RotarySensor + 'static,
+        G: Gyro + 'static,
+        const NUM_FORWARD: usize,
+        const NUM_SIDEWAYS: usize,
+    >(
+        origin: impl Into<Vec2<f64>>,
+        heading: Angle,
+        forward_wheels: [TrackingWheel<T>; NUM_FORWARD],
+        sideways_wheels: [TrackingWheel<U>; NUM_SIDEWAYS],
+        gyro: Option<G>,
+        units: TrackingUnits,
     ) -> Self {
+        // Measured in inches.
         const FORWARD_TRACKER_OFFSET_TOLERANCE: f64 = 0.5;
 
         const {
@@ -134,6 +571,11 @@ impl WheeledTracking {
             "Wheeled tracking requires either a Gyro or two parallel forward tracking wheels to determine robot orientation."
         );
 
+        // Tracking is internally performed in inches, so convert our wheels' geometry before
+        // doing anything else with them.
+        let forward_wheels = forward_wheels.map(|wheel| units.wheel_to_inches(wheel));
+        let sideways_wheels = sideways_wheels.map(|wheel| units.wheel_to_inches(wheel));
+
         // Locate two parallel tracking wheels with roughly the same absolute offset from the
         // center of tracking. We use these for our backup wheeled heading calculations.
         let mut parallel_forward_indicies = None;
@@ -166,23 +608,16 @@ impl WheeledTracking {
         let initial_sideways_wheel_data = sideways_wheels
             .each_ref()
             .map(|wheel| wheel.travel().map(|travel| (travel, wheel.offset)));
-        let initial_raw_heading = match Self::compute_raw_heading(
+        // If no heading source is working yet, the first source to return a reading will be
+        // offset to start from zero.
+        let initial_heading = Self::read_heading(
             gyro.as_ref(),
             parallel_forward_indicies.map(|(left_index, right_index)| {
                 (&forward_wheels[left_index], &forward_wheels[right_index])
             }),
-        ) {
-            Ok(heading) => heading,
-            Err(HeadingError::Imu(wheel_heading)) => {
-                gyro = None;
-                // NOTE: This returning `None` means that there's no real point in spawning the task
-                // since the gyro disconnected, but we'll leave that to the task to figure out,
-                // since there's an early return condition in the loop if this occurs and we don't
-                // want to have to handle errors in this specific function.
-                wheel_heading.unwrap_or_default()
-            }
-            _ => Angle::default(),
-        };
+            1.0,
+        );
+        let initial_raw_heading = initial_heading.map_or(Angle::ZERO, |(heading, _)| heading);
         let initial_forward_travel = {
             let mut travel_sum = 0.0;
 
@@ -203,9 +638,22 @@ impl WheeledTracking {
         };
 
         let data = Rc::new(RefCell::new(TrackingData {
-            position: origin.into(),
-            heading_offset: heading,
+            position: units.position_to_inches(origin.into()),
+            heading_offset: units.convert_heading(heading),
             raw_heading: initial_raw_heading,
+            heading_source: initial_heading.map(|(_, source)| source),
+            scrub_factor: 1.0,
+            forward_wheel_travel: initial_forward_wheel_data
+                .iter()
+                .map(|data| data.as_ref().ok().map(|(travel, _)| *travel))
+                .collect(),
+            sideways_wheel_travel: initial_sideways_wheel_data
+                .iter()
+                .map(|data| data.as_ref().ok().map(|(travel, _)| *travel))
+                .collect(),
+            forward_wheel_zeros: Vec::from([0.0; NUM_FORWARD]),
+            sideways_wheel_zeros: Vec::from([0.0; NUM_SIDEWAYS]),
+            units,
             ..Default::default()
         }));
 
@@ -243,85 +691,114 @@ impl WheeledTracking {
 
     // MARK: Heading Calculation
 
-    /// Determines the orientation of the robot.
+    /// Reads the orientation of the robot from the highest-priority heading source that is
+    /// currently working.
     ///
-    /// "raw" in this case refers to the fact that the angle returned by this method has not been
-    /// offset by any amount (meaning the user's "initial heading" configuration isn't considered),
-    /// and has unspecified bounds (it may be out of the range of [0, 2π]). The angle is guaranteed
-    /// to be counterclockwise-positive, but is otherwise a raw reading from whatever sensor is
-    /// being used to determine orientation (either tracking wheels or a [`Gyro`]).
+    /// The angle returned by this method is a raw reading from whichever sensor was used (either
+    /// a [`Gyro`] or a pair of parallel tracking wheels), meaning it hasn't been offset by any
+    /// amount and has unspecified bounds. The angle is guaranteed to be
+    /// counterclockwise-positive.
     ///
-    /// To determine the final heading value returned by [`Self::heading`], you must add the heading
-    /// offset value and wrap the angle from [0, 2π] using [`Angle::wrapped_positive`].
-    ///
-    /// # Errors
-    ///
-    /// There are two ways to determine robot orientation with wheeled tracking. You can either use
-    /// a [`Gyro`], or you can use two parallel tracking wheels with roughly the same offset
-    /// (spacing) from the center of rotation on the robot.
-    ///
-    /// - If the gyro fails to return a value, then [`HeadingError::Imu`] will be returned, and a
-    ///   fallback wheeled heading may be available to use in this error type if the tracking setup
-    ///   has parallel tracking wheels that support it.
-    /// - If a tracking wheel fails then [`HeadingError::RotarySensor`] will be returned containing
-    ///   the underlying error that occurred.
-    ///
-    /// # Panics
-    ///
-    /// An assertion will panic if both `gyro` and `parallel_wheels` is `None`. This should never
-    /// happen.
-    fn compute_raw_heading<G: Gyro, T: RotarySensor>(
+    /// The gyro is read first, falling back to the tracking wheels if the gyro fails or isn't
+    /// present. Returns `None` if neither source is able to provide a reading. Wheel headings
+    /// are computed using the track width multiplied by `scrub_factor`.
+    fn read_heading<G: Gyro, T: RotarySensor>(
         gyro: Option<&G>,
         parallel_wheels: Option<(&TrackingWheel<T>, &TrackingWheel<T>)>,
-    ) -> Result<Angle, HeadingError<T>> {
-        assert!(
-            gyro.is_some() || parallel_wheels.is_some(),
-            "No gyro or wheeled tracking sensors provided to compute_heading."
-        );
+        scrub_factor: f64,
+    ) -> Option<(Angle, HeadingSource)> {
+        // Gyro headings are converted to be counterclockwise-positive by the `Gyro` trait.
+        if let Some(Ok(gyro_heading)) = gyro.map(Gyro::heading) {
+            return Some((gyro_heading, HeadingSource::Gyro));
+        }
+
+        let (left_wheel, right_wheel) = parallel_wheels?;
+
+        // Effective distance between the left and right wheels.
+        let track_width = (left_wheel.offset.abs() + right_wheel.offset) * scrub_factor;
 
-        // Try to get a reading of the robot's heading from the gyro. We should only do this if the
-        // gyro hasn't returned a port-related error before (flagged by the `gyro_invalid`
-        // variable). If it has, the gyro has no chance of recovery and we should fallback to
-        // wheeled heading calculation.
-        let gyro_rotation = gyro.as_ref().map(|gyro| gyro.heading());
-
-        // Compute the unbounded robot orientation in radians. In the case of the gyro, this
-        // actually is bounded to [0, TAU] already due to how gyro heading works, but this will be
-        // wrapped to [0, TAU] regardless later either way.
-        let raw_heading = if let Some(Ok(gyro_heading)) = gyro_rotation {
-            // gyros' frame of reference is NED (Z-Down), meaning heading increases as the robot
-            // turns clockwise. We don't want this, since it doesn't match up with how the unit
-            // circle works with cartesian coordinates (what we localize in), so we need to convert
-            // to a CCW+ angle system.
-            gyro_heading.as_radians()
-        } else if let Some((left_wheel, right_wheel)) = parallel_wheels {
-            // Distance between the left and right wheels.
-            let track_width = left_wheel.offset.abs() + right_wheel.offset;
-
-            // Nothing we can use if either of these disconnects, so all we can do is wait for them
-            // to reconnect. Seriously, fix your wiring!
-            let left_travel = left_wheel
-                .travel()
-                .map_err(|err| HeadingError::RotarySensor(err))?;
-            let right_travel = right_wheel
-                .travel()
-                .map_err(|err| HeadingError::RotarySensor(err))?;
-
-            (right_travel - left_travel) / track_width
-        } else if let Some(Err(_)) = gyro_rotation {
-            // gyro failed and we have no viable sensors to determine heading. Nothing we can do to
-            // recover from this.
-            return Err(HeadingError::Imu(None));
-        } else {
-            unreachable!() // handled by the assertion at the top of this function
+        // Nothing we can use if either of these disconnects, so all we can do is wait for them
+        // to reconnect. Seriously, fix your wiring!
+        let left_travel = left_wheel.travel().ok()?;
+        let right_travel = right_wheel.travel().ok()?;
+
+        Some((
+            ((right_travel - left_travel) / track_width).rad(),
+            HeadingSource::Wheels,
+        ))
+    }
+
+    /// Determines which wheels on a given axis are slipping.
+    ///
+    /// Each wheel's change in travel is compensated for the robot's rotation (a wheel offset from
+    /// the center of rotation will travel further while turning), then compared against the
+    /// compensated change in travel of the axis's slip reference. Wheels whose velocity differs
+    /// from the reference by more than `threshold` are flagged as slipping and recorded in
+    /// `statuses`.
+    ///
+    /// Without a reference, each wheel is instead compared against the average of all other
+    /// wheels on the same axis. With only two wheels, a disagreement can't be attributed to
+    /// either of them, so this needs at least three wheels to detect anything. Only wheels that
+    /// returned a reading this iteration and the previous iteration can be considered.
+    #[allow(clippy::too_many_arguments)]
+    fn detect_slip<E, const N: usize>(
+        axis: WheelAxis,
+        wheel_data: &[Result<(f64, f64), E>; N],
+        prev_wheel_data: &[Result<(f64, f64), E>; N],
+        reference_delta: Option<f64>,
+        delta_heading: Angle,
+        dt: Duration,
+        threshold: f64,
+        statuses: &mut Vec<WheelStatus>,
+    ) -> [bool; N] {
+        let mut slipping = [false; N];
+
+        let compensated_deltas: [Option<f64>; N] = core::array::from_fn(|i| {
+            if let Ok((travel, offset)) = wheel_data[i]
+                && let Ok((prev_travel, _)) = prev_wheel_data[i]
+            {
+                Some((travel - prev_travel) - offset * delta_heading.as_radians())
+            } else {
+                None
+            }
+        });
+
+        let (sum, count) = compensated_deltas
+            .iter()
+            .flatten()
+            .fold((0.0, 0), |(sum, count), delta| (sum + delta, count + 1));
+
+        if dt.is_zero() || (reference_delta.is_none() && count < 3) {
+            return slipping;
+        }
+
+        for (i, delta) in compensated_deltas.iter().enumerate() {
+            if let Some(delta) = delta {
+                let expected = reference_delta.unwrap_or((sum - delta) / f64::from(count - 1));
+                let velocity_error = (delta - expected) / dt.as_secs_f64();
+
+                if velocity_error.abs() > threshold {
+                    slipping[i] = true;
+                    statuses.push(WheelStatus {
+                        axis,
+                        index: i,
+                        velocity_error,
+                    });
+                }
+            }
         }
-        .rad();
 
-        match gyro_rotation {
-            // gyro failed but we have a wheeled heading source to fall back to.
-            Some(Err(_)) => Err(HeadingError::Imu(Some(raw_heading))),
-            _ => Ok(raw_heading),
+        // If there isn't a single wheel we trust on this axis, then there's no way to tell
+        // which wheels are actually at fault, so don't exclude any of them.
+        if compensated_deltas
+            .iter()
+            .zip(&slipping)
+            .all(|(delta, slipping)| delta.is_none() || *slipping)
+        {
+            slipping = [false; N];
         }
+
+        slipping
     }
 
     // MARK: Task
@@ -336,7 +813,7 @@ impl WheeledTracking {
     >(
         forward_wheels: [TrackingWheel<T>; NUM_FORWARD],
         sideways_wheels: [TrackingWheel<U>; NUM_SIDEWAYS],
-        mut gyro: Option<G>,
+        gyro: Option<G>,
         data: Rc<RefCell<TrackingData>>,
         parallel_forward_indicies: Option<(usize, usize)>,
         mut prev_forward_wheel_data: [Result<(f64, f64), <T as RotarySensor>::Error>; NUM_FORWARD],
@@ -346,6 +823,12 @@ impl WheeledTracking {
         mut prev_forward_travel: f64,
     ) {
         let mut prev_time = Instant::now();
+        let mut acceleration = AccelerationEstimator::default();
+
+        // Offset applied to readings from the current heading source, so that the heading stays
+        // continuous when switching between sources.
+        let mut source_offset = Angle::ZERO;
+        let mut prev_scrub_factor = 1.0;
 
         loop {
             sleep(Motor::WRITE_INTERVAL).await;
@@ -359,52 +842,107 @@ impl WheeledTracking {
                 .each_ref()
                 .map(|wheel| wheel.travel().map(|travel| (travel, wheel.offset)));
 
+            for (travel, reading) in data
+                .forward_wheel_travel
+                .iter_mut()
+                .zip(&forward_wheel_data)
+            {
+                *travel = reading.as_ref().ok().map(|(travel, _)| *travel);
+            }
+            for (travel, reading) in data
+                .sideways_wheel_travel
+                .iter_mut()
+                .zip(&sideways_wheel_data)
+            {
+                *travel = reading.as_ref().ok().map(|(travel, _)| *travel);
+            }
+
             // Calculate absolute robot orientation (heading).
             //
             // This can be done in two possible ways - Either using a gyro (if it is available and
             // actually working) or through the use of two parallel forward trackers. The former is
             // generally far more reliable and isn't prone to wheel slip.
-            data.raw_heading = match Self::compute_raw_heading(
+            let Some((heading_reading, heading_source)) = Self::read_heading(
                 gyro.as_ref(),
                 parallel_forward_indicies.map(|(left_index, right_index)| {
                     (&forward_wheels[left_index], &forward_wheels[right_index])
                 }),
-            ) {
-                // Cool
-                Ok(raw_heading) => raw_heading,
-
-                // We got an error from the gyro, which means it likely disconnected. Once a gyro
-                // disconnects it will reclibrate upon regaining power, which will mess tracking up
-                // badly, so we need to stop using it in this case and switched to a wheeled method
-                // of determining heading.
-                Err(HeadingError::Imu(raw_wheel_heading)) => {
-                    gyro = None; // Set gyro to `None` so we don't use it in the future.
-
-                    // Use the backup wheeled heading value in the gyro failed.
-                    if let Some(raw_wheel_heading) = raw_wheel_heading {
-                        raw_wheel_heading
-                    } else {
-                        // If no backup heading is available, that means we have no means of
-                        // determining heading in the future (because we have no parallel forward
-                        // trackers), meaning there's no point in continuing this task. Womp womp.
-                        return;
-                    }
-                }
+                data.scrub_factor,
+            ) else {
+                // No heading source is working right now, so just wait for one to recover.
+                continue;
+            };
 
-                // Occurs if both the gyro failed and the backup heading source failed.
-                Err(HeadingError::RotarySensor(_)) if gyro.is_some() => {
-                    gyro = None; // No more gyro :(
-                    continue;
-                }
+            // MARK: Heading Source Switching
 
-                // One of the tracking wheels failed and we don't have a gyro, so just wait for it
-                // to reconnect I guess.
-                _ => continue,
-            };
+            // Our heading source changed (such as from the gyro failing or finishing its
+            // calibration), so offset the new source to continue from the last heading we had.
+            // The gyro and wheels don't share a frame of reference (and a gyro that lost power
+            // will have recalibrated), so otherwise the heading would jump.
+            //
+            // Wheel headings are scaled by the scrub factor, so changing it has the same effect.
+            let scrub_factor_changed =
+                heading_source == HeadingSource::Wheels && data.scrub_factor != prev_scrub_factor;
+            if data.heading_source != Some(heading_source) || scrub_factor_changed {
+                source_offset = prev_raw_heading - heading_reading;
+                data.heading_source = Some(heading_source);
+            }
+            prev_scrub_factor = data.scrub_factor;
+
+            data.raw_heading = heading_reading + source_offset;
 
             // Change in raw heading from the previous loop iteration.
             let delta_heading = (data.raw_heading - prev_raw_heading).wrapped_half();
 
+            // Used for estimating instantaneous velocity from wheel deltas.
+            let dt = prev_time.elapsed();
+            prev_time = Instant::now();
+
+            // MARK: Slip Detection
+
+            // Determine which wheels disagree with the rest of the tracking system, and should
+            // be excluded from this iteration's position update (if enabled).
+            data.slipping_wheels.clear();
+            let forward_reference_delta = data
+                .forward_slip_reference
+                .as_mut()
+                .and_then(|reference| reference.update(delta_heading));
+            let sideways_reference_delta = data
+                .sideways_slip_reference
+                .as_mut()
+                .and_then(|reference| reference.update(delta_heading));
+            let (excluded_forward_wheels, excluded_sideways_wheels) =
+                if let Some(threshold) = data.slip_threshold {
+                    let forward = Self::detect_slip(
+                        WheelAxis::Forward,
+                        &forward_wheel_data,
+                        &prev_forward_wheel_data,
+                        forward_reference_delta,
+                        delta_heading,
+                        dt,
+                        threshold,
+                        &mut data.slipping_wheels,
+                    );
+                    let sideways = Self::detect_slip(
+                        WheelAxis::Sideways,
+                        &sideways_wheel_data,
+                        &prev_sideways_wheel_data,
+                        sideways_reference_delta,
+                        delta_heading,
+                        dt,
+                        threshold,
+                        &mut data.slipping_wheels,
+                    );
+
+                    if data.exclude_slipping_wheels {
+                        (forward, sideways)
+                    } else {
+                        ([false; NUM_FORWARD], [false; NUM_SIDEWAYS])
+                    }
+                } else {
+                    ([false; NUM_FORWARD], [false; NUM_SIDEWAYS])
+                };
+
             // Average between the current and previous heading reading used conversion between
             // global and local coordinate displacements.
             //
@@ -423,8 +961,13 @@ impl WheeledTracking {
                 let mut local_y_sum = 0.0;
                 let mut count = 0;
 
-                for (data, prev_data) in sideways_wheel_data.iter().zip(&prev_sideways_wheel_data) {
-                    if let Ok((travel, _)) = data
+                for ((data, prev_data), excluded) in sideways_wheel_data
+                    .iter()
+                    .zip(&prev_sideways_wheel_data)
+                    .zip(excluded_sideways_wheels)
+                {
+                    if !excluded
+                        && let Ok((travel, _)) = data
                         && let Ok((prev_travel, offset)) = prev_data
                     {
                         let delta_travel = travel - prev_travel;
@@ -457,7 +1000,11 @@ impl WheeledTracking {
 
                 // Sum up all of our wheel values to determine average forward wheel travel and
                 // average local x-axis displacement.
-                for (data, prev_data) in forward_wheel_data.iter().zip(&prev_forward_wheel_data) {
+                for ((data, prev_data), excluded) in forward_wheel_data
+                    .iter()
+                    .zip(&prev_forward_wheel_data)
+                    .zip(excluded_forward_wheels)
+                {
                     if let Ok((travel, _)) = data {
                         travel_sum += travel;
                         count += 1;
@@ -467,7 +1014,7 @@ impl WheeledTracking {
                         // our current and previous wheel travel, meaning we can only consider
                         // wheels that returned `Ok(_)` in both the current AND previous loop
                         // iteration here.
-                        if let Ok((prev_travel, offset)) = prev_data {
+                        if !excluded && let Ok((prev_travel, offset)) = prev_data {
                             let delta_travel = travel - prev_travel;
                             prev_count += 1;
 
@@ -502,10 +1049,6 @@ impl WheeledTracking {
                 prev_forward_wheel_data = forward_wheel_data;
             };
 
-            // Used for estimating instantaneous velocity from wheel deltas.
-            let dt = prev_time.elapsed();
-            prev_time = Instant::now();
-
             // Linear/angular drivetrain velocity estimation
             //
             // TODO: Any kind of "dx/dt"-style differentiations here are flawed and will return zero
@@ -513,9 +1056,11 @@ impl WheeledTracking {
             //       of our sensor. We should also maybe consider EMA filtering this or something.
             data.linear_velocity = (data.forward_travel - prev_forward_travel) / dt.as_secs_f64();
             prev_forward_travel = data.forward_travel;
+            data.linear_acceleration = acceleration.update(data.linear_velocity, dt);
 
             data.angular_velocity = gyro
                 .as_ref()
+                .filter(|_| heading_source == HeadingSource::Gyro)
                 .and_then(|gyro| gyro.angular_velocity().ok())
                 .unwrap_or_else(|| delta_heading.as_radians() / dt.as_secs_f64());
 
@@ -524,7 +1069,36 @@ impl WheeledTracking {
             // estimate of our change in position to get a new estimate of the global position.
             //
             // If all this seems like gibberish to you, check out <https://www.youtube.com/watch?v=ZW7T6EFyYnc>.
-            data.position += local_displacement.rotated(avg_heading.as_radians());
+            let displacement = local_displacement.rotated(avg_heading.as_radians());
+
+            // MARK: Jump Detection
+
+            // A robot can only move so far in a single iteration. If our estimated displacement is
+            // further than that, then something went very wrong with our sensors (such as from
+            // the robot being hit hard), so we shouldn't trust this iteration's update.
+            if data
+                .max_velocity
+                .is_some_and(|max_velocity| displacement.length() > max_velocity * dt.as_secs_f64())
+            {
+                data.last_rejected_delta = Some(displacement);
+            } else {
+                data.position += displacement;
+            }
+
+            // MARK: Pose History
+
+            // Only record a new pose once we've moved far enough from the last one, so that time
+            // spent sitting still doesn't push older poses out of the history.
+            if data.pose_history.latest().is_none_or(|latest| {
+                latest.position.distance(data.position) >= data.pose_history_spacing
+            }) {
+                let sample = PoseSample {
+                    timestamp: Instant::now(),
+                    position: data.position,
+                    heading: data.raw_heading + data.heading_offset,
+                };
+                data.pose_history.push(sample);
+            }
         }
     }
 
@@ -533,12 +1107,254 @@ impl WheeledTracking {
     /// Offsets the currently tracked heading to a given [`Angle`].
     pub fn set_heading(&mut self, heading: Angle) {
         let mut data = self.data.borrow_mut();
-        data.heading_offset = heading - data.raw_heading;
+        data.heading_offset = data.units.convert_heading(heading) - data.raw_heading;
     }
 
     /// Sets the currently tracked position to a new point.
     pub fn set_position(&mut self, position: impl Into<Vec2<f64>>) {
-        self.data.borrow_mut().position = position.into();
+        let mut data = self.data.borrow_mut();
+        data.position = data.units.position_to_inches(position.into());
+    }
+
+    /// Zeroes the positions returned by [`WheeledTracking::wheel_positions`].
+    ///
+    /// This only changes the positions reported for each wheel. The tracked pose (and forward
+    /// travel) is unaffected, so this can be called at any time, even during a motion. Wheels
+    /// that failed to return a reading during the most recent tracking update keep their
+    /// previous zero.
+    pub fn zero_wheels(&mut self) {
+        let data = &mut *self.data.borrow_mut();
+
+        for (zero, travel) in data
+            .forward_wheel_zeros
+            .iter_mut()
+            .zip(&data.forward_wheel_travel)
+        {
+            if let Some(travel) = travel {
+                *zero = *travel;
+            }
+        }
+        for (zero, travel) in data
+            .sideways_wheel_zeros
+            .iter_mut()
+            .zip(&data.sideways_wheel_travel)
+        {
+            if let Some(travel) = travel {
+                *zero = *travel;
+            }
+        }
+    }
+
+    /// Sets the scrub factor used to calibrate headings measured by tracking wheels.
+    ///
+    /// When the robot turns, its wheels scrub sideways against the ground, so the track width
+    /// that best describes a turn is usually somewhat different from the measured distance
+    /// between the wheels. Headings computed from a pair of parallel forward tracking wheels use
+    /// the measured track width multiplied by this factor. To calibrate it:
+    ///
+    /// 1. Turn the robot in place through a known angle (several full turns gives a more precise
+    ///    measurement), while tracking heading from the wheels rather than a gyro.
+    /// 2. Read the change in heading reported by the tracking system.
+    /// 3. Set the scrub factor to the reported angle divided by the known angle.
+    ///
+    /// This has no effect while heading is tracked by a gyro (see
+    /// [`WheeledTracking::heading_source`]). The factor defaults to `1.0`, and changing it
+    /// doesn't change the currently tracked heading.
+    pub fn set_scrub_factor(&mut self, scrub_factor: f64) {
+        self.data.borrow_mut().scrub_factor = scrub_factor;
+    }
+
+    /// Sets the velocity difference (in wheel units per second) at which a tracking wheel is
+    /// considered to be slipping.
+    ///
+    /// A wheel is flagged as slipping when its velocity disagrees with the velocity measured by
+    /// the axis's slip reference (see [`WheeledTracking::set_slip_reference`]) by more than this
+    /// threshold, such as when the wheel lifts off the ground or jams. On axes without a
+    /// reference, wheels are instead compared against the average velocity of the other wheels
+    /// on the same axis, which requires at least three tracking wheels on that axis. Slip
+    /// detection is disabled when the threshold is `None` (the default).
+    pub fn set_slip_threshold(&mut self, threshold: Option<f64>) {
+        let mut data = self.data.borrow_mut();
+        data.slip_threshold = threshold.map(|threshold| data.units.length_to_inches(threshold));
+    }
+
+    /// Sets an independent measurement of the robot's travel along `axis` that tracking wheels on
+    /// that axis are checked against for slip, or removes it if `reference` is `None`.
+    ///
+    /// The reference is typically the drivetrain's own motors, since they measure the robot's
+    /// motion independently of the tracking wheels. Like any other tracking wheel, its geometry
+    /// is measured in wheel units, and its offset is compensated for while turning. Passing every
+    /// motor on a differential drivetrain as a single wheel with no offset averages the left and
+    /// right sides, measuring the travel of the center of the robot.
+    ///
+    /// A reference allows slip to be detected on axes with only one or two tracking wheels.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let drive_motors = vec![left_front, left_back, right_front, right_back];
+    ///
+    /// tracking.set_slip_threshold(Some(6.0));
+    /// tracking.set_slip_reference(
+    ///     WheelAxis::Forward,
+    ///     Some(TrackingWheel::new(drive_motors, 3.25, 0.0, GearRatio::new(36.0, 48.0))),
+    /// );
+    /// ```
+    pub fn set_slip_reference<T: RotarySensor + 'static>(
+        &mut self,
+        axis: WheelAxis,
+        reference: Option<TrackingWheel<T>>,
+    ) {
+        let mut data = self.data.borrow_mut();
+        let reference =
+            reference.map(|wheel| SlipReference::new(data.units.wheel_to_inches(wheel)));
+
+        match axis {
+            WheelAxis::Forward => data.forward_slip_reference = reference,
+            WheelAxis::Sideways => data.sideways_slip_reference = reference,
+        }
+    }
+
+    /// Sets whether wheels detected as slipping should be excluded from position updates.
+    ///
+    /// When enabled, any wheel flagged by slip detection is ignored when integrating the robot's
+    /// position for that update. If every wheel on an axis is flagged, then none of them will be
+    /// excluded, since there's no way to tell which wheel is at fault.
+    pub fn set_exclude_slipping_wheels(&mut self, exclude: bool) {
+        self.data.borrow_mut().exclude_slipping_wheels = exclude;
+    }
+
+    /// Sets the maximum physically plausible velocity of the robot (in linear units per second).
+    ///
+    /// When set, any tracking update that would move the robot further than it could possibly
+    /// travel at this velocity is rejected, leaving the tracked position unchanged for that
+    /// update. This prevents a single bad reading (such as from the robot being hit hard by
+    /// another robot) from corrupting the position estimate. This is disabled when the velocity
+    /// is `None` (the default).
+    ///
+    /// The velocity should be set somewhat higher than the robot's true top speed to avoid
+    /// rejecting valid updates.
+    pub fn set_max_velocity(&mut self, max_velocity: Option<f64>) {
+        let mut data = self.data.borrow_mut();
+        data.max_velocity = max_velocity.map(|velocity| data.units.length_to_inches(velocity));
+    }
+
+    /// Enables recording a history of the robot's recent poses, which can be retrieved using
+    /// [`WheeledTracking::pose_history`].
+    ///
+    /// A new pose is recorded once the robot has moved at least `spacing` linear units from the
+    /// previously recorded pose. The history holds up to `capacity` poses, after which the oldest
+    /// pose is overwritten. A `capacity` of zero (the default) disables the history.
+    ///
+    /// Any poses that were previously recorded are discarded.
+    pub fn set_pose_history(&mut self, capacity: usize, spacing: f64) {
+        let mut data = self.data.borrow_mut();
+        data.pose_history = PoseBuffer::new(capacity);
+        data.pose_history_spacing = data.units.length_to_inches(spacing);
+    }
+
+    /// Removes every pose recorded in the pose history.
+    pub fn clear_pose_history(&mut self) {
+        self.data.borrow_mut().pose_history.clear();
+    }
+
+    // MARK: Getters
+
+    /// Returns a copy of the robot's recent pose history, from oldest to newest.
+    ///
+    /// This will always be empty unless a pose history has been enabled using
+    /// [`WheeledTracking::set_pose_history`].
+    #[must_use]
+    pub fn pose_history(&self) -> PoseBuffer {
+        let data = self.data.borrow();
+        let mut history = PoseBuffer::new(data.pose_history.capacity());
+
+        for sample in data.pose_history.iter() {
+            history.push(PoseSample {
+                position: data.units.position_from_inches(sample.position),
+                heading: data.units.convert_heading(sample.heading).wrapped_full(),
+                ..*sample
+            });
+        }
+
+        history
+    }
+
+    /// Returns the status of every tracking wheel that was detected as slipping during the most
+    /// recent tracking update.
+    ///
+    /// This will always be empty unless a slip threshold has been configured using
+    /// [`WheeledTracking::set_slip_threshold`].
+    #[must_use]
+    pub fn slipping_wheels(&self) -> Vec<WheelStatus> {
+        let data = self.data.borrow();
+
+        data.slipping_wheels
+            .iter()
+            .map(|status| WheelStatus {
+                velocity_error: data.units.length_from_inches(status.velocity_error),
+                ..*status
+            })
+            .collect()
+    }
+
+    /// Returns the position of each tracking wheel on an axis (in linear units) as of the most
+    /// recent tracking update, in the order they were passed to [`WheeledTracking::new`].
+    ///
+    /// Each position is the total distance measured by the wheel since it was last zeroed by
+    /// [`WheeledTracking::zero_wheels`] (or since its sensor was reset, if it was never zeroed),
+    /// with the wheel's [scale](TrackingWheel::scale) applied. Wheels that failed to return a
+    /// reading are `None`.
+    ///
+    /// Reading the wheels independently is useful for diagnosing odometry problems, such as a
+    /// dead encoder that never moves, or for calibration: zero the wheels, push the robot a known
+    /// distance, then compare each wheel's position against that distance.
+    #[must_use]
+    pub fn wheel_positions(&self, axis: WheelAxis) -> Vec<Option<f64>> {
+        let data = self.data.borrow();
+        let (travel, zeros) = match axis {
+            WheelAxis::Forward => (&data.forward_wheel_travel, &data.forward_wheel_zeros),
+            WheelAxis::Sideways => (&data.sideways_wheel_travel, &data.sideways_wheel_zeros),
+        };
+
+        travel
+            .iter()
+            .zip(zeros)
+            .map(|(travel, zero)| travel.map(|travel| data.units.length_from_inches(travel - zero)))
+            .collect()
+    }
+
+    /// Returns the most recent change in position that was rejected for exceeding the maximum
+    /// velocity set by [`WheeledTracking::set_max_velocity`], if any.
+    #[must_use]
+    pub fn last_rejected_delta(&self) -> Option<Vec2<f64>> {
+        let data = self.data.borrow();
+
+        data.last_rejected_delta
+            .map(|delta| data.units.position_from_inches(delta))
+    }
+
+    /// Returns the sensor that the robot's heading is currently being determined from.
+    ///
+    /// This is `None` if no heading source has returned a reading yet. See the
+    /// [type-level documentation](WheeledTracking#heading-sources) for more information.
+    #[must_use]
+    pub fn heading_source(&self) -> Option<HeadingSource> {
+        self.data.borrow().heading_source
+    }
+
+    /// Returns the scrub factor used to calibrate headings measured by tracking wheels.
+    ///
+    /// See [`WheeledTracking::set_scrub_factor`] for more information.
+    #[must_use]
+    pub fn scrub_factor(&self) -> f64 {
+        self.data.borrow().scrub_factor
+    }
+
+    /// Returns the units used by this tracking system.
+    #[must_use]
+    pub fn units(&self) -> TrackingUnits {
+        self.data.borrow().units
     }
 }
 
@@ -548,7 +1364,8 @@ impl Tracking for WheeledTracking {}
 
 impl TracksPosition for WheeledTracking {
     fn position(&self) -> Vec2<f64> {
-        self.data.borrow().position
+        let data = self.data.borrow();
+        data.units.position_from_inches(data.position)
     }
 }
 
@@ -556,23 +1373,39 @@ impl TracksHeading for WheeledTracking {
     fn heading(&self) -> Angle {
         let data = self.data.borrow();
 
-        // Apply heading offset and wrap from [0, 2π]
-        (data.raw_heading + data.heading_offset).wrapped_full()
+        // Apply heading offset, convert to the user's heading convention, and wrap from [0, 2π]
+        data.units
+            .convert_heading(data.raw_heading + data.heading_offset)
+            .wrapped_full()
     }
 }
 
 impl TracksForwardTravel for WheeledTracking {
     fn forward_travel(&self) -> f64 {
-        self.data.borrow().forward_travel
+        let data = self.data.borrow();
+        data.units.length_from_inches(data.forward_travel)
     }
 }
 
 impl TracksVelocity for WheeledTracking {
     fn angular_velocity(&self) -> f64 {
-        self.data.borrow().angular_velocity
+        let data = self.data.borrow();
+        data.units.convert_rotation(data.angular_velocity)
     }
 
     fn linear_velocity(&self) -> f64 {
-        self.data.borrow().linear_velocity
+        let data = self.data.borrow();
+        data.units.length_from_inches(data.linear_velocity)
+    }
+}
+
+impl TracksAcceleration for WheeledTracking {
+    /// Returns the robot's estimated linear acceleration in linear units per second squared.
+    ///
+    /// This is estimated by differentiating low-pass filtered forward velocity, and lags behind
+    /// the robot's true acceleration by roughly 100 milliseconds.
+    fn linear_acceleration(&self) -> f64 {
+        let data = self.data.borrow();
+        data.units.length_from_inches(data.linear_acceleration)
     }
 }
@@ -54,7 +54,7 @@
 //! // ...
 //!
 //! let mut drivetrain = Drivetrain::new(
-//!     Differential::new(left_motors, right_motors),
+//!     Differential::new(left_motors, right_motors, 3.25, 12.0),
 //!     MyLocalization {},
 //! );
 //! ```
@@ -155,9 +155,9 @@ pub mod prelude {
     #[cfg(feature = "control")]
     pub use crate::control::Tolerances;
     #[cfg(feature = "math")]
-    pub use crate::math::IntoAngle;
+    pub use crate::math::{ApproxEqAngle, DisplayAngle, IntoAngle, MirrorAngle};
     #[cfg(feature = "tracking")]
     pub use crate::tracking::{
-        TracksForwardTravel, TracksHeading, TracksPosition, TracksVelocity,
+        TracksAcceleration, TracksForwardTravel, TracksHeading, TracksPosition, TracksVelocity,
     };
 }